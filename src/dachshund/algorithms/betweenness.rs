@@ -5,11 +5,44 @@
  * LICENSE file in the root directory of this source tree.
  */
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
+use crate::dachshund::algorithms::laplacian::Laplacian;
 use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode};
 use crate::dachshund::simple_undirected_graph::UndirectedGraph;
-use std::collections::HashMap;
+use core::cmp::Reverse;
+use ordered_float::NotNan;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+type EdgeBetweenness = HashMap<(NodeId, NodeId), f64>;
+type NodePredecessors = HashMap<NodeId, Vec<NodeId>>;
+
+// Brandes' dependency accumulation for edges: nodes are popped from stack in order
+// of nonincreasing distance from the source, and each edge (pred, w) on a shortest
+// path gets the fraction of shortest paths through it of 1 + w's dependency.
+// Every pair is visited from both of its ends, hence the 0.5.
+fn accumulate_edge_dependencies(
+    betweenness: &mut EdgeBetweenness,
+    mut stack: Vec<NodeId>,
+    path_counts: &HashMap<NodeId, f64>,
+    preds: &HashMap<NodeId, Vec<NodeId>>,
+) {
+    let mut dependencies: HashMap<NodeId, f64> = HashMap::new();
+    while let Some(w) = stack.pop() {
+        let dependency_w = *dependencies.get(&w).unwrap_or(&0.0);
+        for pred in &preds[&w] {
+            let credit = path_counts[pred] / path_counts[&w] * (1.0 + dependency_w);
+            *betweenness
+                .entry((w.min(*pred), w.max(*pred)))
+                .or_insert(0.0) += 0.5 * credit;
+            *dependencies.entry(*pred).or_insert(0.0) += credit;
+        }
+    }
+}
 
 pub trait Betweenness:
     UndirectedGraph + Connectivity + ShortestPaths + ConnectivityUndirected
@@ -94,4 +127,217 @@ where
 
         Ok(betweenness)
     }
+
+    // Number of shortest paths going through each edge (as in Girvan-Newman), where
+    // each pair of nodes splits its credit evenly among its shortest paths. Edges
+    // are keyed as (smaller id, larger id).
+    fn get_edge_betweenness(&self) -> EdgeBetweenness {
+        let mut betweenness: EdgeBetweenness = HashMap::new();
+        for node in self.get_nodes_iter() {
+            for e in node.get_edges() {
+                let (a, b) = (node.get_id(), e.get_neighbor_id());
+                betweenness.insert((a.min(b), a.max(b)), 0.0);
+            }
+        }
+        for source in self.get_ids_iter() {
+            let (stack, shortest_path_counts, preds) = self.get_shortest_paths_bfs(*source);
+            let path_counts: HashMap<NodeId, f64> = shortest_path_counts
+                .into_iter()
+                .map(|(id, count)| (id, count as f64))
+                .collect();
+            accumulate_edge_dependencies(&mut betweenness, stack, &path_counts, &preds);
+        }
+        betweenness
+    }
+
+    // Current-flow (random-walk) betweenness, after Newman: for each pair of nodes
+    // (s, t) in the same component, a unit current is injected at s and extracted
+    // at t, with every edge a unit resistor. The node potentials are given by the
+    // pseudo-inverse of the Laplacian, L+ (e_s - e_t), and each other node is
+    // credited with the current flowing through it, i.e. half of the absolute
+    // current on its edges. As with get_node_betweenness, each unordered pair
+    // contributes once and endpoints are not credited. Unlike shortest-path
+    // betweenness, all routes between s and t carry some of the flow.
+    fn current_flow_betweenness(&self) -> HashMap<NodeId, f64>
+    where
+        Self: Laplacian,
+    {
+        let mut betweenness: HashMap<NodeId, f64> =
+            self.get_ids_iter().map(|id| (*id, 0.0)).collect();
+        if self.count_nodes() == 0 {
+            return betweenness;
+        }
+        let (laplacian, node_ids) = self.get_laplacian_matrix();
+        let pinv = laplacian.pseudo_inverse(1e-9).unwrap();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let neighbors: Vec<Vec<usize>> = node_ids
+            .iter()
+            .map(|id| {
+                self.get_node(*id)
+                    .get_edges()
+                    .map(|e| positions[&e.get_neighbor_id()])
+                    .collect()
+            })
+            .collect();
+        for (s, source) in node_ids.iter().enumerate() {
+            let reachable = self.get_hop_distances(*source);
+            for (t, target) in node_ids.iter().enumerate().skip(s + 1) {
+                if !reachable.contains_key(target) {
+                    continue;
+                }
+                let potential = |i: usize| pinv[(i, s)] - pinv[(i, t)];
+                for (i, id) in node_ids.iter().enumerate() {
+                    if i == s || i == t {
+                        continue;
+                    }
+                    let throughput: f64 = neighbors[i]
+                        .iter()
+                        .map(|j| (potential(i) - potential(*j)).abs())
+                        .sum();
+                    *betweenness.get_mut(id).unwrap() += 0.5 * throughput;
+                }
+            }
+        }
+        betweenness
+    }
+}
+
+pub trait WeightedBetweenness: GraphBase<NodeType = WeightedNode> {
+    // Dijkstra's algorithm from source, counting shortest paths as in Brandes'
+    // algorithm, with shortest paths minimizing the total weight of their edges.
+    // Fails on a NaN edge weight.
+    fn _get_weighted_shortest_paths(
+        &self,
+        source: NodeId,
+    ) -> CLQResult<(
+        Vec<NodeId>,          // settled nodes in nondecreasing order by distance
+        HashMap<NodeId, f64>, // number of shortest paths from source
+        NodePredecessors,     // immediate predecessors
+    )> {
+        let mut stack: Vec<NodeId> = Vec::new();
+        let mut dist: HashMap<NodeId, NotNan<f64>> = HashMap::new();
+        let mut path_counts: HashMap<NodeId, f64> = HashMap::new();
+        let mut preds: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        let mut settled: HashSet<NodeId> = HashSet::new();
+        let mut heap: BinaryHeap<(Reverse<NotNan<f64>>, NodeId)> = BinaryHeap::new();
+
+        dist.insert(source, NotNan::new(0.0).unwrap());
+        path_counts.insert(source, 1.0);
+        preds.insert(source, Vec::new());
+        heap.push((Reverse(dist[&source]), source));
+        while let Some((Reverse(d), v)) = heap.pop() {
+            if !settled.insert(v) {
+                continue;
+            }
+            stack.push(v);
+            for e in &self.get_node(v).edges {
+                let w = e.target_id;
+                // with zero-weight edges, a settled node can be as far as v.
+                if settled.contains(&w) {
+                    continue;
+                }
+                let weight = NotNan::new(e.weight).map_err(|_| {
+                    CLQError::InvalidParameter(format!(
+                        "edge ({}, {}) has a NaN weight.",
+                        v.value(),
+                        w.value()
+                    ))
+                })?;
+                let alt = d + weight;
+                match dist.get(&w) {
+                    Some(dw) if alt > *dw => (),
+                    Some(dw) if alt == *dw => {
+                        *path_counts.get_mut(&w).unwrap() += path_counts[&v];
+                        preds.get_mut(&w).unwrap().push(v);
+                    }
+                    _ => {
+                        dist.insert(w, alt);
+                        path_counts.insert(w, path_counts[&v]);
+                        preds.insert(w, vec![v]);
+                        heap.push((Reverse(alt), w));
+                    }
+                }
+            }
+        }
+        Ok((stack, path_counts, preds))
+    }
+
+    // Same as Betweenness::get_edge_betweenness, with shortest paths minimizing the
+    // total weight of their edges (found with Dijkstra's algorithm) instead of hops.
+    // Fails on a NaN edge weight.
+    fn weighted_edge_betweenness(&self) -> CLQResult<EdgeBetweenness> {
+        let mut betweenness: EdgeBetweenness = HashMap::new();
+        for node in self.get_nodes_iter() {
+            for e in &node.edges {
+                let (a, b) = (node.node_id, e.target_id);
+                betweenness.insert((a.min(b), a.max(b)), 0.0);
+            }
+        }
+        for source in self.get_ids_iter() {
+            let (stack, path_counts, preds) = self._get_weighted_shortest_paths(*source)?;
+            accumulate_edge_dependencies(&mut betweenness, stack, &path_counts, &preds);
+        }
+        Ok(betweenness)
+    }
+
+    // Brandes' node betweenness, summing the dependencies of the given sources
+    // on every other node. Each pair counts for 0.5 from either end, as in
+    // Betweenness::get_node_betweenness_brandes.
+    fn _weighted_node_betweenness_from(
+        &self,
+        sources: &[NodeId],
+    ) -> CLQResult<HashMap<NodeId, f64>> {
+        let mut betweenness: HashMap<NodeId, f64> =
+            self.get_ids_iter().map(|id| (*id, 0.0)).collect();
+        for source in sources {
+            let (mut stack, path_counts, preds) = self._get_weighted_shortest_paths(*source)?;
+            let mut dependencies: HashMap<NodeId, f64> = HashMap::new();
+            while let Some(w) = stack.pop() {
+                let dependency_w = *dependencies.get(&w).unwrap_or(&0.0);
+                for pred in &preds[&w] {
+                    *dependencies.entry(*pred).or_insert(0.0) +=
+                        path_counts[pred] / path_counts[&w] * (0.5 + dependency_w);
+                }
+                if w != *source {
+                    *betweenness.get_mut(&w).unwrap() += dependency_w;
+                }
+            }
+        }
+        Ok(betweenness)
+    }
+
+    // Node betweenness with shortest paths minimizing the total weight of their
+    // edges. Unlike Betweenness::get_node_betweenness_brandes, disconnected graphs
+    // are fine: unreachable pairs just don't count. Fails on a NaN edge weight.
+    fn weighted_node_betweenness(&self) -> CLQResult<HashMap<NodeId, f64>> {
+        self._weighted_node_betweenness_from(&self.get_ordered_node_ids())
+    }
+
+    // Estimates weighted_node_betweenness from num_samples sources picked uniformly
+    // at random (without replacement, so at most every node), scaling their
+    // contributions by n / num_samples. Exact when num_samples >= n.
+    fn approximate_weighted_betweenness(
+        &self,
+        num_samples: usize,
+        seed: u64,
+    ) -> CLQResult<HashMap<NodeId, f64>> {
+        let node_ids = self.get_ordered_node_ids();
+        let num_samples = num_samples.min(node_ids.len());
+        let sources: Vec<NodeId> = node_ids
+            .choose_multiple(&mut StdRng::seed_from_u64(seed), num_samples)
+            .cloned()
+            .collect();
+        let mut betweenness = self._weighted_node_betweenness_from(&sources)?;
+        if num_samples > 0 {
+            let scale = node_ids.len() as f64 / num_samples as f64;
+            for value in betweenness.values_mut() {
+                *value *= scale;
+            }
+        }
+        Ok(betweenness)
+    }
 }
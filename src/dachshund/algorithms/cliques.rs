@@ -0,0 +1,196 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashSet;
+use std::collections::{BTreeSet, HashMap};
+
+pub trait Cliques: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId, NodeSetType = FxHashSet<NodeId>>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Nodes in increasing order of id, along with the positions of their neighbors
+    // in that order (self-loops excluded).
+    fn _get_indexed_adjacency(&self) -> (Vec<NodeId>, Vec<FxHashSet<usize>>) {
+        let node_ids = self.get_ordered_node_ids();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let adjacency = node_ids
+            .iter()
+            .map(|id| {
+                self.get_node(*id)
+                    .get_edges()
+                    .map(|e| positions[&e.get_neighbor_id()])
+                    .filter(|j| node_ids[*j] != *id)
+                    .collect()
+            })
+            .collect();
+        (node_ids, adjacency)
+    }
+
+    // Bron-Kerbosch with Tomita pivoting. Every maximal clique is returned once, as a
+    // sorted vector of node ids. Isolated nodes are maximal cliques of size 1.
+    fn enumerate_maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let (node_ids, adjacency) = self._get_indexed_adjacency();
+        let mut cliques: Vec<Vec<NodeId>> = Vec::new();
+        visit_maximal_cliques(&adjacency, |clique| {
+            let mut ids: Vec<NodeId> = clique.iter().map(|i| node_ids[*i]).collect();
+            ids.sort();
+            cliques.push(ids);
+        });
+        cliques
+    }
+
+    // Number of maximal cliques of each size, from the same traversal as
+    // enumerate_maximal_cliques but without keeping the cliques around.
+    fn maximal_clique_size_distribution(&self) -> HashMap<usize, usize> {
+        let (_node_ids, adjacency) = self._get_indexed_adjacency();
+        let mut distribution: HashMap<usize, usize> = HashMap::new();
+        visit_maximal_cliques(&adjacency, |clique| {
+            *distribution.entry(clique.len()).or_insert(0) += 1;
+        });
+        distribution
+    }
+
+    // Order in which nodes are removed when repeatedly deleting a node of minimum
+    // remaining degree (ties broken by position).
+    fn _get_degeneracy_ordering(&self, adjacency: &[FxHashSet<usize>]) -> Vec<usize> {
+        let mut degrees: Vec<usize> = adjacency.iter().map(|a| a.len()).collect();
+        let mut queue: BTreeSet<(usize, usize)> =
+            degrees.iter().enumerate().map(|(i, d)| (*d, i)).collect();
+        let mut removed: Vec<bool> = vec![false; adjacency.len()];
+        let mut ordering: Vec<usize> = Vec::with_capacity(adjacency.len());
+        while let Some((_, v)) = queue.pop_first() {
+            removed[v] = true;
+            ordering.push(v);
+            for u in &adjacency[v] {
+                if !removed[*u] {
+                    queue.remove(&(degrees[*u], *u));
+                    degrees[*u] -= 1;
+                    queue.insert((degrees[*u], *u));
+                }
+            }
+        }
+        ordering
+    }
+
+    // Exact maximum clique by branch and bound (Tomita & Seki's MCQ). Candidates are
+    // greedily colored, and since a clique can use each color at most once, a branch
+    // is pruned as soon as the current clique plus the number of colors left can't
+    // beat the best clique found so far. Candidates start in reverse degeneracy
+    // order, so that the dense core of the graph is explored first.
+    fn max_clique_bnb(&self) -> Vec<NodeId> {
+        let (node_ids, adjacency) = self._get_indexed_adjacency();
+        let mut candidates = self._get_degeneracy_ordering(&adjacency);
+        candidates.reverse();
+
+        let mut best: Vec<usize> = Vec::new();
+        let mut clique: Vec<usize> = Vec::new();
+        expand_clique(&adjacency, &mut clique, candidates, &mut best);
+        let mut max_clique: Vec<NodeId> = best.into_iter().map(|i| node_ids[i]).collect();
+        max_clique.sort();
+        max_clique
+    }
+}
+
+// Bron-Kerbosch with Tomita pivoting over positions 0..adjacency.len(), calling
+// visit once on each maximal clique (in no particular order).
+fn visit_maximal_cliques<F: FnMut(&[usize])>(adjacency: &[FxHashSet<usize>], mut visit: F) {
+    let mut stack: Vec<(Vec<usize>, FxHashSet<usize>, FxHashSet<usize>)> = vec![(
+        Vec::new(),
+        (0..adjacency.len()).collect(),
+        FxHashSet::default(),
+    )];
+    while let Some((clique, mut candidates, mut excluded)) = stack.pop() {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                visit(&clique);
+            }
+            continue;
+        }
+        // branch only on nodes that aren't neighbors of the pivot, which is chosen
+        // to have as many candidate neighbors as possible.
+        let pivot = *candidates
+            .union(&excluded)
+            .max_by_key(|u| adjacency[**u].intersection(&candidates).count())
+            .unwrap();
+        let mut branches: Vec<usize> = candidates
+            .iter()
+            .filter(|v| !adjacency[pivot].contains(v))
+            .cloned()
+            .collect();
+        branches.sort_unstable();
+        for v in branches {
+            let mut grown = clique.clone();
+            grown.push(v);
+            stack.push((
+                grown,
+                candidates.intersection(&adjacency[v]).cloned().collect(),
+                excluded.intersection(&adjacency[v]).cloned().collect(),
+            ));
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+    }
+}
+
+// Greedily colors candidates (in their given order), returning them sorted by color
+// along with the number of colors used up to and including each of them.
+fn color_sort(adjacency: &[FxHashSet<usize>], candidates: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let mut color_classes: Vec<Vec<usize>> = Vec::new();
+    for v in candidates {
+        match color_classes
+            .iter_mut()
+            .find(|class| class.iter().all(|u| !adjacency[*v].contains(u)))
+        {
+            Some(class) => class.push(*v),
+            None => color_classes.push(vec![*v]),
+        }
+    }
+    let mut sorted: Vec<usize> = Vec::with_capacity(candidates.len());
+    let mut colors: Vec<usize> = Vec::with_capacity(candidates.len());
+    for (color, class) in color_classes.into_iter().enumerate() {
+        for v in class {
+            sorted.push(v);
+            colors.push(color + 1);
+        }
+    }
+    (sorted, colors)
+}
+
+fn expand_clique(
+    adjacency: &[FxHashSet<usize>],
+    clique: &mut Vec<usize>,
+    candidates: Vec<usize>,
+    best: &mut Vec<usize>,
+) {
+    let (mut sorted, colors) = color_sort(adjacency, &candidates);
+    while let Some(v) = sorted.pop() {
+        if clique.len() + colors[sorted.len()] <= best.len() {
+            return;
+        }
+        clique.push(v);
+        let next: Vec<usize> = sorted
+            .iter()
+            .filter(|u| adjacency[v].contains(u))
+            .cloned()
+            .collect();
+        if next.is_empty() {
+            if clique.len() > best.len() {
+                *best = clique.clone();
+            }
+        } else {
+            expand_clique(adjacency, clique, next, best);
+        }
+        clique.pop();
+    }
+}
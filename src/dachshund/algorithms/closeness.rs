@@ -0,0 +1,254 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode};
+use core::cmp::Reverse;
+use ordered_float::NotNan;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+// Closeness of a node that reaches num_reachable nodes (including itself) at a
+// total distance of total_distance, in a graph with num_nodes nodes. Uses the
+// Wasserman-Faust correction for disconnected graphs: the closeness within the
+// node's component is scaled by the fraction of the graph it can reach.
+fn closeness_from_distances(total_distance: f64, num_reachable: usize, num_nodes: usize) -> f64 {
+    if total_distance <= 0.0 || num_nodes <= 1 {
+        return 0.0;
+    }
+    let num_others = (num_reachable - 1) as f64;
+    (num_others / total_distance) * (num_others / (num_nodes - 1) as f64)
+}
+
+pub trait Closeness: GraphBase + ShortestPaths
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Inverse of the average hop distance to the nodes each node can reach, scaled
+    // by the fraction of nodes it can reach. Nodes that reach nobody score 0.0.
+    fn closeness_centrality(&self) -> HashMap<NodeId, f64> {
+        let n = self.count_nodes();
+        self.get_ids_iter()
+            .map(|id| {
+                let dists = self.get_hop_distances(*id);
+                let total_distance = dists.values().sum::<usize>() as f64;
+                (
+                    *id,
+                    closeness_from_distances(total_distance, dists.len(), n),
+                )
+            })
+            .collect()
+    }
+    // Approximates closeness_centrality from the hop distances to num_landmarks
+    // nodes picked at random (seeded), at the cost of one BFS per landmark instead
+    // of one per node. For each node, the landmarks (other than itself) stand in
+    // for the rest of the graph: the fraction it reaches estimates the fraction of
+    // the graph it reaches, and the average distance to them estimates its average
+    // distance. With every node as a landmark, this is exact.
+    fn landmark_closeness(&self, num_landmarks: usize, seed: u64) -> HashMap<NodeId, f64> {
+        let n = self.count_nodes();
+        let mut rng = StdRng::seed_from_u64(seed);
+        // sorted so that the outcome only depends on the seed.
+        let node_ids = self.get_ordered_node_ids();
+        let landmarks: Vec<NodeId> = node_ids
+            .choose_multiple(&mut rng, num_landmarks.min(n))
+            .cloned()
+            .collect();
+        // distances are symmetric, so a BFS from each landmark gives its distance
+        // from every node.
+        let landmark_dists: Vec<HashMap<NodeId, usize>> = landmarks
+            .iter()
+            .map(|landmark| self.get_hop_distances(*landmark))
+            .collect();
+        node_ids
+            .iter()
+            .map(|id| {
+                let mut num_landmarks_reached: usize = 0;
+                let mut total_distance: usize = 0;
+                let mut num_other_landmarks: usize = 0;
+                for (landmark, dists) in landmarks.iter().zip(&landmark_dists) {
+                    if landmark == id {
+                        continue;
+                    }
+                    num_other_landmarks += 1;
+                    if let Some(dist) = dists.get(id) {
+                        num_landmarks_reached += 1;
+                        total_distance += dist;
+                    }
+                }
+                let centrality = if total_distance == 0 {
+                    0.0
+                } else {
+                    (num_landmarks_reached as f64 / total_distance as f64)
+                        * (num_landmarks_reached as f64 / num_other_landmarks as f64)
+                };
+                (*id, centrality)
+            })
+            .collect()
+    }
+}
+
+pub trait WeightedCloseness: GraphBase<NodeType = WeightedNode> {
+    // Dijkstra's algorithm, treating edge weights as (non-negative) lengths. Returns
+    // the distance from source to every node it can reach (including itself).
+    // Fails if source isn't in the graph, or on a NaN edge weight.
+    fn get_weighted_distances(&self, source: NodeId) -> CLQResult<HashMap<NodeId, f64>> {
+        if !self.has_node(source) {
+            return Err(CLQError::NodeNotFound(source));
+        }
+        let mut dists: HashMap<NodeId, f64> = HashMap::new();
+        let mut heap: BinaryHeap<(Reverse<NotNan<f64>>, NodeId)> = BinaryHeap::new();
+        heap.push((Reverse(NotNan::new(0.0).unwrap()), source));
+        while let Some((Reverse(d), id)) = heap.pop() {
+            if dists.contains_key(&id) {
+                continue;
+            }
+            dists.insert(id, d.into_inner());
+            for e in &self.get_node(id).edges {
+                if !dists.contains_key(&e.target_id) {
+                    let weight = NotNan::new(e.weight).map_err(|_| {
+                        CLQError::InvalidParameter(format!(
+                            "edge ({}, {}) has a NaN weight.",
+                            id.value(),
+                            e.target_id.value()
+                        ))
+                    })?;
+                    heap.push((Reverse(d + weight), e.target_id));
+                }
+            }
+        }
+        Ok(dists)
+    }
+
+    // Largest weighted distance from id to any other node, or None if id isn't in
+    // the graph or some node can't be reached from it. Fails on a NaN edge weight.
+    fn weighted_eccentricity(&self, id: NodeId) -> CLQResult<Option<f64>> {
+        if !self.has_node(id) {
+            return Ok(None);
+        }
+        let dists = self.get_weighted_distances(id)?;
+        if dists.len() < self.count_nodes() {
+            return Ok(None);
+        }
+        Ok(Some(dists.values().cloned().fold(0.0, f64::max)))
+    }
+
+    // Largest weighted distance between any two nodes, or None if the graph is
+    // empty or disconnected (see weighted_diameter_largest_component). Fails on a
+    // NaN edge weight.
+    fn weighted_diameter(&self) -> CLQResult<Option<f64>> {
+        if self.count_nodes() == 0 {
+            return Ok(None);
+        }
+        let mut diameter: f64 = 0.0;
+        for id in self.get_ids_iter() {
+            match self.weighted_eccentricity(*id)? {
+                Some(eccentricity) => diameter = diameter.max(eccentricity),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(diameter))
+    }
+
+    // Same as weighted_diameter, within the connected component with the most
+    // nodes (the one with the smallest node id, if several are the largest).
+    // None only for the empty graph. Fails on a NaN edge weight.
+    fn weighted_diameter_largest_component(&self) -> CLQResult<Option<f64>> {
+        // one search per component finds the largest one, along with the
+        // distances from its smallest node.
+        let mut visited: HashSet<NodeId> = HashSet::new();
+        let mut largest: Option<(NodeId, HashMap<NodeId, f64>)> = None;
+        for id in self.get_ordered_node_ids() {
+            if visited.contains(&id) {
+                continue;
+            }
+            let dists = self.get_weighted_distances(id)?;
+            visited.extend(dists.keys());
+            if largest.as_ref().is_none_or(|x| dists.len() > x.1.len()) {
+                largest = Some((id, dists));
+            }
+        }
+        let Some((source, component)) = largest else {
+            return Ok(None);
+        };
+        let mut diameter: f64 = component.values().cloned().fold(0.0, f64::max);
+        for id in component.keys() {
+            if *id == source {
+                continue;
+            }
+            let dists = self.get_weighted_distances(*id)?;
+            diameter = dists.values().cloned().fold(diameter, f64::max);
+        }
+        Ok(Some(diameter))
+    }
+
+    // Same as Closeness::closeness_centrality, with distances given by the total
+    // weight of the lightest path instead of the number of hops. Fails on a NaN
+    // edge weight.
+    fn weighted_closeness_centrality(&self) -> CLQResult<HashMap<NodeId, f64>> {
+        let n = self.count_nodes();
+        self.get_ids_iter()
+            .map(|id| {
+                let dists = self.get_weighted_distances(*id)?;
+                let total_distance = dists.values().sum::<f64>();
+                Ok((
+                    *id,
+                    closeness_from_distances(total_distance, dists.len(), n),
+                ))
+            })
+            .collect()
+    }
+    // Same as weighted_closeness_centrality, but computes all distances at once
+    // with Floyd-Warshall, which is faster than n runs of Dijkstra on small, dense
+    // graphs (O(n^3) regardless of the number of edges).
+    fn all_pairs_weighted_closeness(&self) -> HashMap<NodeId, f64> {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut dists: Vec<Vec<f64>> = vec![vec![f64::INFINITY; n]; n];
+        for (i, id) in node_ids.iter().enumerate() {
+            dists[i][i] = 0.0;
+            for e in &self.get_node(*id).edges {
+                let j = positions[&e.target_id];
+                dists[i][j] = dists[i][j].min(e.weight);
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                if dists[i][k].is_infinite() {
+                    continue;
+                }
+                for j in 0..n {
+                    let alt = dists[i][k] + dists[k][j];
+                    if alt < dists[i][j] {
+                        dists[i][j] = alt;
+                    }
+                }
+            }
+        }
+        node_ids
+            .iter()
+            .zip(dists.iter())
+            .map(|(id, row)| {
+                let reachable: Vec<f64> = row.iter().cloned().filter(|d| d.is_finite()).collect();
+                let total_distance = reachable.iter().sum::<f64>();
+                (
+                    *id,
+                    closeness_from_distances(total_distance, reachable.len(), n),
+                )
+            })
+            .collect()
+    }
+}
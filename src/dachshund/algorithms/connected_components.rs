@@ -6,9 +6,13 @@
  */
 extern crate fxhash;
 use crate::dachshund::algorithms::connectivity::Connectivity;
+use crate::dachshund::algorithms::subgraph::InducedSubgraph;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{DirectedNodeBase, NodeBase, NodeEdgeBase, SimpleDirectedNode};
+use crate::dachshund::node::{
+    DirectedNodeBase, NodeBase, NodeEdgeBase, SimpleDirectedNode, WeightedNode,
+};
 use crate::dachshund::simple_undirected_graph::UndirectedGraph;
 use fxhash::FxHashSet;
 use std::collections::{BTreeSet, HashMap, HashSet};
@@ -16,6 +20,40 @@ use std::iter::FromIterator;
 
 type OrderedNodeSet = BTreeSet<NodeId>;
 
+// Root of id's set in a union-find forest, halving the path on the way up.
+pub(crate) fn find_root(parents: &mut HashMap<NodeId, NodeId>, mut id: NodeId) -> NodeId {
+    while parents[&id] != id {
+        let grandparent = parents[&parents[&id]];
+        parents.insert(id, grandparent);
+        id = grandparent;
+    }
+    id
+}
+
+// Each edge of a weighted graph once, as (weight, smaller id, larger id), for
+// sorting by weight. Fails on a NaN weight, which can't be sorted.
+pub(crate) fn get_weighted_edge_list<G>(graph: &G) -> CLQResult<Vec<(f64, NodeId, NodeId)>>
+where
+    G: GraphBase<NodeType = WeightedNode>,
+{
+    let mut edges: Vec<(f64, NodeId, NodeId)> = Vec::new();
+    for node in graph.get_nodes_iter() {
+        for e in &node.edges {
+            if e.weight.is_nan() {
+                return Err(CLQError::InvalidParameter(format!(
+                    "edge ({}, {}) has a NaN weight.",
+                    node.node_id.value(),
+                    e.target_id.value()
+                )));
+            }
+            if node.node_id < e.target_id {
+                edges.push((e.weight, node.node_id, e.target_id));
+            }
+        }
+    }
+    Ok(edges)
+}
+
 pub trait ConnectedComponents: GraphBase
 where
     Self::NodeType: NodeBase<NodeIdType = NodeId, NodeSetType = FxHashSet<NodeId>>,
@@ -86,6 +124,16 @@ where
         }
         v
     }
+    // Connected components of the graph as if ignore_nodes (and their edges) and
+    // ignore_edges (given in either direction) were deleted, without changing the
+    // graph. Ignored nodes are in no component, while nodes only losing edges are.
+    fn connected_components_excluding(
+        &self,
+        ignore_nodes: Option<&FxHashSet<NodeId>>,
+        ignore_edges: Option<&HashSet<(NodeId, NodeId)>>,
+    ) -> Vec<Vec<NodeId>> {
+        self._get_connected_components(ignore_nodes, ignore_edges)
+    }
 }
 
 pub trait ConnectedComponentsUndirected: GraphBase + ConnectedComponents + UndirectedGraph
@@ -96,6 +144,18 @@ where
     fn get_connected_components(&self) -> Vec<Vec<NodeId>> {
         self._get_connected_components(None, None)
     }
+    // Extracts each connected component as its own subgraph and applies f to it,
+    // returning one result per component (in the order of get_connected_components).
+    fn for_each_component<F, R>(&self, f: F) -> Vec<R>
+    where
+        Self: InducedSubgraph,
+        F: Fn(&Self) -> R,
+    {
+        self.get_connected_components()
+            .iter()
+            .map(|component| f(&self.get_induced_subgraph(component).unwrap()))
+            .collect()
+    }
 }
 pub trait ConnectedComponentsDirected:
     GraphBase<NodeType = SimpleDirectedNode> + ConnectedComponents + Connectivity
@@ -154,3 +214,35 @@ pub trait ConnectedComponentsDirected:
         components
     }
 }
+
+pub trait WeightedConnectedComponents: GraphBase<NodeType = WeightedNode> {
+    // How the graph comes together as edges are added from the heaviest to the
+    // lightest (as in Kruskal's algorithm), starting from isolated nodes: for each
+    // weight at which the number of connected components drops, that weight and
+    // the number of components once all edges at least as heavy are in. Read the
+    // other way, it's how the graph falls apart as ever heavier edges are removed.
+    // Thresholds are in decreasing order; the last count is the number of
+    // connected components of the graph (unless it has no edges at all). Fails on
+    // a NaN edge weight.
+    fn weight_hierarchy(&self) -> CLQResult<Vec<(f64, usize)>> {
+        let mut edges = get_weighted_edge_list(self)?;
+        edges.sort_by(|x, y| y.0.partial_cmp(&x.0).unwrap());
+
+        let mut parents: HashMap<NodeId, NodeId> =
+            self.get_ids_iter().map(|id| (*id, *id)).collect();
+        let mut num_components = self.count_nodes();
+        let mut hierarchy: Vec<(f64, usize)> = Vec::new();
+        for (i, (weight, a, b)) in edges.iter().enumerate() {
+            let (root_a, root_b) = (find_root(&mut parents, *a), find_root(&mut parents, *b));
+            if root_a != root_b {
+                parents.insert(root_a.max(root_b), root_a.min(root_b));
+                num_components -= 1;
+            }
+            let is_last_at_weight = edges.get(i + 1).is_none_or(|next| next.0 != *weight);
+            if is_last_at_weight && hierarchy.last().is_none_or(|x| x.1 != num_components) {
+                hierarchy.push((*weight, num_components));
+            }
+        }
+        Ok(hierarchy)
+    }
+}
@@ -4,13 +4,14 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{DirectedNodeBase, NodeBase, NodeEdgeBase};
 use crate::dachshund::simple_directed_graph::DirectedGraph;
 
 use crate::dachshund::simple_undirected_graph::UndirectedGraph;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 type OrderedNodeSet = BTreeSet<NodeId>;
 
@@ -61,6 +62,71 @@ where
         self.visit_nodes_from_root(root, &mut visited, &mut Vec::new(), edge_fn);
         Ok(visited.len() == self.count_nodes())
     }
+    // Bidirectional BFS between a and b (following edges in both directions), which
+    // expands the smaller of the two frontiers one level at a time and stops as
+    // soon as they meet. Returns whether a and b are connected, and how many nodes
+    // were visited on the way.
+    fn _bidirectional_search(&self, a: NodeId, b: NodeId) -> (bool, usize) {
+        if !self.has_node(a) || !self.has_node(b) {
+            return (false, 0);
+        }
+        if a == b {
+            return (true, 1);
+        }
+        let mut visited: [HashSet<NodeId>; 2] = [HashSet::from([a]), HashSet::from([b])];
+        let mut frontiers: [Vec<NodeId>; 2] = [vec![a], vec![b]];
+        while !frontiers[0].is_empty() && !frontiers[1].is_empty() {
+            let side = if frontiers[0].len() <= frontiers[1].len() {
+                0
+            } else {
+                1
+            };
+            let mut next: Vec<NodeId> = Vec::new();
+            for id in &frontiers[side] {
+                for e in self.get_node(*id).get_edges() {
+                    let neighbor_id = e.get_neighbor_id();
+                    if visited[1 - side].contains(&neighbor_id) {
+                        return (true, visited[0].len() + visited[1].len());
+                    }
+                    if visited[side].insert(neighbor_id) {
+                        next.push(neighbor_id);
+                    }
+                }
+            }
+            frontiers[side] = next;
+        }
+        (false, visited[0].len() + visited[1].len())
+    }
+    // Whether there is a path between a and b (ignoring edge directions), without
+    // computing the connected components of the whole graph. False if either node
+    // is not in the graph.
+    fn are_connected(&self, a: NodeId, b: NodeId) -> bool {
+        self._bidirectional_search(a, b).0
+    }
+    // Classic 2-approximation of a minimum vertex cover: repeatedly picks an edge
+    // neither of whose ends is covered yet, and adds both ends to the cover. The
+    // picked edges form a matching, which any cover needs one node of each of, so
+    // the result is at most twice as large as a minimum cover.
+    fn approximate_vertex_cover(&self) -> HashSet<NodeId> {
+        let mut cover: HashSet<NodeId> = HashSet::new();
+        // sorted so that the result doesn't depend on the iteration order of nodes.
+        for id in self.get_ordered_node_ids() {
+            if cover.contains(&id) {
+                continue;
+            }
+            let mut neighbor_ids: Vec<NodeId> = self
+                .get_node(id)
+                .get_edges()
+                .map(|e| e.get_neighbor_id())
+                .collect();
+            neighbor_ids.sort();
+            if let Some(neighbor_id) = neighbor_ids.into_iter().find(|x| !cover.contains(x)) {
+                cover.insert(id);
+                cover.insert(neighbor_id);
+            }
+        }
+        cover
+    }
 }
 
 pub trait ConnectivityUndirected: GraphBase + Connectivity + UndirectedGraph
@@ -71,6 +137,174 @@ where
     fn get_is_connected(&self) -> Result<bool, &'static str> {
         self._get_is_connected(Self::NodeType::get_edges)
     }
+
+    // Nodes in increasing order of id, and the edges between them as pairs of
+    // positions in that order (each edge once, self-loops excluded).
+    fn _get_indexed_edges(&self) -> (Vec<NodeId>, Vec<(usize, usize)>) {
+        let node_ids = self.get_ordered_node_ids();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for (i, id) in node_ids.iter().enumerate() {
+            for e in self.get_node(*id).get_edges() {
+                let j = positions[&e.get_neighbor_id()];
+                if i < j {
+                    edges.push((i, j));
+                }
+            }
+        }
+        (node_ids, edges)
+    }
+
+    // Minimum number of edges whose removal disconnects the graph (0 if it already
+    // is disconnected or has fewer than 2 nodes). By Menger's theorem, this is the
+    // smallest number of edge-disjoint paths between the first node and any other,
+    // which is found with unit-capacity max flows.
+    fn edge_connectivity(&self) -> usize {
+        let (node_ids, edges) = self._get_indexed_edges();
+        let n = node_ids.len();
+        let arcs: Vec<(usize, usize, usize)> = edges
+            .iter()
+            .flat_map(|(u, v)| [(*u, *v, 1), (*v, *u, 1)])
+            .collect();
+        (1..n).map(|t| max_flow(n, &arcs, 0, t)).min().unwrap_or(0)
+    }
+
+    // Minimum number of nodes whose removal disconnects the graph, or leaves a
+    // single node (so n - 1 for a complete graph on n nodes). By Menger's theorem,
+    // this is the smallest number of internally node-disjoint paths between two
+    // non-adjacent nodes.
+    fn vertex_connectivity(&self) -> usize {
+        let (node_ids, edges) = self._get_indexed_edges();
+        let n = node_ids.len();
+        let adjacent: HashSet<(usize, usize)> = edges.iter().cloned().collect();
+        let arcs = split_node_arcs(n, &edges);
+        let mut connectivity = n.saturating_sub(1);
+        for s in 0..n {
+            for t in s + 1..n {
+                if !adjacent.contains(&(s, t)) {
+                    connectivity = connectivity.min(max_flow(2 * n, &arcs, 2 * s + 1, 2 * t));
+                }
+            }
+        }
+        connectivity
+    }
+
+    // Maximum number of paths between source and target that share no node other
+    // than their ends (the edge between them, if any, being one such path). By
+    // Menger's theorem, this is also the smallest number of nodes (or of nodes and
+    // that edge) whose removal disconnects them. 0 if source and target are the
+    // same node, or if either is not in the graph.
+    fn node_disjoint_paths(&self, source: NodeId, target: NodeId) -> usize {
+        if source == target || !self.has_node(source) || !self.has_node(target) {
+            return 0;
+        }
+        let (node_ids, edges) = self._get_indexed_edges();
+        let s = node_ids.binary_search(&source).unwrap();
+        let t = node_ids.binary_search(&target).unwrap();
+        let arcs = split_node_arcs(node_ids.len(), &edges);
+        max_flow(2 * node_ids.len(), &arcs, 2 * s + 1, 2 * t)
+    }
+
+    // Greedily picks edges, in increasing order of their ends' ids, as long as
+    // neither end has reached its bound in max_degree. No further edge can be
+    // added to the result without exceeding a bound, so with all bounds equal to
+    // 1 this is a maximal matching. Every node must have a bound.
+    fn degree_constrained_subgraph(
+        &self,
+        max_degree: &HashMap<NodeId, usize>,
+    ) -> CLQResult<Vec<(NodeId, NodeId)>> {
+        let (node_ids, mut edges) = self._get_indexed_edges();
+        let mut remaining: Vec<usize> = Vec::with_capacity(node_ids.len());
+        for id in &node_ids {
+            match max_degree.get(id) {
+                Some(bound) => remaining.push(*bound),
+                None => {
+                    return Err(CLQError::InvalidParameter(format!(
+                        "node {} has no degree bound.",
+                        id.value()
+                    )))
+                }
+            }
+        }
+        edges.sort_unstable();
+        let mut selected: Vec<(NodeId, NodeId)> = Vec::new();
+        for (i, j) in edges {
+            if remaining[i] > 0 && remaining[j] > 0 {
+                remaining[i] -= 1;
+                remaining[j] -= 1;
+                selected.push((node_ids[i], node_ids[j]));
+            }
+        }
+        Ok(selected)
+    }
+}
+
+// Flow network in which paths can't share nodes: each node i is split into an "in"
+// vertex 2i and an "out" vertex 2i + 1, joined by a unit capacity arc, and each edge
+// (u, v) becomes unit capacity arcs from u's out vertex to v's in vertex and back.
+fn split_node_arcs(num_nodes: usize, edges: &[(usize, usize)]) -> Vec<(usize, usize, usize)> {
+    let mut arcs: Vec<(usize, usize, usize)> =
+        (0..num_nodes).map(|i| (2 * i, 2 * i + 1, 1)).collect();
+    for (u, v) in edges {
+        arcs.push((2 * u + 1, 2 * v, 1));
+        arcs.push((2 * v + 1, 2 * u, 1));
+    }
+    arcs
+}
+
+// Edmonds-Karp max flow from source to sink, on a network with num_vertices
+// vertices and (from, to, capacity) arcs.
+fn max_flow(
+    num_vertices: usize,
+    arcs: &[(usize, usize, usize)],
+    source: usize,
+    sink: usize,
+) -> usize {
+    let mut residual: Vec<HashMap<usize, usize>> = vec![HashMap::new(); num_vertices];
+    for (u, v, capacity) in arcs {
+        *residual[*u].entry(*v).or_insert(0) += capacity;
+        residual[*v].entry(*u).or_insert(0);
+    }
+    let mut flow: usize = 0;
+    loop {
+        // find a shortest augmenting path by BFS.
+        let mut parent: Vec<Option<usize>> = vec![None; num_vertices];
+        parent[source] = Some(source);
+        let mut queue: VecDeque<usize> = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            if u == sink {
+                break;
+            }
+            for (v, capacity) in &residual[u] {
+                if *capacity > 0 && parent[*v].is_none() {
+                    parent[*v] = Some(u);
+                    queue.push_back(*v);
+                }
+            }
+        }
+        if parent[sink].is_none() {
+            return flow;
+        }
+        let mut bottleneck = usize::MAX;
+        let mut v = sink;
+        while v != source {
+            let u = parent[v].unwrap();
+            bottleneck = bottleneck.min(residual[u][&v]);
+            v = u;
+        }
+        let mut v = sink;
+        while v != source {
+            let u = parent[v].unwrap();
+            *residual[u].get_mut(&v).unwrap() -= bottleneck;
+            *residual[v].get_mut(&u).unwrap() += bottleneck;
+            v = u;
+        }
+        flow += bottleneck;
+    }
 }
 
 pub trait ConnectivityDirected: GraphBase + Connectivity + DirectedGraph
@@ -7,9 +7,11 @@
 extern crate fxhash;
 
 use crate::dachshund::algorithms::connected_components::ConnectedComponents;
+use crate::dachshund::algorithms::ranking::top_k_by_value;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase};
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, SimpleNode, WeightedNode, WeightedNodeBase};
 use core::cmp::Reverse;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::iter::FromIterator;
@@ -17,10 +19,66 @@ use std::iter::FromIterator;
 use fxhash::FxHashSet;
 use ordered_float::NotNan;
 use priority_queue::PriorityQueue;
+use rayon::prelude::*;
 
 type OrderedNodeSet = BTreeSet<NodeId>;
 type OrderedEdgeSet = BTreeSet<(NodeId, NodeId)>;
 
+// Bucket queue behind Coreness::iter_shells. degrees only holds the nodes that
+// haven't been peeled yet, and a node is in the bucket of its current degree,
+// possibly along with stale entries in the buckets of its earlier degrees.
+struct ShellIter<'a, G> {
+    graph: &'a G,
+    degrees: HashMap<NodeId, usize>,
+    buckets: Vec<Vec<NodeId>>,
+    k: usize,
+}
+
+impl<G> Iterator for ShellIter<'_, G>
+where
+    G: GraphBase,
+    G::NodeType: NodeBase<NodeIdType = NodeId>,
+    <G::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    type Item = (usize, Vec<NodeId>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // every node left has degree at least k, so the next shell is the lowest
+        // bucket from k on with a node that is still at that degree.
+        let k = (self.k..self.buckets.len()).find(|d| {
+            self.buckets[*d]
+                .iter()
+                .any(|id| self.degrees.get(id) == Some(d))
+        })?;
+        self.k = k;
+        let mut stack: Vec<NodeId> = std::mem::take(&mut self.buckets[k]);
+        let mut shell: Vec<NodeId> = Vec::new();
+        while let Some(id) = stack.pop() {
+            // stale, or already peeled.
+            if self.degrees.get(&id) != Some(&k) {
+                continue;
+            }
+            self.degrees.remove(&id);
+            shell.push(id);
+            for e in self.graph.get_node(id).get_edges() {
+                let nid = e.get_neighbor_id();
+                if let Some(degree) = self.degrees.get_mut(&nid) {
+                    if *degree > k {
+                        *degree -= 1;
+                        if *degree == k {
+                            stack.push(nid);
+                        } else {
+                            self.buckets[*degree].push(nid);
+                        }
+                    }
+                }
+            }
+        }
+        shell.sort_unstable();
+        Some((k, shell))
+    }
+}
+
 pub trait Coreness: GraphBase + ConnectedComponents
 where
     Self::NodeType: NodeBase<NodeIdType = NodeId, NodeSetType = FxHashSet<NodeId>>,
@@ -85,6 +143,29 @@ where
         (core_assignments, coreness)
     }
 
+    // Shells of the core decomposition, as (k, nodes with coreness k) pairs in
+    // increasing order of k, with nodes in increasing order of id. Shells with no
+    // nodes are skipped. Each shell is peeled off when it is asked for, so the
+    // shells are never all held at once: besides the shell being returned, only
+    // the degrees of the nodes left and a bucket queue on them are kept.
+    fn iter_shells(&self) -> impl Iterator<Item = (usize, Vec<NodeId>)> + '_ {
+        let degrees: HashMap<NodeId, usize> = self
+            .get_nodes_iter()
+            .map(|node| (node.get_id(), node.degree()))
+            .collect();
+        let max_degree = degrees.values().cloned().max().unwrap_or(0);
+        let mut buckets: Vec<Vec<NodeId>> = vec![Vec::new(); max_degree + 1];
+        for (id, degree) in &degrees {
+            buckets[*degree].push(*id);
+        }
+        ShellIter {
+            graph: self,
+            degrees,
+            buckets,
+            k: 0,
+        }
+    }
+
     fn _get_core_assignments(&self, coreness: &HashMap<NodeId, usize>) -> Vec<Vec<Vec<NodeId>>> {
         // Use coreness mapping to compute connected components of each k-core.
         let mut nodes: Vec<NodeId> = coreness.keys().cloned().collect();
@@ -104,13 +185,59 @@ where
     }
 
     fn get_coreness_values(&self) -> HashMap<NodeId, usize> {
+        let node_ids: Vec<NodeId> = self.get_ids_iter().cloned().collect();
+        self._get_coreness_values_of(&node_ids)
+    }
+
+    // Same as get_coreness_values, but runs the peeling separately on each
+    // connected component (in parallel), so that many small components don't
+    // share one large set of bins. Gives exactly the same coreness values.
+    fn get_coreness_by_component(&self) -> HashMap<NodeId, usize>
+    where
+        Self: Sync,
+    {
+        // a plain BFS: _get_connected_components keeps its queues in ordered sets,
+        // and would cost more than the peeling saves.
+        let mut visited: FxHashSet<NodeId> = FxHashSet::default();
+        let mut components: Vec<Vec<NodeId>> = Vec::new();
+        for id in self.get_ids_iter() {
+            if !visited.insert(*id) {
+                continue;
+            }
+            let mut component: Vec<NodeId> = vec![*id];
+            let mut i = 0;
+            while i < component.len() {
+                for e in self.get_node(component[i]).get_edges() {
+                    let neighbor_id = e.get_neighbor_id();
+                    if visited.insert(neighbor_id) {
+                        component.push(neighbor_id);
+                    }
+                }
+                i += 1;
+            }
+            components.push(component);
+        }
+        let component_coreness: Vec<HashMap<NodeId, usize>> = components
+            .par_iter()
+            .map(|component| self._get_coreness_values_of(component))
+            .collect();
+        let mut coreness: HashMap<NodeId, usize> = HashMap::with_capacity(self.count_nodes());
+        for values in component_coreness {
+            coreness.extend(values);
+        }
+        coreness
+    }
+
+    // Coreness values of the given nodes, which must be closed under taking
+    // neighbors (e.g. the whole graph, or a connected component).
+    fn _get_coreness_values_of(&self, node_ids: &[NodeId]) -> HashMap<NodeId, usize> {
         // Traverse the nodes in increasing order of degree to calculate coreness.
         // See: https://arxiv.org/abs/cs/0310049 for an explanation of the bookkeeping details.
 
         // The initial value for the coreness of each node is its degree.
-        let mut coreness: HashMap<NodeId, usize> = self
-            .get_nodes_iter()
-            .map(|x| (x.get_id(), x.degree()))
+        let mut coreness: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .map(|id| (*id, self.get_node(*id).degree()))
             .collect();
 
         // Nodes in increasing order of coreness. We process this in order
@@ -126,7 +253,8 @@ where
         }
 
         let mut neighbors: HashMap<NodeId, FxHashSet<NodeId>> = HashMap::new();
-        for node in self.get_nodes_iter() {
+        for id in node_ids {
+            let node = self.get_node(*id);
             neighbors.insert(
                 node.get_id(),
                 FxHashSet::<NodeId>::from_iter(node.get_edges().map(|edge| edge.get_neighbor_id())),
@@ -180,6 +308,29 @@ where
         anomaly_scores
     }
 
+    // Nodes whose coreness anomaly score exceeds threshold, from most to least
+    // anomalous (ties broken by smallest id).
+    fn flag_coreness_anomalies(
+        &self,
+        coreness: &HashMap<NodeId, usize>,
+        threshold: f64,
+    ) -> Vec<NodeId> {
+        let flagged: HashMap<NodeId, f64> = self
+            .get_coreness_anomaly(coreness)
+            .into_iter()
+            .filter(|(_, score)| *score > threshold)
+            .collect();
+        top_k_by_value(&flagged, flagged.len())
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    // The k most anomalous nodes with their coreness anomaly scores.
+    fn top_anomalies(&self, coreness: &HashMap<NodeId, usize>, k: usize) -> Vec<(NodeId, f64)> {
+        top_k_by_value(&self.get_coreness_anomaly(coreness), k)
+    }
+
     fn _get_k_trusses(
         &self,
         k: usize,
@@ -252,19 +403,210 @@ where
             .collect::<HashSet<OrderedNodeSet>>();
         (filtered_trusses, truss_nodes)
     }
-    fn get_k_trusses(&self, k: usize) -> (Vec<OrderedEdgeSet>, HashSet<OrderedNodeSet>) {
+    // Support of each edge: the number of triangles it is part of, which the k-truss
+    // peeling compares to k - 2. Edges are keyed as (smaller id, larger id).
+    fn edge_support(&self) -> HashMap<(NodeId, NodeId), usize> {
+        let neighbors: HashMap<NodeId, HashSet<NodeId>> = self
+            .get_nodes_iter()
+            .map(|node| {
+                (
+                    node.get_id(),
+                    node.get_edges().map(|e| e.get_neighbor_id()).collect(),
+                )
+            })
+            .collect();
+        let mut support: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+        for (id, node_neighbors) in &neighbors {
+            for neighbor_id in node_neighbors {
+                if id < neighbor_id {
+                    let num_triangles =
+                        node_neighbors.intersection(&neighbors[neighbor_id]).count();
+                    support.insert((*id, *neighbor_id), num_triangles);
+                }
+            }
+        }
+        support
+    }
+    fn get_k_trusses(&self, k: usize) -> CLQResult<(Vec<OrderedEdgeSet>, HashSet<OrderedNodeSet>)> {
         // Basic algorithm: https://louridas.github.io/rwa/assignments/finding-trusses/
+        if k < 2 {
+            return Err(CLQError::InvalidParameter(format!(
+                "k-trusses are defined for k >= 2, got {}.",
+                k
+            )));
+        }
 
         // ignore_nodes will contain all the irrelevant nodes after
         // calling self._get_k_cores();
         let mut ignore_nodes: FxHashSet<NodeId> = FxHashSet::default();
         // this really only works for an undirected graph
         self._get_k_cores(k - 1, &mut ignore_nodes);
-        self._get_k_trusses(k, &ignore_nodes)
+        Ok(self._get_k_trusses(k, &ignore_nodes))
+    }
+}
+
+pub trait IncrementalCoreness: Coreness + GraphBase<NodeType = SimpleNode> {
+    // Checks that both nodes exist, and that the edge (a, b) is present or absent as expected.
+    fn _check_edge(&self, a: NodeId, b: NodeId, should_exist: bool) -> CLQResult<()> {
+        if a == b {
+            return Err(CLQError::InvalidParameter(
+                "self-loops are not supported.".to_owned(),
+            ));
+        }
+        for id in [a, b] {
+            if !self.has_node(id) {
+                return Err(CLQError::NodeNotFound(id));
+            }
+        }
+        let exists = self.get_node(a).neighbors.contains(&b);
+        if exists != should_exist {
+            return Err(CLQError::InvalidParameter(format!(
+                "edge ({}, {}) is {} the graph.",
+                a.value(),
+                b.value(),
+                if exists { "already in" } else { "not in" }
+            )));
+        }
+        Ok(())
+    }
+
+    // Collects the subcore of the root nodes: all nodes with coreness k reachable from
+    // them through nodes with coreness k. Then repeatedly evicts subcore nodes with at
+    // most min_support neighbors of coreness >= k that haven't been evicted. Returns
+    // the subcore and the evicted nodes.
+    fn _peel_subcore(
+        &self,
+        roots: Vec<NodeId>,
+        k: usize,
+        min_support: usize,
+        coreness: &HashMap<NodeId, usize>,
+    ) -> (HashSet<NodeId>, HashSet<NodeId>) {
+        let mut subcore: HashSet<NodeId> = roots.iter().cloned().collect();
+        let mut stack: Vec<NodeId> = roots;
+        let mut support: HashMap<NodeId, usize> = HashMap::new();
+        while let Some(id) = stack.pop() {
+            let mut num_supporting: usize = 0;
+            for nid in &self.get_node(id).neighbors {
+                let c = coreness[nid];
+                if c >= k {
+                    num_supporting += 1;
+                }
+                if c == k && subcore.insert(*nid) {
+                    stack.push(*nid);
+                }
+            }
+            support.insert(id, num_supporting);
+        }
+
+        let mut evicted: HashSet<NodeId> = HashSet::new();
+        let mut to_evict: Vec<NodeId> = support
+            .iter()
+            .filter(|(_, num_supporting)| **num_supporting <= min_support)
+            .map(|(id, _)| *id)
+            .collect();
+        while let Some(id) = to_evict.pop() {
+            if !evicted.insert(id) {
+                continue;
+            }
+            for nid in &self.get_node(id).neighbors {
+                if subcore.contains(nid) && !evicted.contains(nid) {
+                    let num_supporting = support.get_mut(nid).unwrap();
+                    *num_supporting -= 1;
+                    if *num_supporting == min_support {
+                        to_evict.push(*nid);
+                    }
+                }
+            }
+        }
+        (subcore, evicted)
+    }
+
+    // Inserts the edge (a, b) and updates coreness (as computed by get_coreness_values)
+    // in place, following the subcore algorithm of Sariyuce et al.,
+    // "Streaming Algorithms for k-core Decomposition" (VLDB 2013). An insertion can
+    // only raise core numbers by one, and only for nodes with the smaller endpoint
+    // coreness K that are connected to that endpoint through other nodes with
+    // coreness K (the subcore), so only that subcore is revisited.
+    fn insert_edge_update_coreness(
+        &mut self,
+        a: NodeId,
+        b: NodeId,
+        coreness: &mut HashMap<NodeId, usize>,
+    ) -> CLQResult<()> {
+        self._check_edge(a, b, false)?;
+        let nodes = self.get_mut_nodes();
+        nodes.get_mut(&a).unwrap().neighbors.insert(b);
+        nodes.get_mut(&b).unwrap().neighbors.insert(a);
+
+        let k = coreness[&a].min(coreness[&b]);
+        let roots: Vec<NodeId> = [a, b].into_iter().filter(|id| coreness[id] == k).collect();
+        // subcore nodes with more than k supporting neighbors move up to the (k + 1)-core.
+        let (subcore, evicted) = self._peel_subcore(roots, k, k, coreness);
+        for id in subcore.difference(&evicted) {
+            *coreness.get_mut(id).unwrap() += 1;
+        }
+        Ok(())
+    }
+
+    // Removes the edge (a, b) and updates coreness in place. Symmetrically to
+    // insertion, a removal can only lower core numbers by one, and only within the
+    // subcore of the endpoint(s) with the smaller coreness K.
+    fn remove_edge_update_coreness(
+        &mut self,
+        a: NodeId,
+        b: NodeId,
+        coreness: &mut HashMap<NodeId, usize>,
+    ) -> CLQResult<()> {
+        self._check_edge(a, b, true)?;
+        let nodes = self.get_mut_nodes();
+        nodes.get_mut(&a).unwrap().neighbors.remove(&b);
+        nodes.get_mut(&b).unwrap().neighbors.remove(&a);
+
+        // both endpoints had an edge, so k >= 1.
+        let k = coreness[&a].min(coreness[&b]);
+        let roots: Vec<NodeId> = [a, b].into_iter().filter(|id| coreness[id] == k).collect();
+        // subcore nodes left with fewer than k supporting neighbors drop out of the k-core.
+        let (_subcore, evicted) = self._peel_subcore(roots, k, k - 1, coreness);
+        for id in evicted {
+            *coreness.get_mut(&id).unwrap() -= 1;
+        }
+        Ok(())
     }
 }
 
 pub trait FractionalCoreness: GraphBase<NodeType = WeightedNode> {
+    // Weighted counterpart of Coreness::edge_support: each triangle an edge is part
+    // of counts for the weight of its weakest other edge, so that an edge only gets
+    // strong support from triangles closed by strong ties. Equal to the number of
+    // triangles when all weights are 1.0. Edges are keyed as (smaller id, larger id).
+    fn weighted_edge_support(&self) -> HashMap<(NodeId, NodeId), f64> {
+        let weights: HashMap<NodeId, HashMap<NodeId, f64>> = self
+            .get_nodes_iter()
+            .map(|node| {
+                (
+                    node.node_id,
+                    node.edges.iter().map(|e| (e.target_id, e.weight)).collect(),
+                )
+            })
+            .collect();
+        let mut support: HashMap<(NodeId, NodeId), f64> = HashMap::new();
+        for (id, node_weights) in &weights {
+            for neighbor_id in node_weights.keys() {
+                if id >= neighbor_id {
+                    continue;
+                }
+                let neighbor_weights = &weights[neighbor_id];
+                let edge_support: f64 = node_weights
+                    .iter()
+                    .filter_map(|(third_id, weight)| {
+                        neighbor_weights.get(third_id).map(|w| weight.min(*w))
+                    })
+                    .sum();
+                support.insert((*id, *neighbor_id), edge_support);
+            }
+        }
+        support
+    }
     fn get_fractional_coreness_values(&self) -> HashMap<NodeId, f64> {
         // The fractional coreness value is the same as standard k-cores except
         // using total edge weight for each vertex in the k-core, instead of the
@@ -314,20 +656,201 @@ pub trait FractionalCoreness: GraphBase<NodeType = WeightedNode> {
         }
         coreness
     }
+
+    // The peeling behind get_fractional_coreness_values, in rounds: each round
+    // raises the strength threshold to the smallest remaining strength (if above
+    // the previous threshold), and removes every node at or below it at once, in
+    // increasing order of id. Returns (threshold, removed nodes) for each round.
+    // Nodes removed in a round have the round's threshold as fractional coreness,
+    // and rounds are the weighted onion layers of the graph.
+    fn weighted_coreness_peeling_trace(&self) -> Vec<(f64, Vec<NodeId>)> {
+        let mut strengths: HashMap<NodeId, f64> = self
+            .get_nodes_iter()
+            .map(|node| (node.get_id(), node.weight()))
+            .collect();
+        let mut trace: Vec<(f64, Vec<NodeId>)> = Vec::new();
+        let mut threshold = f64::NEG_INFINITY;
+        while !strengths.is_empty() {
+            let min_strength = strengths.values().cloned().fold(f64::INFINITY, f64::min);
+            threshold = threshold.max(min_strength);
+            let mut removed: Vec<NodeId> = strengths
+                .iter()
+                .filter(|(_, strength)| **strength <= threshold)
+                .map(|(id, _)| *id)
+                .collect();
+            removed.sort();
+            for id in &removed {
+                strengths.remove(id);
+            }
+            for id in &removed {
+                for e in self.get_node(*id).get_edges() {
+                    if let Some(strength) = strengths.get_mut(&e.target_id) {
+                        *strength -= e.weight;
+                    }
+                }
+            }
+            trace.push((threshold, removed));
+        }
+        trace
+    }
+
+    // Generalized core where each node brings its own weight: repeatedly removes
+    // the nodes whose weight (as given by WeightedNodeBase::weight) plus number
+    // of remaining neighbors is below threshold, and returns the surviving nodes
+    // in increasing order of id. With all weights 0.0, it is the k-core for k =
+    // threshold, while heavy nodes stay in even with few neighbors.
+    fn get_node_weight_core(&self, threshold: f64) -> Vec<NodeId> {
+        let mut degrees: HashMap<NodeId, usize> = self
+            .get_nodes_iter()
+            .map(|node| (node.node_id, node.degree()))
+            .collect();
+        let below =
+            |id: NodeId, degree: usize| self.get_node(id).weight() + (degree as f64) < threshold;
+        let mut removed: FxHashSet<NodeId> = FxHashSet::default();
+        let mut queue: Vec<NodeId> = degrees
+            .iter()
+            .filter(|(id, degree)| below(**id, **degree))
+            .map(|(id, _)| *id)
+            .collect();
+        while let Some(id) = queue.pop() {
+            if !removed.insert(id) {
+                continue;
+            }
+            for neighbor_id in &self.get_node(id).neighbors {
+                if removed.contains(neighbor_id) {
+                    continue;
+                }
+                let degree = degrees.get_mut(neighbor_id).unwrap();
+                *degree -= 1;
+                if below(*neighbor_id, *degree) {
+                    queue.push(*neighbor_id);
+                }
+            }
+        }
+        let mut core: Vec<NodeId> = self
+            .get_ids_iter()
+            .filter(|id| !removed.contains(id))
+            .cloned()
+            .collect();
+        core.sort();
+        core
+    }
+
+    // Weighted analogue of Coreness::get_coreness_anomaly (Core-A): the absolute
+    // difference between the logs of each node's rank by fractional coreness and by
+    // weight (strength). Nodes with much more total edge weight than their
+    // embeddedness in heavy cores would suggest get high scores.
+    fn get_weighted_coreness_anomaly(
+        &self,
+        weighted_coreness: &HashMap<NodeId, f64>,
+    ) -> HashMap<NodeId, f64> {
+        let core_ranks = averaged_ties_ranking(weighted_coreness);
+        let weight_ranks = averaged_ties_ranking(
+            &self
+                .get_nodes_iter()
+                .map(|x| (x.get_id(), x.weight()))
+                .collect(),
+        );
+        self.get_ids_iter()
+            .map(|id| (*id, (core_ranks[id].ln() - weight_ranks[id].ln()).abs()))
+            .collect()
+    }
+
+    // Weighted k-shell decomposition of Garas et al. ("A k-shell decomposition
+    // method for weighted networks", 2012): same peeling as fractional coreness,
+    // but a node's priority is sqrt(degree * strength) over the remaining edges.
+    // Unlike the fractional core, a single heavy edge can't make a deep core on
+    // its own, since its endpoints still need many neighbors. Fails on a NaN or
+    // infinite edge weight.
+    fn get_weighted_kshell_values(&self) -> CLQResult<HashMap<NodeId, f64>> {
+        for node in self.get_nodes_iter() {
+            if let Some(e) = node.edges.iter().find(|e| !e.weight.is_finite()) {
+                return Err(CLQError::InvalidParameter(format!(
+                    "edge ({}, {}) has a non-finite weight.",
+                    node.node_id.value(),
+                    e.target_id.value()
+                )));
+            }
+        }
+        let priority = |degree: usize, strength: f64| {
+            Reverse(NotNan::new((degree as f64 * strength.max(0.0)).sqrt()).unwrap())
+        };
+        let mut remaining: HashMap<NodeId, (usize, f64)> = self
+            .get_nodes_iter()
+            .map(|node| (node.get_id(), (node.edges.len(), node.weight())))
+            .collect();
+        let mut pq = PriorityQueue::with_capacity(remaining.len());
+        for (id, (degree, strength)) in &remaining {
+            pq.push(*id, priority(*degree, *strength));
+        }
+        let mut coreness: HashMap<NodeId, f64> = HashMap::new();
+        let mut next_shell_coreness = NotNan::new(f64::NEG_INFINITY).unwrap();
+        while let Some((node_id, Reverse(nn))) = pq.pop() {
+            if nn > next_shell_coreness {
+                next_shell_coreness = nn
+            }
+            coreness.insert(node_id, next_shell_coreness.into_inner());
+            for e in self.get_node(node_id).get_edges() {
+                if pq.get_priority(&e.target_id).is_some() {
+                    let (degree, strength) = remaining.get_mut(&e.target_id).unwrap();
+                    *degree -= 1;
+                    *strength -= e.weight;
+                    pq.change_priority(&e.target_id, priority(*degree, *strength));
+                }
+            }
+        }
+        Ok(coreness)
+    }
+
+    // For each node, its rank by weighted k-shell (see get_weighted_kshell_values)
+    // minus its rank by strength, divided by the number of nodes minus one, so that
+    // scores are in [-1.0, 1.0] (ranks start at 1 for the largest value, ties get
+    // the averaged rank). Large positive gaps flag nodes whose total edge weight
+    // overstates how embedded they are, e.g. leaves hanging off a single heavy edge.
+    // Fails on a NaN or infinite edge weight.
+    fn strength_coreness_gap(&self) -> CLQResult<HashMap<NodeId, f64>> {
+        let n = self.count_nodes();
+        let core_ranks = averaged_ties_ranking(&self.get_weighted_kshell_values()?);
+        let strength_ranks = averaged_ties_ranking(
+            &self
+                .get_nodes_iter()
+                .map(|x| (x.get_id(), x.weight()))
+                .collect(),
+        );
+        let scale = n.saturating_sub(1).max(1) as f64;
+        Ok(self
+            .get_ids_iter()
+            .map(|id| (*id, (core_ranks[id] - strength_ranks[id]) / scale))
+            .collect())
+    }
 }
 
-pub fn averaged_ties_ranking(scores: &HashMap<NodeId, usize>) -> HashMap<NodeId, f64> {
+pub fn averaged_ties_ranking<T: Copy + PartialOrd>(
+    scores: &HashMap<NodeId, T>,
+) -> HashMap<NodeId, f64> {
     // Given a map from NodeIds to values, create a new map from those NodeIds to their rank.
     // In the case of ties, all tied keys get the same, averaged rank.
     // e.g. {1: 10, 2: 20, 3: 15, 4: 20, 5: 25} -> {5: 1, 4: 2.5, 2: 2.5, 3: 4, 1: 5}
+    // Values can be integers (e.g. coreness) or floats (e.g. fractional coreness), but
+    // must not be NaN.
 
     let mut ranking = HashMap::new();
-    let mut sorted_nodes: Vec<(&NodeId, &usize)> = scores.iter().collect();
-    sorted_nodes.sort_unstable_by_key(|(_node, value)| Reverse(*value));
+    if scores.is_empty() {
+        return ranking;
+    }
+    let mut sorted_nodes: Vec<(&NodeId, &T)> = scores.iter().collect();
+    // ties are ordered by node id, so that the traversal doesn't depend on the
+    // iteration order of scores.
+    sorted_nodes.sort_unstable_by(|(node1, value1), (node2, value2)| {
+        value2
+            .partial_cmp(value1)
+            .unwrap()
+            .then_with(|| node1.cmp(node2))
+    });
 
     let mut tied_nodes: Vec<&NodeId> = Vec::<&NodeId>::new();
     let mut tied_rank: f64;
-    let mut last_value: Option<usize> = None;
+    let mut last_value: Option<T> = None;
 
     for (i, (node, &value)) in sorted_nodes.into_iter().enumerate() {
         if last_value.is_none() || Some(value) == last_value {
@@ -0,0 +1,53 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use crate::dachshund::simple_undirected_graph::UndirectedGraph;
+use std::collections::BTreeSet;
+
+/// Differences between two snapshots of a graph over the same node id space.
+/// Edges are (smaller id, larger id) pairs, and all vectors are sorted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GraphDiff {
+    pub added_edges: Vec<(NodeId, NodeId)>,
+    pub removed_edges: Vec<(NodeId, NodeId)>,
+    pub added_nodes: Vec<NodeId>,
+    pub removed_nodes: Vec<NodeId>,
+}
+
+pub trait Diff: UndirectedGraph
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    fn _get_edge_set(&self) -> BTreeSet<(NodeId, NodeId)> {
+        let mut edges: BTreeSet<(NodeId, NodeId)> = BTreeSet::new();
+        for node in self.get_nodes_iter() {
+            let id = node.get_id();
+            for e in node.get_edges() {
+                let nid = e.get_neighbor_id();
+                edges.insert((id.min(nid), id.max(nid)));
+            }
+        }
+        edges
+    }
+
+    // What changed going from self to other: edges and nodes that are in other but
+    // not in self are added, those in self but not in other are removed.
+    fn diff(&self, other: &Self) -> GraphDiff {
+        let nodes: BTreeSet<NodeId> = self.get_ids_iter().cloned().collect();
+        let other_nodes: BTreeSet<NodeId> = other.get_ids_iter().cloned().collect();
+        let edges = self._get_edge_set();
+        let other_edges = other._get_edge_set();
+        GraphDiff {
+            added_edges: other_edges.difference(&edges).cloned().collect(),
+            removed_edges: edges.difference(&other_edges).cloned().collect(),
+            added_nodes: other_nodes.difference(&nodes).cloned().collect(),
+            removed_nodes: nodes.difference(&other_nodes).cloned().collect(),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::{HashMap, HashSet};
+
+/// Order in which greedy algorithms (e.g. greedy coloring or independent sets)
+/// visit the nodes of a graph.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColoringOrder {
+    /// In increasing order of node id.
+    Natural,
+    /// In decreasing order of degree, ties broken by node id.
+    LargestFirst,
+    /// In increasing order of degree, ties broken by node id.
+    SmallestFirst,
+    /// In a random order, given by the seed.
+    Random(u64),
+}
+
+pub trait IndependentSet: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    fn get_node_ordering(&self, ordering: ColoringOrder) -> Vec<NodeId> {
+        match ordering {
+            ColoringOrder::Natural => self.get_ordered_node_ids(),
+            ColoringOrder::LargestFirst => self.nodes_by_degree(false),
+            ColoringOrder::SmallestFirst => self.nodes_by_degree(true),
+            ColoringOrder::Random(seed) => {
+                let mut node_ids = self.get_ordered_node_ids();
+                node_ids.shuffle(&mut StdRng::seed_from_u64(seed));
+                node_ids
+            }
+        }
+    }
+    // Visits nodes in the given order, selecting each one that has no neighbor
+    // selected yet. No node can be added to the result without breaking its
+    // independence, but it is not necessarily a maximum independent set.
+    fn maximal_independent_set(&self, ordering: ColoringOrder) -> Vec<NodeId> {
+        let mut selected: Vec<NodeId> = Vec::new();
+        let mut excluded: HashSet<NodeId> = HashSet::new();
+        for id in self.get_node_ordering(ordering) {
+            if excluded.contains(&id) {
+                continue;
+            }
+            selected.push(id);
+            excluded.insert(id);
+            for e in self.get_node(id).get_edges() {
+                excluded.insert(e.get_neighbor_id());
+            }
+        }
+        selected
+    }
+    // Size of a greedy maximal independent set, visiting low degree nodes first,
+    // which is a lower bound on the size of the largest independent set.
+    fn independence_number_lower_bound(&self) -> usize {
+        self.maximal_independent_set(ColoringOrder::SmallestFirst)
+            .len()
+    }
+    // Exact maximum weight independent set of a forest, given the weight of each
+    // node (0.0 for nodes missing from node_weights). Each tree is rooted at its
+    // smallest node, and the best weight of each subtree is computed bottom-up both
+    // with and without its root; the set is then read off top-down. Runs in linear
+    // time, whereas the general problem is NP-hard. Fails if the graph has a cycle,
+    // or if node_weights has an id that isn't in the graph. Returns the sorted set
+    // and its total weight.
+    // The node weights are passed in, rather than read off a weighted graph, since
+    // weighted graphs only weigh their edges: weighing each node by the total
+    // weight of its edges would be pointless on a tree, where either side of the
+    // bipartition already covers every edge.
+    fn max_weight_independent_set_tree(
+        &self,
+        node_weights: &HashMap<NodeId, f64>,
+    ) -> CLQResult<(Vec<NodeId>, f64)> {
+        if let Some(id) = node_weights.keys().find(|id| !self.has_node(**id)) {
+            return Err(CLQError::NodeNotFound(*id));
+        }
+        let mut parents: HashMap<NodeId, Option<NodeId>> = HashMap::new();
+        // nodes in DFS preorder, so that children come after their parents.
+        let mut preorder: Vec<NodeId> = Vec::new();
+        for root in self.get_ordered_node_ids() {
+            if parents.contains_key(&root) {
+                continue;
+            }
+            parents.insert(root, None);
+            let mut stack: Vec<NodeId> = vec![root];
+            while let Some(id) = stack.pop() {
+                preorder.push(id);
+                let parent = parents[&id];
+                let mut is_parent_seen = false;
+                for e in self.get_node(id).get_edges() {
+                    let neighbor_id = e.get_neighbor_id();
+                    if Some(neighbor_id) == parent && !is_parent_seen {
+                        is_parent_seen = true;
+                        continue;
+                    }
+                    if parents.contains_key(&neighbor_id) {
+                        return Err(CLQError::InvalidParameter(
+                            "graph has a cycle, it must be a tree (or a forest).".to_owned(),
+                        ));
+                    }
+                    parents.insert(neighbor_id, Some(id));
+                    stack.push(neighbor_id);
+                }
+            }
+        }
+        let weight = |id: &NodeId| *node_weights.get(id).unwrap_or(&0.0);
+        let mut with_root: HashMap<NodeId, f64> =
+            preorder.iter().map(|id| (*id, weight(id))).collect();
+        let mut without_root: HashMap<NodeId, f64> = preorder.iter().map(|id| (*id, 0.0)).collect();
+        for id in preorder.iter().rev() {
+            if let Some(parent) = parents[id] {
+                let (with_id, without_id) = (with_root[id], without_root[id]);
+                *with_root.get_mut(&parent).unwrap() += without_id;
+                *without_root.get_mut(&parent).unwrap() += with_id.max(without_id);
+            }
+        }
+        let mut selected: HashSet<NodeId> = HashSet::new();
+        let mut total_weight: f64 = 0.0;
+        for id in &preorder {
+            let is_parent_selected = match parents[id] {
+                Some(parent) => selected.contains(&parent),
+                None => false,
+            };
+            if !is_parent_selected && with_root[id] > without_root[id] {
+                selected.insert(*id);
+                total_weight += weight(id);
+            }
+        }
+        let mut independent_set: Vec<NodeId> = selected.into_iter().collect();
+        independent_set.sort();
+        Ok((independent_set, total_weight))
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::WeightedNode;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::{BTreeMap, HashMap};
+
+pub trait WeightedLabelPropagation: GraphBase<NodeType = WeightedNode> {
+    // Label propagation (Raghavan et al., 2007) where each node adopts the label
+    // with the largest total weight of edges to the neighbors carrying it, rather
+    // than the most frequent one. Every node starts with a label of its own, and
+    // nodes are updated one at a time, in an order shuffled (from seed) on every
+    // pass. Ties keep the node's current label if it's among the best, and are
+    // otherwise broken at random. Stops after a pass without changes, or after
+    // max_iter passes. Communities are numbered 0.. in order of their smallest
+    // node id.
+    fn weighted_label_propagation(&self, max_iter: usize, seed: u64) -> HashMap<NodeId, usize> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut node_ids = self.get_ordered_node_ids();
+        let mut labels: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        for _ in 0..max_iter {
+            node_ids.shuffle(&mut rng);
+            let mut changed = false;
+            for id in &node_ids {
+                let mut label_weights: BTreeMap<usize, f64> = BTreeMap::new();
+                for e in &self.get_node(*id).edges {
+                    if e.target_id != *id {
+                        *label_weights.entry(labels[&e.target_id]).or_insert(0.0) += e.weight;
+                    }
+                }
+                let max_weight = match label_weights.values().cloned().reduce(f64::max) {
+                    Some(x) => x,
+                    None => continue,
+                };
+                let current = labels[id];
+                if label_weights.get(&current) == Some(&max_weight) {
+                    continue;
+                }
+                let best: Vec<usize> = label_weights
+                    .into_iter()
+                    .filter(|(_, weight)| *weight == max_weight)
+                    .map(|(label, _)| label)
+                    .collect();
+                labels.insert(*id, *best.choose(&mut rng).unwrap());
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut community_ids: HashMap<usize, usize> = HashMap::new();
+        let mut communities: HashMap<NodeId, usize> = HashMap::new();
+        for id in self.get_ordered_node_ids() {
+            let next_id = community_ids.len();
+            let community = *community_ids.entry(labels[&id]).or_insert(next_id);
+            communities.insert(id, community);
+        }
+        communities
+    }
+}
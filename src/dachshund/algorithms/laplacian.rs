@@ -5,9 +5,11 @@
  * LICENSE file in the root directory of this source tree.
  */
 use crate::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
+use crate::dachshund::algorithms::connectivity::ConnectivityUndirected;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase};
 use nalgebra::{DMatrix, DVector};
 
 type GraphMatrix = DMatrix<f64>;
@@ -33,4 +35,129 @@ where
         let adj_mat = self.get_adjacency_matrix_given_node_ids(&node_ids);
         (deg_mat - adj_mat, node_ids)
     }
+    // Effective resistance between a and b when every edge is a unit resistor:
+    // L+[a, a] + L+[b, b] - 2 L+[a, b], where L+ is the Moore-Penrose pseudo-inverse
+    // of the Laplacian. Unlike hop distance, it accounts for all paths between the
+    // two nodes, dropping as more of them are available. The graph must be connected.
+    fn resistance_distance(&self, a: NodeId, b: NodeId) -> CLQResult<f64>
+    where
+        Self: ConnectivityUndirected,
+    {
+        for id in [a, b] {
+            if !self.has_node(id) {
+                return Err(CLQError::NodeNotFound(id));
+            }
+        }
+        match self.get_is_connected() {
+            Ok(true) => (),
+            Ok(false) => return Err(CLQError::NotConnected),
+            Err(e) => return Err(CLQError::from(e)),
+        }
+        let (laplacian, node_ids) = self.get_laplacian_matrix();
+        let pinv = laplacian.pseudo_inverse(1e-9).map_err(CLQError::from)?;
+        let i = node_ids.iter().position(|x| *x == a).unwrap();
+        let j = node_ids.iter().position(|x| *x == b).unwrap();
+        Ok(pinv[(i, i)] + pinv[(j, j)] - 2.0 * pinv[(i, j)])
+    }
+    // Kirchhoff index: the sum of resistance distances over all (unordered) pairs
+    // of nodes, computed as n times the sum of 1 / lambda over the nonzero
+    // eigenvalues lambda of the Laplacian. Lower values mean a more robustly
+    // connected graph. The graph must be connected.
+    fn kirchhoff_index(&self) -> CLQResult<f64>
+    where
+        Self: ConnectivityUndirected,
+    {
+        match self.get_is_connected() {
+            Ok(true) => (),
+            Ok(false) => return Err(CLQError::NotConnected),
+            Err(e) => return Err(CLQError::from(e)),
+        }
+        let (laplacian, node_ids) = self.get_laplacian_matrix();
+        let n = node_ids.len();
+        let mut eigenvalues: Vec<f64> = laplacian.symmetric_eigenvalues().iter().cloned().collect();
+        eigenvalues.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        // a connected graph has a single zero eigenvalue, the smallest.
+        Ok(n as f64 * eigenvalues.iter().skip(1).map(|x| 1.0 / x).sum::<f64>())
+    }
+}
+
+pub trait WeightedLaplacian: GraphBase<NodeType = WeightedNode> {
+    // Weighted Laplacian, with rows and columns in increasing order of node id:
+    // node weights (strengths) on the diagonal, minus the weights of the edges.
+    fn weighted_laplacian_matrix(&self) -> GraphMatrix {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        let mut laplacian = GraphMatrix::zeros(n, n);
+        for (i, id) in node_ids.iter().enumerate() {
+            let node = self.get_node(*id);
+            laplacian[(i, i)] += node.weight();
+            for e in &node.edges {
+                let j = node_ids.binary_search(&e.target_id).unwrap();
+                laplacian[(i, j)] -= e.weight;
+            }
+        }
+        laplacian
+    }
+
+    // Splits the nodes in two with a sweep over the Fiedler vector (the
+    // eigenvector of the second-smallest eigenvalue) of the weighted Laplacian:
+    // nodes are sorted by their entry in it, and the sorted list is cut where the
+    // ratio cut, the weight of the edges cut times (1 / |A| + 1 / |B|), is
+    // smallest. The side holding the smallest id comes first, and both sides are
+    // in increasing order of id.
+    fn weighted_spectral_bipartition(&self) -> (Vec<NodeId>, Vec<NodeId>) {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        if n < 2 {
+            return (node_ids, Vec::new());
+        }
+        let eigen = self.weighted_laplacian_matrix().symmetric_eigen();
+        let mut eigen_order: Vec<usize> = (0..n).collect();
+        eigen_order.sort_by(|a, b| {
+            eigen.eigenvalues[*a]
+                .partial_cmp(&eigen.eigenvalues[*b])
+                .unwrap()
+        });
+        let fiedler = eigen.eigenvectors.column(eigen_order[1]);
+        let mut sweep: Vec<usize> = (0..n).collect();
+        sweep.sort_by(|a, b| fiedler[*a].partial_cmp(&fiedler[*b]).unwrap());
+
+        let mut in_prefix: Vec<bool> = vec![false; n];
+        let mut cut_weight: f64 = 0.0;
+        let mut best: Option<(f64, usize)> = None;
+        for (k, i) in sweep[..n - 1].iter().enumerate() {
+            // moving node i to the prefix cuts its edges to the rest, and uncuts
+            // its edges to the prefix.
+            in_prefix[*i] = true;
+            for e in &self.get_node(node_ids[*i]).edges {
+                let j = node_ids.binary_search(&e.target_id).unwrap();
+                if in_prefix[j] {
+                    cut_weight -= e.weight;
+                } else {
+                    cut_weight += e.weight;
+                }
+            }
+            let size = (k + 1) as f64;
+            let ratio_cut = cut_weight * (1.0 / size + 1.0 / (n as f64 - size));
+            if best.is_none_or(|(x, _)| ratio_cut < x) {
+                best = Some((ratio_cut, k + 1));
+            }
+        }
+        let prefix_size = best.unwrap().1;
+        let mut in_prefix: Vec<bool> = vec![false; n];
+        for i in &sweep[..prefix_size] {
+            in_prefix[*i] = true;
+        }
+        let mut first: Vec<NodeId> = Vec::new();
+        let mut second: Vec<NodeId> = Vec::new();
+        for (i, id) in node_ids.into_iter().enumerate() {
+            // the side holding the smallest id comes first.
+            if in_prefix[i] == in_prefix[0] {
+                first.push(id);
+            } else {
+                second.push(id);
+            }
+        }
+        (first, second)
+    }
 }
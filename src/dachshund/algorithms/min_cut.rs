@@ -0,0 +1,95 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{SimpleNode, WeightedNodeBase};
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use crate::dachshund::weighted_undirected_graph_builder::{
+    TWeightedUndirectedGraphBuilder, WeightedUndirectedGraphBuilder,
+};
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::{BTreeMap, HashMap};
+
+pub trait MinCut: GraphBase<NodeType = SimpleNode> {
+    // Copy of the graph with unit weights, to be contracted into a multigraph
+    // (where weights count parallel edges).
+    fn _as_multigraph(&self) -> WeightedUndirectedGraph {
+        let ids: BTreeMap<NodeId, BTreeMap<NodeId, f64>> = self
+            .get_nodes_iter()
+            .map(|node| {
+                (
+                    node.node_id,
+                    node.neighbors.iter().map(|nid| (*nid, 1.0)).collect(),
+                )
+            })
+            .collect();
+        let nodes = WeightedUndirectedGraphBuilder::get_nodes(ids);
+        WeightedUndirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        }
+    }
+
+    // Karger's algorithm: contracts uniformly random edges (counting multiplicities)
+    // until two super-nodes remain, whose edges form a cut. Returns the smallest such
+    // cut over all iterations, along with the sorted nodes on the smaller side of it.
+    // A disconnected graph has a cut of size 0, and a graph with fewer than two nodes
+    // has no cut at all, which is also reported as (0, []).
+    fn karger_min_cut(&self, iterations: usize, seed: u64) -> (usize, Vec<NodeId>) {
+        if self.count_nodes() < 2 {
+            return (0, Vec::new());
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut best: Option<(usize, Vec<NodeId>)> = None;
+        for _ in 0..iterations {
+            let mut graph = self._as_multigraph();
+            let mut members: HashMap<NodeId, Vec<NodeId>> =
+                graph.ids.iter().map(|id| (*id, vec![*id])).collect();
+            while graph.count_nodes() > 2 {
+                let mut edges: Vec<(NodeId, NodeId, f64)> = Vec::new();
+                for node in graph.get_nodes_iter() {
+                    for e in &node.edges {
+                        if node.node_id < e.target_id {
+                            edges.push((node.node_id, e.target_id, e.weight));
+                        }
+                    }
+                }
+                if edges.is_empty() {
+                    break;
+                }
+                // sorted so that the outcome only depends on the seed.
+                edges.sort_by_key(|x| (x.0, x.1));
+                let dist = WeightedIndex::new(edges.iter().map(|e| e.2)).unwrap();
+                let (a, b, _) = edges[dist.sample(&mut rng)];
+                graph.contract_edge(a, b).unwrap();
+                let absorbed = members.remove(&b).unwrap();
+                members.get_mut(&a).unwrap().extend(absorbed);
+            }
+            // with more than 2 super-nodes left, any one of them is cut off from the rest.
+            let (id, cut_size) = graph
+                .get_nodes_iter()
+                .map(|node| (node.node_id, node.weight().round() as usize))
+                .min()
+                .unwrap();
+            if best.is_none() || cut_size < best.as_ref().unwrap().0 {
+                let mut side = members.remove(&id).unwrap();
+                if 2 * side.len() > self.count_nodes() {
+                    side = self
+                        .get_ids_iter()
+                        .filter(|x| !side.contains(x))
+                        .cloned()
+                        .collect();
+                }
+                side.sort();
+                best = Some((cut_size, side));
+            }
+        }
+        best.unwrap_or((0, Vec::new()))
+    }
+}
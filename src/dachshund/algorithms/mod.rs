@@ -8,13 +8,32 @@ pub mod adjacency_matrix;
 pub mod algebraic_connectivity;
 pub mod betweenness;
 pub mod brokerage;
+pub mod cliques;
+pub mod closeness;
 pub mod clustering;
 pub mod cnm_communities;
 pub mod connected_components;
 pub mod connectivity;
 pub mod coreness;
 pub mod eigenvector_centrality;
+pub mod graph_diff;
+pub mod independent_set;
 pub mod k_peaks;
+pub mod label_propagation;
 pub mod laplacian;
+pub mod min_cut;
+pub mod modularity;
+pub mod motifs;
+pub mod pagerank;
+pub mod ranking;
+pub mod relabel;
+pub mod rewiring;
+pub mod roles;
 pub mod shortest_paths;
+pub mod similarity;
+pub mod simrank;
+pub mod spanning_tree;
+pub mod statistics;
+pub mod subgraph;
 pub mod transitivity;
+pub mod triad_census;
@@ -0,0 +1,281 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::{BTreeMap, HashMap};
+
+// Newman's modularity, sum over communities c of L_c / m - (D_c / 2m)^2, given the
+// (possibly weighted) internal edge total L_c and degree total D_c of each
+// community, and the total edge count (or weight) m.
+fn modularity_from_totals(
+    internal: &HashMap<usize, f64>,
+    degrees: &HashMap<usize, f64>,
+    total: f64,
+) -> f64 {
+    if total <= 0.0 {
+        return 0.0;
+    }
+    degrees
+        .iter()
+        .map(|(c, d)| internal.get(c).unwrap_or(&0.0) / total - (d / (2.0 * total)).powi(2))
+        .sum()
+}
+
+/// Quality measures for a partition of the nodes, as computed by
+/// `Modularity::community_report`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommunityReport {
+    pub modularity: f64,
+    pub num_communities: usize,
+    // sizes of the communities, largest first.
+    pub community_sizes: Vec<usize>,
+    // fraction of possible internal edges present, averaged over communities
+    // (singleton communities count as 0.0).
+    pub average_internal_density: f64,
+    // edges leaving a community divided by the smaller of the community's volume
+    // and that of the rest of the graph, averaged over communities.
+    pub average_conductance: f64,
+}
+
+pub trait Modularity: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Modularity of a partition of the nodes, given as node_id => community id.
+    // Every node in the graph must be assigned to a community.
+    fn modularity(&self, communities: &HashMap<NodeId, usize>) -> f64 {
+        let mut internal: HashMap<usize, f64> = HashMap::new();
+        let mut degrees: HashMap<usize, f64> = HashMap::new();
+        for node in self.get_nodes_iter() {
+            let c = communities[&node.get_id()];
+            *degrees.entry(c).or_insert(0.0) += node.degree() as f64;
+            for e in node.get_edges() {
+                if communities[&e.get_neighbor_id()] == c {
+                    // each internal edge is seen from both of its ends.
+                    *internal.entry(c).or_insert(0.0) += 0.5;
+                }
+            }
+        }
+        modularity_from_totals(&internal, &degrees, self.count_edges() as f64)
+    }
+
+    // Modularity, community sizes, and average internal density and conductance of
+    // a partition of the nodes. Every node in the graph must be assigned to a community.
+    fn community_report(&self, communities: &HashMap<NodeId, usize>) -> CommunityReport {
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        let mut volumes: HashMap<usize, usize> = HashMap::new();
+        let mut internal_ends: HashMap<usize, usize> = HashMap::new();
+        let mut cut_edges: HashMap<usize, usize> = HashMap::new();
+        for node in self.get_nodes_iter() {
+            let c = communities[&node.get_id()];
+            *sizes.entry(c).or_insert(0) += 1;
+            *volumes.entry(c).or_insert(0) += node.degree();
+            for e in node.get_edges() {
+                if communities[&e.get_neighbor_id()] == c {
+                    *internal_ends.entry(c).or_insert(0) += 1;
+                } else {
+                    *cut_edges.entry(c).or_insert(0) += 1;
+                }
+            }
+        }
+        let total_volume: usize = volumes.values().sum();
+        let mut total_density: f64 = 0.0;
+        let mut total_conductance: f64 = 0.0;
+        for (c, size) in &sizes {
+            let possible_edges = size * (size - 1) / 2;
+            if possible_edges > 0 {
+                let internal_edges = internal_ends.get(c).unwrap_or(&0) / 2;
+                total_density += internal_edges as f64 / possible_edges as f64;
+            }
+            let volume = volumes[c].min(total_volume - volumes[c]);
+            if volume > 0 {
+                total_conductance += *cut_edges.get(c).unwrap_or(&0) as f64 / volume as f64;
+            }
+        }
+        let num_communities = sizes.len();
+        let mut community_sizes: Vec<usize> = sizes.into_values().collect();
+        community_sizes.sort_unstable_by(|a, b| b.cmp(a));
+        let (average_internal_density, average_conductance) = if num_communities == 0 {
+            (0.0, 0.0)
+        } else {
+            (
+                total_density / num_communities as f64,
+                total_conductance / num_communities as f64,
+            )
+        };
+        CommunityReport {
+            modularity: self.modularity(communities),
+            num_communities,
+            community_sizes,
+            average_internal_density,
+            average_conductance,
+        }
+    }
+
+    // (internal, external) edge counts of each community: edges with both ends in
+    // it, and edges from it to other communities. Edges between two communities
+    // count as external for both. Every node in the graph must be assigned to a
+    // community.
+    fn community_edge_counts(
+        &self,
+        communities: &HashMap<NodeId, usize>,
+    ) -> HashMap<usize, (usize, usize)> {
+        let mut counts: HashMap<usize, (usize, usize)> = HashMap::new();
+        for node in self.get_nodes_iter() {
+            let id = node.get_id();
+            let c = communities[&id];
+            let count = counts.entry(c).or_insert((0, 0));
+            for e in node.get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                if communities[&neighbor_id] != c {
+                    count.1 += 1;
+                } else if id <= neighbor_id {
+                    // each internal edge once, from its end with the smaller id.
+                    count.0 += 1;
+                }
+            }
+        }
+        counts
+    }
+}
+
+pub trait WeightedModularity: GraphBase<NodeType = WeightedNode> {
+    // Same as Modularity::modularity, with edge counts replaced by edge weights and
+    // degrees replaced by node strengths.
+    fn weighted_modularity(&self, communities: &HashMap<NodeId, usize>) -> f64 {
+        let mut internal: HashMap<usize, f64> = HashMap::new();
+        let mut degrees: HashMap<usize, f64> = HashMap::new();
+        let mut total_weight: f64 = 0.0;
+        for node in self.get_nodes_iter() {
+            let c = communities[&node.node_id];
+            *degrees.entry(c).or_insert(0.0) += node.weight();
+            total_weight += node.weight();
+            for e in &node.edges {
+                if communities[&e.target_id] == c {
+                    *internal.entry(c).or_insert(0.0) += e.weight / 2.0;
+                }
+            }
+        }
+        modularity_from_totals(&internal, &degrees, total_weight / 2.0)
+    }
+
+    // Louvain method (Blondel et al., 2008) maximizing weighted modularity, with
+    // the expected internal weight of each community scaled by resolution (higher
+    // values give more, smaller communities). Each level moves nodes, in an order
+    // shuffled from seed, to the neighboring community with the best modularity
+    // gain until no move helps, and then merges each community into a single node,
+    // whose edges carry the summed weights of the edges between communities (and
+    // a self-loop their internal weight). Stops once a level moves nothing.
+    // Communities are numbered 0.. in order of their smallest node id.
+    fn weighted_louvain(&self, resolution: f64, seed: u64) -> HashMap<NodeId, usize> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let node_ids = self.get_ordered_node_ids();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        // symmetric weighted adjacency of the current level, where a self-loop
+        // counts for both of its ends.
+        let mut adjacency: Vec<BTreeMap<usize, f64>> = node_ids
+            .iter()
+            .map(|id| {
+                let mut row: BTreeMap<usize, f64> = BTreeMap::new();
+                for e in &self.get_node(*id).edges {
+                    *row.entry(positions[&e.target_id]).or_insert(0.0) += e.weight;
+                }
+                row
+            })
+            .collect();
+        // position of each original node at the current level.
+        let mut membership: Vec<usize> = (0..node_ids.len()).collect();
+        let total_weight: f64 = adjacency.iter().flat_map(|row| row.values()).sum();
+
+        loop {
+            // without edges, every node stays on its own.
+            if total_weight <= 0.0 {
+                break;
+            }
+            let n = adjacency.len();
+            let strengths: Vec<f64> = adjacency.iter().map(|row| row.values().sum()).collect();
+            let mut community: Vec<usize> = (0..n).collect();
+            let mut community_strengths: Vec<f64> = strengths.clone();
+            let mut order: Vec<usize> = (0..n).collect();
+            let mut moved = false;
+            loop {
+                order.shuffle(&mut rng);
+                let mut improved = false;
+                for i in &order {
+                    let current = community[*i];
+                    community_strengths[current] -= strengths[*i];
+                    let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+                    for (j, weight) in &adjacency[*i] {
+                        if j != i {
+                            *links.entry(community[*j]).or_insert(0.0) += weight;
+                        }
+                    }
+                    let gain = |c: usize, link: f64| {
+                        link - resolution * community_strengths[c] * strengths[*i] / total_weight
+                    };
+                    let mut best = current;
+                    let mut best_gain = gain(current, *links.get(&current).unwrap_or(&0.0));
+                    for (c, link) in &links {
+                        if gain(*c, *link) > best_gain {
+                            best = *c;
+                            best_gain = gain(*c, *link);
+                        }
+                    }
+                    community_strengths[best] += strengths[*i];
+                    if best != current {
+                        community[*i] = best;
+                        improved = true;
+                        moved = true;
+                    }
+                }
+                if !improved {
+                    break;
+                }
+            }
+            if !moved {
+                break;
+            }
+
+            // merge each community into a node of the next level.
+            let mut levels: BTreeMap<usize, usize> = BTreeMap::new();
+            for c in &community {
+                let next = levels.len();
+                levels.entry(*c).or_insert(next);
+            }
+            let mut aggregated: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); levels.len()];
+            for (i, row) in adjacency.iter().enumerate() {
+                for (j, weight) in row {
+                    *aggregated[levels[&community[i]]]
+                        .entry(levels[&community[*j]])
+                        .or_insert(0.0) += weight;
+                }
+            }
+            for position in membership.iter_mut() {
+                *position = levels[&community[*position]];
+            }
+            adjacency = aggregated;
+        }
+
+        let mut community_ids: HashMap<usize, usize> = HashMap::new();
+        node_ids
+            .iter()
+            .zip(membership)
+            .map(|(id, position)| {
+                let next_id = community_ids.len();
+                (*id, *community_ids.entry(position).or_insert(next_id))
+            })
+            .collect()
+    }
+}
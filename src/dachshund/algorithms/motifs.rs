@@ -0,0 +1,315 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Connected 3-node subgraphs (up to isomorphism).
+#[derive(Hash, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Motif3 {
+    // a-b-c, with no edge between a and c.
+    Path,
+    Triangle,
+}
+
+/// Connected 4-node subgraphs (up to isomorphism).
+#[derive(Hash, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Motif4 {
+    // a-b-c-d
+    Path,
+    // one center connected to three leaves (a.k.a. claw)
+    Star,
+    // a-b-c-d-a
+    Cycle,
+    // a triangle with a pendant edge (a.k.a. paw)
+    TailedTriangle,
+    // K4 with one edge removed
+    Diamond,
+    Clique,
+}
+
+pub trait Motifs: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId, NodeSetType = FxHashSet<NodeId>>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    fn get_neighbor_sets(&self) -> HashMap<NodeId, FxHashSet<NodeId>> {
+        self.get_nodes_iter()
+            .map(|node| {
+                (
+                    node.get_id(),
+                    node.get_edges()
+                        .map(|e| e.get_neighbor_id())
+                        .filter(|x| *x != node.get_id())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    // Counts occurrences of each connected 3-node induced subgraph. Every triangle
+    // closes three paths, so open paths are the connected triples minus 3 * triangles.
+    fn motif_census_3(&self) -> HashMap<Motif3, usize> {
+        let neighbors = self.get_neighbor_sets();
+        let mut num_triples: usize = 0;
+        let mut num_triangles: usize = 0;
+        for (id, nbrs) in &neighbors {
+            let d = nbrs.len();
+            num_triples += d * d.saturating_sub(1) / 2;
+            // count each triangle once, from its smallest node.
+            for a in nbrs.iter().filter(|x| *x > id) {
+                num_triangles += nbrs
+                    .iter()
+                    .filter(|b| *b > a && neighbors[a].contains(b))
+                    .count();
+            }
+        }
+        let mut census: HashMap<Motif3, usize> = HashMap::new();
+        census.insert(Motif3::Path, num_triples - 3 * num_triangles);
+        census.insert(Motif3::Triangle, num_triangles);
+        census
+    }
+
+    // Counts occurrences of each connected 4-node induced subgraph. Node sets are
+    // grown one neighbor at a time from every node, deduplicated, and classified by
+    // their edge count and maximum degree.
+    fn motif_census_4(&self) -> HashMap<Motif4, usize> {
+        let neighbors = self.get_neighbor_sets();
+        let mut frontier: HashSet<BTreeSet<NodeId>> =
+            neighbors.keys().map(|id| BTreeSet::from([*id])).collect();
+        for _ in 1..4 {
+            let mut next: HashSet<BTreeSet<NodeId>> = HashSet::new();
+            for node_set in &frontier {
+                for id in node_set {
+                    for nid in &neighbors[id] {
+                        if !node_set.contains(nid) {
+                            let mut grown = node_set.clone();
+                            grown.insert(*nid);
+                            next.insert(grown);
+                        }
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let mut census: HashMap<Motif4, usize> = [
+            Motif4::Path,
+            Motif4::Star,
+            Motif4::Cycle,
+            Motif4::TailedTriangle,
+            Motif4::Diamond,
+            Motif4::Clique,
+        ]
+        .into_iter()
+        .map(|m| (m, 0))
+        .collect();
+        for node_set in frontier {
+            let degrees: Vec<usize> = node_set
+                .iter()
+                .map(|id| {
+                    node_set
+                        .iter()
+                        .filter(|x| neighbors[id].contains(x))
+                        .count()
+                })
+                .collect();
+            let num_edges = degrees.iter().sum::<usize>() / 2;
+            let max_degree = *degrees.iter().max().unwrap();
+            let motif = match (num_edges, max_degree) {
+                (3, 3) => Motif4::Star,
+                (3, _) => Motif4::Path,
+                (4, 3) => Motif4::TailedTriangle,
+                (4, _) => Motif4::Cycle,
+                (5, _) => Motif4::Diamond,
+                _ => Motif4::Clique,
+            };
+            *census.get_mut(&motif).unwrap() += 1;
+        }
+        census
+    }
+    // Canonical edge list, as sorted (smaller, larger) pairs of positions 0..n,
+    // such that two graphs with the same number of nodes are isomorphic iff their
+    // canonical forms are equal. Nodes are first split into cells by color
+    // refinement (starting from degrees, nodes are told apart by the colors of
+    // their neighbors), which doesn't depend on node ids. Cells take consecutive
+    // positions in a fixed order, and the smallest edge list over all orderings
+    // of the nodes within each cell is returned. This takes up to n! steps on
+    // regular graphs, so it's only meant for small graphs such as motifs.
+    fn canonical_form(&self) -> Vec<(usize, usize)> {
+        let neighbor_sets = self.get_neighbor_sets();
+        let node_ids = self.get_ordered_node_ids();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let neighbors: Vec<Vec<usize>> = node_ids
+            .iter()
+            .map(|id| neighbor_sets[id].iter().map(|x| positions[x]).collect())
+            .collect();
+
+        let mut colors: Vec<usize> = neighbors.iter().map(|x| x.len()).collect();
+        loop {
+            let signatures: Vec<(usize, Vec<usize>)> = neighbors
+                .iter()
+                .enumerate()
+                .map(|(i, nbrs)| {
+                    let mut nbr_colors: Vec<usize> = nbrs.iter().map(|j| colors[*j]).collect();
+                    nbr_colors.sort_unstable();
+                    (colors[i], nbr_colors)
+                })
+                .collect();
+            let mut distinct: Vec<&(usize, Vec<usize>)> = signatures.iter().collect();
+            distinct.sort_unstable();
+            distinct.dedup();
+            let refined: Vec<usize> = signatures
+                .iter()
+                .map(|x| distinct.binary_search(&x).unwrap())
+                .collect();
+            let num_colors = colors.iter().collect::<HashSet<_>>().len();
+            colors = refined;
+            if distinct.len() == num_colors {
+                break;
+            }
+        }
+
+        // cell_of[p] is the color of the nodes that may be placed at position p.
+        let mut cell_of: Vec<usize> = colors.clone();
+        cell_of.sort_unstable();
+        let mut best: Option<Vec<(usize, usize)>> = None;
+        let mut order: Vec<usize> = Vec::with_capacity(node_ids.len());
+        let mut placed: Vec<bool> = vec![false; node_ids.len()];
+        canonical_search(
+            &neighbors,
+            &colors,
+            &cell_of,
+            &mut order,
+            &mut placed,
+            &mut best,
+        );
+        best.unwrap_or_default()
+    }
+
+    // All connected induced subgraphs on k nodes, as sorted node sets in increasing
+    // order, found with the ESU algorithm (Wernicke, "Efficient detection of network
+    // motifs", 2006). Each set is grown from its smallest node, only ever adding
+    // larger nodes that are neighbors of the newest node but of no earlier one,
+    // so that every set is reached exactly once. The output alone can grow as
+    // n * max_degree^(k - 1), so k is limited to MAX_ENUMERATED_SUBGRAPH_SIZE.
+    fn enumerate_connected_subgraphs(&self, k: usize) -> CLQResult<Vec<Vec<NodeId>>> {
+        if k == 0 || k > MAX_ENUMERATED_SUBGRAPH_SIZE {
+            return Err(CLQError::InvalidParameter(format!(
+                "subgraph size must be in 1..={}, got {}.",
+                MAX_ENUMERATED_SUBGRAPH_SIZE, k
+            )));
+        }
+        let neighbors = self.get_neighbor_sets();
+        let mut subgraphs: Vec<Vec<NodeId>> = Vec::new();
+        for root in self.get_ordered_node_ids() {
+            let mut extension: Vec<NodeId> = neighbors[&root]
+                .iter()
+                .filter(|x| **x > root)
+                .cloned()
+                .collect();
+            extension.sort();
+            extend_subgraph(
+                &neighbors,
+                root,
+                k,
+                &mut vec![root],
+                extension,
+                &mut subgraphs,
+            );
+        }
+        for subgraph in subgraphs.iter_mut() {
+            subgraph.sort();
+        }
+        subgraphs.sort();
+        Ok(subgraphs)
+    }
+}
+
+// Largest subgraph size accepted by Motifs::enumerate_connected_subgraphs.
+pub const MAX_ENUMERATED_SUBGRAPH_SIZE: usize = 8;
+
+// ESU recursion: adds each node of extension in turn to subgraph, extending the
+// candidates with that node's exclusive neighbors larger than root.
+fn extend_subgraph(
+    neighbors: &HashMap<NodeId, FxHashSet<NodeId>>,
+    root: NodeId,
+    k: usize,
+    subgraph: &mut Vec<NodeId>,
+    mut extension: Vec<NodeId>,
+    subgraphs: &mut Vec<Vec<NodeId>>,
+) {
+    if subgraph.len() == k {
+        subgraphs.push(subgraph.clone());
+        return;
+    }
+    while let Some(w) = extension.pop() {
+        let mut next_extension = extension.clone();
+        for u in &neighbors[&w] {
+            if *u > root
+                && !subgraph.contains(u)
+                && !subgraph.iter().any(|x| neighbors[x].contains(u))
+                && !next_extension.contains(u)
+            {
+                next_extension.push(*u);
+            }
+        }
+        next_extension.sort();
+        subgraph.push(w);
+        extend_subgraph(neighbors, root, k, subgraph, next_extension, subgraphs);
+        subgraph.pop();
+    }
+}
+
+// Tries every placement of nodes into positions consistent with their colors,
+// keeping the smallest sorted edge list in best.
+fn canonical_search(
+    neighbors: &[Vec<usize>],
+    colors: &[usize],
+    cell_of: &[usize],
+    order: &mut Vec<usize>,
+    placed: &mut [bool],
+    best: &mut Option<Vec<(usize, usize)>>,
+) {
+    let position = order.len();
+    if position == neighbors.len() {
+        let mut new_positions: Vec<usize> = vec![0; order.len()];
+        for (p, node) in order.iter().enumerate() {
+            new_positions[*node] = p;
+        }
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for (i, nbrs) in neighbors.iter().enumerate() {
+            for j in nbrs {
+                let (a, b) = (new_positions[i], new_positions[*j]);
+                if a < b {
+                    edges.push((a, b));
+                }
+            }
+        }
+        edges.sort_unstable();
+        if best.as_ref().is_none_or(|x| edges < *x) {
+            *best = Some(edges);
+        }
+        return;
+    }
+    for node in 0..neighbors.len() {
+        if !placed[node] && colors[node] == cell_of[position] {
+            placed[node] = true;
+            order.push(node);
+            canonical_search(neighbors, colors, cell_of, order, placed, best);
+            order.pop();
+            placed[node] = false;
+        }
+    }
+}
@@ -0,0 +1,111 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{WeightedDirectedNode, WeightedNodeBase};
+use std::collections::HashMap;
+
+pub trait PageRank: GraphBase<NodeType = WeightedDirectedNode> {
+    // Power iteration for PageRank on a weighted directed graph. With probability
+    // damping, the random surfer follows an out-edge, picked proportionally to its
+    // weight; otherwise it teleports to a node picked uniformly at random. Dangling
+    // nodes (no out-edges, or only zero-weight ones) redistribute their rank
+    // uniformly over all nodes. Stops after max_iter iterations, or once the ranks
+    // move by at most tolerance in total (L1 norm). Ranks sum to 1.0.
+    fn pagerank(&self, damping: f64, max_iter: usize, tolerance: f64) -> HashMap<NodeId, f64> {
+        let n = self.count_nodes();
+        let teleport: HashMap<NodeId, f64> = self
+            .get_ids_iter()
+            .map(|id| (*id, 1.0 / n as f64))
+            .collect();
+        self._pagerank(damping, max_iter, tolerance, &teleport)
+    }
+
+    // Same as pagerank, but the random surfer teleports to each node (and dangling
+    // nodes redistribute their rank) with probability proportional to the node's
+    // weight, i.e. its total in- and out-edge weight, so that heavy nodes anchor
+    // the ranking. Fails on a NaN or negative node weight, or unless some node has
+    // a positive weight.
+    fn node_weighted_pagerank(
+        &self,
+        damping: f64,
+        tolerance: f64,
+        max_iter: usize,
+    ) -> CLQResult<HashMap<NodeId, f64>> {
+        let weights: HashMap<NodeId, f64> = self
+            .get_nodes_iter()
+            .map(|node| (node.node_id, node.weight()))
+            .collect();
+        for (id, weight) in &weights {
+            if weight.is_nan() {
+                return Err(CLQError::InvalidParameter(format!(
+                    "node {} has a NaN weight.",
+                    id.value()
+                )));
+            }
+            if *weight < 0.0 {
+                return Err(CLQError::NegativeWeight(*weight));
+            }
+        }
+        let total_weight: f64 = weights.values().sum();
+        if total_weight <= 0.0 {
+            return Err(CLQError::InvalidParameter(
+                "node weights must have a positive sum.".to_owned(),
+            ));
+        }
+        let teleport: HashMap<NodeId, f64> = weights
+            .into_iter()
+            .map(|(id, weight)| (id, weight / total_weight))
+            .collect();
+        Ok(self._pagerank(damping, max_iter, tolerance, &teleport))
+    }
+
+    // Power iteration shared by the PageRank variants, with teleport giving the
+    // probability of landing on each node when teleporting (summing to 1.0).
+    fn _pagerank(
+        &self,
+        damping: f64,
+        max_iter: usize,
+        tolerance: f64,
+        teleport: &HashMap<NodeId, f64>,
+    ) -> HashMap<NodeId, f64> {
+        let mut ranks: HashMap<NodeId, f64> = teleport.clone();
+        let out_weights: HashMap<NodeId, f64> = self
+            .get_nodes_iter()
+            .map(|node| (node.node_id, node.out_weight()))
+            .collect();
+        for _ in 0..max_iter {
+            let dangling_rank: f64 = ranks
+                .iter()
+                .filter(|(id, _)| out_weights[id] <= 0.0)
+                .map(|(_, rank)| rank)
+                .sum();
+            let base = 1.0 - damping + damping * dangling_rank;
+            let mut next: HashMap<NodeId, f64> = teleport
+                .iter()
+                .map(|(id, share)| (*id, base * share))
+                .collect();
+            for node in self.get_nodes_iter() {
+                let out_weight = out_weights[&node.node_id];
+                if out_weight <= 0.0 {
+                    continue;
+                }
+                let rank = ranks[&node.node_id];
+                for e in &node.out_edges {
+                    *next.get_mut(&e.target_id).unwrap() += damping * rank * e.weight / out_weight;
+                }
+            }
+            let change: f64 = next.iter().map(|(id, rank)| (rank - ranks[id]).abs()).sum();
+            ranks = next;
+            if change <= tolerance {
+                break;
+            }
+        }
+        ranks
+    }
+}
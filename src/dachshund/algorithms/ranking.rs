@@ -0,0 +1,20 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::id_types::NodeId;
+use std::collections::HashMap;
+
+pub fn top_k_by_value(map: &HashMap<NodeId, f64>, k: usize) -> Vec<(NodeId, f64)> {
+    // Returns the k nodes with the highest values (e.g. centrality scores), in
+    // descending order of value. Ties are broken by smallest NodeId, so the output
+    // doesn't depend on the iteration order of the map.
+    let mut sorted: Vec<(NodeId, f64)> = map.iter().map(|(id, value)| (*id, *value)).collect();
+    sorted.sort_unstable_by(|(id1, value1), (id2, value2)| {
+        value2.total_cmp(value1).then_with(|| id1.cmp(id2))
+    });
+    sorted.truncate(k);
+    sorted
+}
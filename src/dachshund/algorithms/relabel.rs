@@ -0,0 +1,33 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::NodeBase;
+use std::collections::HashMap;
+
+/// Implemented by graphs that can produce a copy of themselves with node ids
+/// 0..n, e.g. to index dense matrices or arrays by node id.
+pub trait RelabelContiguous: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+{
+    // Maps the nodes to 0..n in increasing order of id. Returns the old -> new
+    // mapping, and the new -> old one as a vector indexed by new id.
+    fn _get_contiguous_ids(&self) -> (HashMap<NodeId, NodeId>, Vec<NodeId>) {
+        let new_to_old = self.get_ordered_node_ids();
+        let old_to_new: HashMap<NodeId, NodeId> = new_to_old
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, NodeId::from(i as i64)))
+            .collect();
+        (old_to_new, new_to_old)
+    }
+    // Returns a copy of the graph whose nodes are numbered 0..n, in the same order
+    // as their original ids, along with the old -> new id mapping and the new -> old
+    // one (as a vector indexed by new id).
+    fn relabel_contiguous(&self) -> (Self, HashMap<NodeId, NodeId>, Vec<NodeId>);
+}
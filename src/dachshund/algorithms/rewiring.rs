@@ -0,0 +1,69 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::SimpleNode;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+pub trait Rewiring: GraphBase<NodeType = SimpleNode> {
+    // Attempts num_swaps double-edge swaps: picks two edges (a, b) and (c, d) at random
+    // and rewires them to (a, d) and (c, b), unless that would create a self-loop or a
+    // parallel edge. Node degrees are preserved exactly. Returns the number of swaps
+    // that were actually performed.
+    fn double_edge_swap(&mut self, num_swaps: usize, seed: u64) -> CLQResult<usize> {
+        let mut edges: Vec<(NodeId, NodeId)> = Vec::new();
+        for node in self.get_nodes_iter() {
+            for neighbor_id in &node.neighbors {
+                if node.node_id < *neighbor_id {
+                    edges.push((node.node_id, *neighbor_id));
+                }
+            }
+        }
+        if edges.len() < 2 {
+            return Err(CLQError::InvalidParameter(
+                "graph needs at least two edges to perform a double-edge swap.".to_owned(),
+            ));
+        }
+        // sorted so that the outcome only depends on the seed.
+        edges.sort();
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut num_successful_swaps: usize = 0;
+        for _ in 0..num_swaps {
+            let i = rng.gen_range(0..edges.len());
+            let j = rng.gen_range(0..edges.len());
+            if i == j {
+                continue;
+            }
+            let (a, b) = edges[i];
+            // pick an orientation for the second edge, so both rewirings are reachable.
+            let (c, d) = if rng.gen::<bool>() {
+                edges[j]
+            } else {
+                (edges[j].1, edges[j].0)
+            };
+            if a == d || c == b {
+                continue;
+            }
+            let nodes = self.get_mut_nodes();
+            if nodes[&a].neighbors.contains(&d) || nodes[&c].neighbors.contains(&b) {
+                continue;
+            }
+            for (x, old, new) in [(a, b, d), (b, a, c), (c, d, b), (d, c, a)] {
+                let neighbors = &mut nodes.get_mut(&x).unwrap().neighbors;
+                neighbors.remove(&old);
+                neighbors.insert(new);
+            }
+            edges[i] = (a.min(d), a.max(d));
+            edges[j] = (c.min(b), c.max(b));
+            num_successful_swaps += 1;
+        }
+        Ok(num_successful_swaps)
+    }
+}
@@ -0,0 +1,72 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::clustering::Clustering;
+use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use fxhash::FxHashSet;
+use std::collections::{HashMap, HashSet};
+
+/// Names of the entries of the vectors returned by `StructuralRoles::structural_features`, in order.
+pub const STRUCTURAL_FEATURE_NAMES: [&str; 5] = [
+    "degree",
+    "clustering_coefficient",
+    "num_triangles",
+    "average_neighbor_degree",
+    "two_hop_neighborhood_size",
+];
+
+pub trait StructuralRoles: GraphBase + Clustering + Transitivity
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId, NodeSetType = FxHashSet<NodeId>>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Number of other nodes at most two hops away from node_id.
+    fn two_hop_neighborhood_size(&self, node_id: NodeId) -> usize {
+        let mut reached: HashSet<NodeId> = HashSet::new();
+        for e in self.get_node(node_id).get_edges() {
+            let neighbor_id = e.get_neighbor_id();
+            reached.insert(neighbor_id);
+            for e2 in self.get_node(neighbor_id).get_edges() {
+                reached.insert(e2.get_neighbor_id());
+            }
+        }
+        reached.remove(&node_id);
+        reached.len()
+    }
+    // Mean degree of the neighbors of node_id (0.0 for isolated nodes).
+    fn average_neighbor_degree(&self, node_id: NodeId) -> f64 {
+        let node = self.get_node(node_id);
+        if node.degree() == 0 {
+            return 0.0;
+        }
+        let degree_sum: usize = node
+            .get_edges()
+            .map(|e| self.get_node(e.get_neighbor_id()).degree())
+            .sum();
+        degree_sum as f64 / node.degree() as f64
+    }
+    // Structural feature vector of each node, for RolX-style role discovery: nodes
+    // with similar vectors play similar roles (hub, bridge, clique member, ...)
+    // wherever they are in the graph. Entries are listed in STRUCTURAL_FEATURE_NAMES;
+    // the clustering coefficient of nodes with fewer than 2 neighbors is 0.0.
+    fn structural_features(&self) -> HashMap<NodeId, Vec<f64>> {
+        self.get_ids_iter()
+            .map(|id| {
+                let features = vec![
+                    self.get_node(*id).degree() as f64,
+                    self.get_clustering_coefficient(*id).unwrap_or(0.0),
+                    self.triangle_count(*id) as f64,
+                    self.average_neighbor_degree(*id),
+                    self.two_hop_neighborhood_size(*id) as f64,
+                ];
+                (*id, features)
+            })
+            .collect()
+    }
+}
@@ -4,10 +4,15 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase};
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode};
+use ordered_float::NotNan;
+use rayon::prelude::*;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 type NodePredecessors = HashMap<NodeId, Vec<NodeId>>;
 pub trait ShortestPaths: GraphBase
@@ -127,6 +132,110 @@ where
         (stack, shortest_path_counts, preds)
     }
 
+    // Hop counts from source to every node it can reach (including itself).
+    fn get_hop_distances(&self, source: NodeId) -> HashMap<NodeId, usize> {
+        let mut dists: HashMap<NodeId, usize> = HashMap::new();
+        dists.insert(source, 0);
+        let mut queue: VecDeque<NodeId> = VecDeque::new();
+        queue.push_back(source);
+        while let Some(id) = queue.pop_front() {
+            let d = dists[&id];
+            for e in self.get_node(id).get_edges() {
+                let nid = e.get_neighbor_id();
+                if let Entry::Vacant(entry) = dists.entry(nid) {
+                    entry.insert(d + 1);
+                    queue.push_back(nid);
+                }
+            }
+        }
+        dists
+    }
+
+    // Same as get_hop_distances, using a level-synchronous BFS: the neighbors of
+    // each frontier are explored in parallel (on rayon's global thread pool), and
+    // nodes are claimed for the next frontier with atomic flags.
+    fn bfs_distances_parallel(&self, source: NodeId) -> HashMap<NodeId, usize>
+    where
+        Self: Sync,
+    {
+        let mut dists: HashMap<NodeId, usize> = HashMap::new();
+        if !self.has_node(source) {
+            return dists;
+        }
+        let node_ids: Vec<NodeId> = self.get_ids_iter().cloned().collect();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let visited: Vec<AtomicBool> = node_ids.iter().map(|_| AtomicBool::new(false)).collect();
+        visited[positions[&source]].store(true, Ordering::Relaxed);
+        let mut frontier: Vec<NodeId> = vec![source];
+        let mut depth: usize = 0;
+        while !frontier.is_empty() {
+            for id in &frontier {
+                dists.insert(*id, depth);
+            }
+            frontier = frontier
+                .par_iter()
+                .flat_map_iter(|id| {
+                    self.get_node(*id)
+                        .get_edges()
+                        .map(|e| e.get_neighbor_id())
+                        .filter(|nid| {
+                            visited[positions[nid]]
+                                .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+                                .is_ok()
+                        })
+                        .collect::<Vec<NodeId>>()
+                })
+                .collect();
+            depth += 1;
+        }
+        dists
+    }
+
+    // BFS tree rooted at source: maps every node reachable from source to its
+    // parent on a shortest path from source, with source mapped to itself.
+    // Following parents from any node in the tree retraces a shortest path.
+    fn shortest_path_tree(&self, source: NodeId) -> HashMap<NodeId, NodeId> {
+        let mut parents: HashMap<NodeId, NodeId> = HashMap::new();
+        if !self.has_node(source) {
+            return parents;
+        }
+        parents.insert(source, source);
+        let mut queue: VecDeque<NodeId> = VecDeque::new();
+        queue.push_back(source);
+        while let Some(id) = queue.pop_front() {
+            for e in self.get_node(id).get_edges() {
+                let nid = e.get_neighbor_id();
+                if let Entry::Vacant(entry) = parents.entry(nid) {
+                    entry.insert(id);
+                    queue.push_back(nid);
+                }
+            }
+        }
+        parents
+    }
+
+    // N(h) for h = 0..=max_hops: the number of ordered pairs of distinct nodes
+    // (u, v) such that v can be reached from u in at most h hops. It reaches
+    // n * (n - 1) at the diameter of a connected graph.
+    fn neighborhood_function(&self, max_hops: usize) -> Vec<usize> {
+        let mut num_pairs: Vec<usize> = vec![0; max_hops + 1];
+        for id in self.get_ids_iter() {
+            for d in self.get_hop_distances(*id).into_values() {
+                if d > 0 && d <= max_hops {
+                    num_pairs[d] += 1;
+                }
+            }
+        }
+        for h in 1..=max_hops {
+            num_pairs[h] += num_pairs[h - 1];
+        }
+        num_pairs
+    }
+
     fn retrace_parent_paths(
         &self,
         node_id: &NodeId,
@@ -177,3 +286,62 @@ where
         paths
     }
 }
+
+pub trait WeightedShortestPaths: GraphBase<NodeType = WeightedNode> {
+    // Widest (maximum bottleneck) path from source to target: the path whose
+    // lightest edge is as heavy as possible, treating edge weights as capacities.
+    // Found by Dijkstra's algorithm with a max-heap on the bottleneck so far.
+    // Returns the path, from source to target, and its bottleneck (infinite if
+    // source is target), or None if target can't be reached from source. Fails on
+    // a NaN edge weight.
+    fn widest_path(&self, source: NodeId, target: NodeId) -> CLQResult<Option<(Vec<NodeId>, f64)>> {
+        if !self.has_node(source) || !self.has_node(target) {
+            return Ok(None);
+        }
+        // widest bottleneck found so far to each node, final once the node is visited.
+        let mut widths: HashMap<NodeId, NotNan<f64>> = HashMap::new();
+        let mut visited: HashSet<NodeId> = HashSet::new();
+        let mut parents: HashMap<NodeId, NodeId> = HashMap::new();
+        let mut heap: BinaryHeap<(NotNan<f64>, NodeId)> = BinaryHeap::new();
+        widths.insert(source, NotNan::new(f64::INFINITY).unwrap());
+        heap.push((widths[&source], source));
+        while let Some((width, id)) = heap.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if id == target {
+                break;
+            }
+            for e in &self.get_node(id).edges {
+                if visited.contains(&e.target_id) {
+                    continue;
+                }
+                let weight = NotNan::new(e.weight).map_err(|_| {
+                    CLQError::InvalidParameter(format!(
+                        "edge ({}, {}) has a NaN weight.",
+                        id.value(),
+                        e.target_id.value()
+                    ))
+                })?;
+                let alt = width.min(weight);
+                if widths.get(&e.target_id).is_none_or(|w| alt > *w) {
+                    widths.insert(e.target_id, alt);
+                    parents.insert(e.target_id, id);
+                    heap.push((alt, e.target_id));
+                }
+            }
+        }
+        if !visited.contains(&target) {
+            return Ok(None);
+        }
+        let width = widths[&target].into_inner();
+        let mut path: Vec<NodeId> = vec![target];
+        let mut current = target;
+        while current != source {
+            current = parents[&current];
+            path.push(current);
+        }
+        path.reverse();
+        Ok(Some((path, width)))
+    }
+}
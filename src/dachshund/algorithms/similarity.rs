@@ -0,0 +1,64 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode};
+use std::collections::{HashMap, HashSet};
+
+pub trait Similarity: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Number of shared neighbors of a and b over the number of nodes adjacent to
+    // either. 0.0 if neither has any neighbors.
+    fn jaccard_similarity(&self, a: NodeId, b: NodeId) -> f64 {
+        let neighbors = |id: NodeId| -> HashSet<NodeId> {
+            self.get_node(id)
+                .get_edges()
+                .map(|e| e.get_neighbor_id())
+                .collect()
+        };
+        let (neighbors_a, neighbors_b) = (neighbors(a), neighbors(b));
+        let num_either = neighbors_a.union(&neighbors_b).count();
+        if num_either == 0 {
+            return 0.0;
+        }
+        neighbors_a.intersection(&neighbors_b).count() as f64 / num_either as f64
+    }
+}
+
+pub trait WeightedSimilarity: GraphBase<NodeType = WeightedNode> {
+    // Generalizes the Jaccard similarity to edge weights: for every node adjacent
+    // to a or b, compares the weights of its ties to a and b (0.0 if absent), and
+    // returns the sum of the smaller weights over the sum of the larger ones. Equal
+    // to jaccard_similarity when all weights are 1.0. 0.0 if a and b have no
+    // edge weight at all.
+    fn weighted_jaccard_similarity(&self, a: NodeId, b: NodeId) -> f64 {
+        let weights = |id: NodeId| -> HashMap<NodeId, f64> {
+            self.get_node(id)
+                .edges
+                .iter()
+                .map(|e| (e.target_id, e.weight))
+                .collect()
+        };
+        let (weights_a, weights_b) = (weights(a), weights(b));
+        let mut min_sum: f64 = 0.0;
+        let mut max_sum: f64 = 0.0;
+        let neighbor_ids: HashSet<&NodeId> = weights_a.keys().chain(weights_b.keys()).collect();
+        for id in neighbor_ids {
+            let weight_a = *weights_a.get(id).unwrap_or(&0.0);
+            let weight_b = *weights_b.get(id).unwrap_or(&0.0);
+            min_sum += weight_a.min(weight_b);
+            max_sum += weight_a.max(weight_b);
+        }
+        if max_sum <= 0.0 {
+            return 0.0;
+        }
+        min_sum / max_sum
+    }
+}
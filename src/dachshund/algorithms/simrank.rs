@@ -0,0 +1,142 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use std::collections::{HashMap, HashSet};
+
+// Unordered pair of nodes, stored as (smaller id, larger id) since SimRank is symmetric.
+fn ordered_pair(a: NodeId, b: NodeId) -> (NodeId, NodeId) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+pub trait SimRank: GraphBase
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    fn _get_neighbor_ids(&self, node_id: NodeId) -> Vec<NodeId> {
+        self.get_node(node_id)
+            .get_edges()
+            .map(|e| e.get_neighbor_id())
+            .collect()
+    }
+
+    // One SimRank update for the pair (a, b): decay times the average similarity
+    // over all pairs of their neighbors, looked up in prev.
+    fn _simrank_step(
+        &self,
+        a: NodeId,
+        b: NodeId,
+        decay: f64,
+        prev: &HashMap<(NodeId, NodeId), f64>,
+    ) -> f64 {
+        if a == b {
+            return 1.0;
+        }
+        let neighbors_a = self._get_neighbor_ids(a);
+        let neighbors_b = self._get_neighbor_ids(b);
+        if neighbors_a.is_empty() || neighbors_b.is_empty() {
+            return 0.0;
+        }
+        let mut total = 0.0;
+        for i in &neighbors_a {
+            for j in &neighbors_b {
+                total += prev.get(&ordered_pair(*i, *j)).unwrap_or(&0.0);
+            }
+        }
+        decay * total / (neighbors_a.len() * neighbors_b.len()) as f64
+    }
+
+    // Iterates the SimRank fixpoint: a node is fully similar to itself, and two
+    // distinct nodes are similar if their neighbors are, with
+    // s(a, b) = decay / (|N(a)| |N(b)|) * sum of s(i, j) over i in N(a), j in N(b).
+    // Stops after max_iter iterations, or once no score moves by more than
+    // tolerance. Returns a score for every ordered pair of nodes.
+    fn simrank(
+        &self,
+        decay: f64,
+        max_iter: usize,
+        tolerance: f64,
+    ) -> HashMap<(NodeId, NodeId), f64> {
+        let node_ids = self.get_ordered_node_ids();
+        let mut scores: HashMap<(NodeId, NodeId), f64> =
+            node_ids.iter().map(|id| ((*id, *id), 1.0)).collect();
+        for _ in 0..max_iter {
+            let mut next: HashMap<(NodeId, NodeId), f64> = HashMap::new();
+            let mut max_change: f64 = 0.0;
+            for (i, a) in node_ids.iter().enumerate() {
+                for b in &node_ids[i..] {
+                    let score = self._simrank_step(*a, *b, decay, &scores);
+                    let previous = scores.get(&(*a, *b)).unwrap_or(&0.0);
+                    max_change = max_change.max((score - previous).abs());
+                    if score > 0.0 {
+                        next.insert((*a, *b), score);
+                    }
+                }
+            }
+            scores = next;
+            if max_change <= tolerance {
+                break;
+            }
+        }
+        let mut all_scores: HashMap<(NodeId, NodeId), f64> = HashMap::new();
+        for a in &node_ids {
+            for b in &node_ids {
+                let score = scores.get(&ordered_pair(*a, *b)).unwrap_or(&0.0);
+                all_scores.insert((*a, *b), *score);
+            }
+        }
+        all_scores
+    }
+
+    // SimRank score of a single pair after max_iter iterations, without computing
+    // the full all-pairs matrix: the score after k iterations only depends on pairs
+    // of nodes within k hops of a and b respectively, so only those pairs are visited.
+    fn simrank_pair(&self, a: NodeId, b: NodeId, decay: f64, max_iter: usize) -> CLQResult<f64> {
+        for id in [a, b] {
+            if !self.has_node(id) {
+                return Err(CLQError::NodeNotFound(id));
+            }
+        }
+        // levels[k] holds the pairs whose score after max_iter - k iterations is needed.
+        let mut levels: Vec<HashSet<(NodeId, NodeId)>> = vec![HashSet::from([ordered_pair(a, b)])];
+        for k in 0..max_iter {
+            let mut next: HashSet<(NodeId, NodeId)> = HashSet::new();
+            for (x, y) in &levels[k] {
+                if x == y {
+                    continue;
+                }
+                for i in self._get_neighbor_ids(*x) {
+                    for j in self._get_neighbor_ids(*y) {
+                        next.insert(ordered_pair(i, j));
+                    }
+                }
+            }
+            levels.push(next);
+        }
+        // scores after 0 iterations: 1.0 on identical pairs, 0.0 elsewhere.
+        let mut scores: HashMap<(NodeId, NodeId), f64> = levels[max_iter]
+            .iter()
+            .filter(|(x, y)| x == y)
+            .map(|pair| (*pair, 1.0))
+            .collect();
+        for level in levels[..max_iter].iter().rev() {
+            scores = level
+                .iter()
+                .map(|(x, y)| ((*x, *y), self._simrank_step(*x, *y, decay, &scores)))
+                .collect();
+        }
+        // after 0 iterations, only identical pairs have a score.
+        Ok(*scores.get(&ordered_pair(a, b)).unwrap_or(&0.0))
+    }
+}
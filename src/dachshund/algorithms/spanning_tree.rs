@@ -0,0 +1,130 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::connected_components::{find_root, get_weighted_edge_list};
+use crate::dachshund::algorithms::connectivity::ConnectivityUndirected;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::{HashMap, HashSet};
+
+pub trait SpanningTree: GraphBase + ConnectivityUndirected
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Samples a spanning tree uniformly at random among all spanning trees of the
+    // graph, using Wilson's algorithm: starting from a root, every node not yet in
+    // the tree does a random walk until it hits the tree, and the loop-erased path
+    // of that walk is added to the tree. Returns the n - 1 tree edges, each as a
+    // (smaller id, larger id) pair. Fails if the graph is empty or disconnected.
+    fn uniform_spanning_tree(&self, seed: u64) -> CLQResult<Vec<(NodeId, NodeId)>> {
+        match self.get_is_connected() {
+            Ok(true) => (),
+            Ok(false) => return Err(CLQError::NotConnected),
+            Err(e) => return Err(CLQError::from(e)),
+        }
+        // sorted so that the outcome only depends on the seed.
+        let node_ids = self.get_ordered_node_ids();
+        let neighbors: HashMap<NodeId, Vec<NodeId>> = node_ids
+            .iter()
+            .map(|id| {
+                let mut nbrs: Vec<NodeId> = self
+                    .get_node(*id)
+                    .get_edges()
+                    .map(|e| e.get_neighbor_id())
+                    .filter(|nid| nid != id)
+                    .collect();
+                nbrs.sort();
+                (*id, nbrs)
+            })
+            .collect();
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut in_tree: HashSet<NodeId> = HashSet::new();
+        in_tree.insert(node_ids[0]);
+        // next[u] is the last step taken out of u in the current walk, so following
+        // it from the start of the walk retraces the walk with its loops erased.
+        let mut next: HashMap<NodeId, NodeId> = HashMap::new();
+        let mut edges: Vec<(NodeId, NodeId)> = Vec::with_capacity(node_ids.len() - 1);
+        for start in &node_ids[1..] {
+            let mut u = *start;
+            while !in_tree.contains(&u) {
+                let step = *neighbors[&u].choose(&mut rng).unwrap();
+                next.insert(u, step);
+                u = step;
+            }
+            u = *start;
+            while !in_tree.contains(&u) {
+                in_tree.insert(u);
+                let v = next[&u];
+                edges.push((u.min(v), u.max(v)));
+                u = v;
+            }
+        }
+        Ok(edges)
+    }
+}
+
+pub trait WeightedSpanningTree: GraphBase<NodeType = WeightedNode> {
+    // Spanning tree whose heaviest edge (the bottleneck) is as light as possible.
+    // Built with Kruskal's algorithm, adding edges from the lightest (ties broken by
+    // smallest ids) whenever they join two components, so the tree is also a minimum
+    // spanning tree, as every minimum spanning tree minimizes the bottleneck. Returns
+    // the n - 1 tree edges in the order they were added, each as (smaller id, larger
+    // id, weight). Fails if the graph is empty or disconnected, or on a NaN edge
+    // weight.
+    fn minimum_bottleneck_spanning_tree(&self) -> CLQResult<Vec<(NodeId, NodeId, f64)>> {
+        let num_nodes = self.count_nodes();
+        if num_nodes == 0 {
+            return Err(CLQError::InvalidParameter(
+                "graph must have at least one node.".to_owned(),
+            ));
+        }
+        let mut edges = get_weighted_edge_list(self)?;
+        edges.sort_by(|x, y| {
+            x.0.partial_cmp(&y.0)
+                .unwrap()
+                .then((x.1, x.2).cmp(&(y.1, y.2)))
+        });
+
+        let mut parents: HashMap<NodeId, NodeId> =
+            self.get_ids_iter().map(|id| (*id, *id)).collect();
+        let mut tree: Vec<(NodeId, NodeId, f64)> = Vec::with_capacity(num_nodes - 1);
+        for (weight, a, b) in edges {
+            if tree.len() == num_nodes - 1 {
+                break;
+            }
+            let (root_a, root_b) = (find_root(&mut parents, a), find_root(&mut parents, b));
+            if root_a != root_b {
+                parents.insert(root_a.max(root_b), root_a.min(root_b));
+                tree.push((a, b, weight));
+            }
+        }
+        if tree.len() < num_nodes - 1 {
+            return Err(CLQError::NotConnected);
+        }
+        Ok(tree)
+    }
+    // Weight of the heaviest edge of a minimum bottleneck spanning tree: the
+    // smallest w such that the edges weighing at most w connect the graph. Fails
+    // if the graph is disconnected, or has fewer than two nodes (no edges to
+    // take the maximum of).
+    fn bottleneck(&self) -> CLQResult<f64> {
+        self.minimum_bottleneck_spanning_tree()?
+            .into_iter()
+            .map(|(_, _, weight)| weight)
+            .reduce(f64::max)
+            .ok_or_else(|| {
+                CLQError::InvalidParameter(
+                    "a graph with a single node has no bottleneck.".to_owned(),
+                )
+            })
+    }
+}
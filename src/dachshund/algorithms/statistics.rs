@@ -0,0 +1,339 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::connected_components::{
+    ConnectedComponents, ConnectedComponentsUndirected,
+};
+use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::algorithms::ranking::top_k_by_value;
+use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase};
+use crate::dachshund::simple_undirected_graph::UndirectedGraph;
+use fxhash::FxHashSet;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Basic statistics describing an undirected graph, as computed by `Statistics::summary`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphSummary {
+    pub num_nodes: usize,
+    pub num_edges: usize,
+    pub density: f64,
+    pub min_degree: usize,
+    pub mean_degree: f64,
+    pub max_degree: usize,
+    pub num_connected_components: usize,
+    pub largest_component_size: usize,
+    // global clustering coefficient (transitivity), 0.0 if the graph has no connected triples.
+    pub global_clustering: f64,
+    // largest k such that the graph has a non-empty k-core.
+    pub degeneracy: usize,
+}
+impl fmt::Display for GraphSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "nodes: {}", self.num_nodes)?;
+        writeln!(f, "edges: {}", self.num_edges)?;
+        writeln!(f, "density: {:.4}", self.density)?;
+        writeln!(
+            f,
+            "degree (min / mean / max): {} / {:.4} / {}",
+            self.min_degree, self.mean_degree, self.max_degree
+        )?;
+        writeln!(f, "connected components: {}", self.num_connected_components)?;
+        writeln!(f, "largest component size: {}", self.largest_component_size)?;
+        writeln!(
+            f,
+            "global clustering coefficient: {:.4}",
+            self.global_clustering
+        )?;
+        write!(f, "degeneracy: {}", self.degeneracy)
+    }
+}
+
+pub trait Statistics: UndirectedGraph
+where
+    Self::NodeType: NodeBase<NodeIdType = NodeId>,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Number of possible edges in a simple undirected graph with this many nodes.
+    fn count_possible_edges(&self) -> usize {
+        let n = self.count_nodes();
+        n * n.saturating_sub(1) / 2
+    }
+    // Fraction of possible edges present in the graph (0.0 for graphs with < 2 nodes).
+    fn density(&self) -> f64 {
+        let possible_edges = self.count_possible_edges();
+        if possible_edges == 0 {
+            return 0.0;
+        }
+        self.count_edges() as f64 / possible_edges as f64
+    }
+    fn average_degree(&self) -> f64 {
+        let n = self.count_nodes();
+        if n == 0 {
+            return 0.0;
+        }
+        2.0 * self.count_edges() as f64 / n as f64
+    }
+    // Fraction of nodes with each degree.
+    fn degree_distribution(&self) -> HashMap<usize, f64> {
+        let n = self.count_nodes() as f64;
+        let mut distribution: HashMap<usize, f64> = HashMap::new();
+        for node in self.get_nodes_iter() {
+            *distribution.entry(node.degree()).or_insert(0.0) += 1.0 / n;
+        }
+        distribution
+    }
+    // Estimates degree_distribution from the degrees of a sample of (at least one)
+    // nodes, picked uniformly at random without replacement, which only needs to
+    // look at sample_fraction of the nodes. sample_fraction must be in (0.0, 1.0].
+    fn estimate_degree_distribution(
+        &self,
+        sample_fraction: f64,
+        seed: u64,
+    ) -> CLQResult<HashMap<usize, f64>> {
+        if !(sample_fraction > 0.0 && sample_fraction <= 1.0) {
+            return Err(CLQError::InvalidParameter(format!(
+                "sample fraction must be in (0.0, 1.0], got {}.",
+                sample_fraction
+            )));
+        }
+        let node_ids = self.get_ordered_node_ids();
+        let num_samples = ((node_ids.len() as f64 * sample_fraction).ceil() as usize).max(1);
+        let sample: Vec<&NodeId> = node_ids
+            .choose_multiple(&mut StdRng::seed_from_u64(seed), num_samples)
+            .collect();
+        let mut distribution: HashMap<usize, f64> = HashMap::new();
+        for id in &sample {
+            *distribution
+                .entry(self.get_node(**id).degree())
+                .or_insert(0.0) += 1.0 / sample.len() as f64;
+        }
+        Ok(distribution)
+    }
+    // Shannon entropy (in nats) of the degree distribution, i.e. of the degree of a
+    // node picked uniformly at random. 0.0 for regular graphs, and higher the more
+    // spread out degrees are.
+    fn degree_entropy(&self) -> f64 {
+        let n = self.count_nodes() as f64;
+        let mut degree_counts: HashMap<usize, usize> = HashMap::new();
+        for node in self.get_nodes_iter() {
+            *degree_counts.entry(node.degree()).or_insert(0) += 1;
+        }
+        degree_counts
+            .values()
+            .map(|count| {
+                let p = *count as f64 / n;
+                -p * p.ln()
+            })
+            .sum()
+    }
+    // Gini coefficient of the degree sequence: half the mean absolute difference
+    // between the degrees of two nodes, relative to the mean degree. Ranges from
+    // 0.0 (all degrees equal) to nearly 1.0 (all edges on a few hubs); 0.0 for
+    // graphs without edges.
+    fn degree_gini(&self) -> f64 {
+        let mut degrees: Vec<usize> = self.get_nodes_iter().map(|node| node.degree()).collect();
+        degrees.sort_unstable();
+        let n = degrees.len() as f64;
+        let degree_sum: usize = degrees.iter().sum();
+        if degree_sum == 0 {
+            return 0.0;
+        }
+        let weighted_sum: f64 = degrees
+            .iter()
+            .enumerate()
+            .map(|(i, degree)| (i + 1) as f64 * *degree as f64)
+            .sum();
+        2.0 * weighted_sum / (n * degree_sum as f64) - (n + 1.0) / n
+    }
+    // Newman's assortativity coefficient for a categorical node attribute (e.g. a
+    // node type): r = (sum_i e_ii - sum_i a_i^2) / (1 - sum_i a_i^2), where e_ij is
+    // the fraction of edge ends joining a node of category i to one of category j,
+    // and a_i the fraction of edge ends at nodes of category i. 1.0 if edges only
+    // join nodes of the same category, negative if they tend to join different
+    // ones, and around 0.0 if categories are mixed at random. Edges with an end
+    // missing from attr are ignored; 0.0 if that leaves no edges or only a single
+    // category.
+    fn attribute_assortativity(&self, attr: &HashMap<NodeId, usize>) -> f64 {
+        let mut same_category: f64 = 0.0;
+        let mut category_ends: HashMap<usize, f64> = HashMap::new();
+        let mut num_ends: f64 = 0.0;
+        for node in self.get_nodes_iter() {
+            let category = match attr.get(&node.get_id()) {
+                Some(category) => *category,
+                None => continue,
+            };
+            for e in node.get_edges() {
+                if let Some(neighbor_category) = attr.get(&e.get_neighbor_id()) {
+                    num_ends += 1.0;
+                    *category_ends.entry(category).or_insert(0.0) += 1.0;
+                    if *neighbor_category == category {
+                        same_category += 1.0;
+                    }
+                }
+            }
+        }
+        if num_ends == 0.0 {
+            return 0.0;
+        }
+        let expected_same: f64 = category_ends
+            .values()
+            .map(|count| (count / num_ends).powi(2))
+            .sum();
+        if expected_same >= 1.0 {
+            return 0.0;
+        }
+        (same_category / num_ends - expected_same) / (1.0 - expected_same)
+    }
+    // Joint degree matrix: the number of edges between nodes of degrees j <= k,
+    // keyed by (j, k), with zero entries left out. Normalized, it's the mixing
+    // matrix underlying degree assortativity.
+    fn joint_degree_matrix(&self) -> HashMap<(usize, usize), usize> {
+        let mut matrix: HashMap<(usize, usize), usize> = HashMap::new();
+        for node in self.get_nodes_iter() {
+            let id = node.get_id();
+            for e in node.get_edges() {
+                let neighbor_id = e.get_neighbor_id();
+                // each edge once, from its end with the smaller id.
+                if id > neighbor_id {
+                    continue;
+                }
+                let (j, k) = (node.degree(), self.get_node(neighbor_id).degree());
+                *matrix.entry((j.min(k), j.max(k))).or_insert(0) += 1;
+            }
+        }
+        matrix
+    }
+    // Computes all of the fields of GraphSummary, with a single pass over the nodes
+    // for the degree statistics.
+    fn summary(&self) -> GraphSummary
+    where
+        Self: ConnectedComponentsUndirected + Transitivity + Coreness,
+        Self::NodeType: NodeBase<NodeSetType = FxHashSet<NodeId>>,
+    {
+        let num_nodes = self.count_nodes();
+        let mut min_degree: usize = if num_nodes == 0 { 0 } else { usize::MAX };
+        let mut max_degree: usize = 0;
+        let mut degree_sum: usize = 0;
+        for node in self.get_nodes_iter() {
+            let degree = node.degree();
+            min_degree = min_degree.min(degree);
+            max_degree = max_degree.max(degree);
+            degree_sum += degree;
+        }
+        let num_edges = degree_sum / 2;
+        let components = self.get_connected_components();
+        let global_clustering = if self.count_connected_triples() == 0 {
+            0.0
+        } else {
+            self.get_transitivity()
+        };
+        let possible_edges = self.count_possible_edges();
+        GraphSummary {
+            num_nodes,
+            num_edges,
+            density: if possible_edges == 0 {
+                0.0
+            } else {
+                num_edges as f64 / possible_edges as f64
+            },
+            min_degree,
+            mean_degree: if num_nodes == 0 {
+                0.0
+            } else {
+                degree_sum as f64 / num_nodes as f64
+            },
+            max_degree,
+            num_connected_components: components.len(),
+            largest_component_size: components.iter().map(|c| c.len()).max().unwrap_or(0),
+            global_clustering,
+            degeneracy: self
+                .get_coreness_values()
+                .values()
+                .max()
+                .cloned()
+                .unwrap_or(0),
+        }
+    }
+}
+
+pub trait WeightedStatistics: Statistics + GraphBase<NodeType = WeightedNode> {
+    // Weight (strength) of a node: the sum of the weights of its edges.
+    fn node_weight(&self, id: NodeId) -> f64 {
+        self.get_node(id).weight()
+    }
+    fn total_node_weight(&self) -> f64 {
+        self.get_nodes_iter().map(|node| node.weight()).sum()
+    }
+    // The k heaviest nodes, in descending order of weight (ties broken by smallest id).
+    fn heaviest_nodes(&self, k: usize) -> Vec<(NodeId, f64)> {
+        let weights: HashMap<NodeId, f64> = self
+            .get_nodes_iter()
+            .map(|node| (node.node_id, node.weight()))
+            .collect();
+        top_k_by_value(&weights, k)
+    }
+    // Each undirected edge is stored on both of its endpoints, so node weights
+    // (strengths) add up to twice the total edge weight.
+    fn get_total_edge_weight(&self) -> f64 {
+        self.total_node_weight() / 2.0
+    }
+    // Total edge weight over the number of possible edges.
+    fn weighted_density(&self) -> f64 {
+        let possible_edges = self.count_possible_edges();
+        if possible_edges == 0 {
+            return 0.0;
+        }
+        self.get_total_edge_weight() / possible_edges as f64
+    }
+    // Average node strength (sum of incident edge weights).
+    fn average_weighted_degree(&self) -> f64 {
+        let n = self.count_nodes();
+        if n == 0 {
+            return 0.0;
+        }
+        2.0 * self.get_total_edge_weight() / n as f64
+    }
+    // Total weight of the edges between the center and its neighbors, and among its
+    // neighbors, divided by the number of nodes in this ego network (the center and
+    // its neighbors). 0.0 for an isolated node.
+    fn weighted_ego_density(&self, center: NodeId) -> f64 {
+        let node = self.get_node(center);
+        let mut internal_weight: f64 = node.weight();
+        for neighbor_id in &node.neighbors {
+            for edge in &self.get_node(*neighbor_id).edges {
+                // edges among neighbors are seen from both of their ends.
+                if edge.target_id != center && node.neighbors.contains(&edge.target_id) {
+                    internal_weight += edge.weight / 2.0;
+                }
+            }
+        }
+        internal_weight / (node.neighbors.len() + 1) as f64
+    }
+    // Total weight of the edges inside each connected component, in the same order
+    // as the components returned by get_connected_components.
+    fn component_weights(&self) -> Vec<f64>
+    where
+        Self: ConnectedComponents,
+    {
+        let (membership, num_components) = self._get_connected_components_membership(None, None);
+        let mut weights: Vec<f64> = vec![0.0; num_components];
+        for node in self.get_nodes_iter() {
+            for edge in &node.edges {
+                if node.node_id < edge.target_id {
+                    weights[membership[&node.node_id]] += edge.weight;
+                }
+            }
+        }
+        weights
+    }
+}
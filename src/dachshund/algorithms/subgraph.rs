@@ -0,0 +1,38 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::CLQResult;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use std::collections::{BTreeMap, HashMap};
+
+/// Implemented by graphs that can produce a copy of themselves restricted to
+/// a subset of their nodes.
+pub trait InducedSubgraph: GraphBase {
+    // Returns the subgraph consisting of node_ids and all edges between them.
+    // Fails if any of the requested nodes is not in the graph.
+    fn get_induced_subgraph(&self, node_ids: &[NodeId]) -> CLQResult<Self>;
+
+    // Returns the induced subgraph of each community, keyed by community label.
+    // Nodes that are missing from communities are dropped, along with their edges.
+    // Fails if communities refers to a node that is not in the graph.
+    fn split_by_community(
+        &self,
+        communities: &HashMap<NodeId, usize>,
+    ) -> CLQResult<HashMap<usize, Self>> {
+        let mut members: BTreeMap<usize, Vec<NodeId>> = BTreeMap::new();
+        for (id, community) in communities {
+            members.entry(*community).or_default().push(*id);
+        }
+        members
+            .into_iter()
+            .map(|(community, mut node_ids)| {
+                node_ids.sort();
+                Ok((community, self.get_induced_subgraph(&node_ids)?))
+            })
+            .collect()
+    }
+}
@@ -6,10 +6,11 @@
  */
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode};
 use fxhash::FxHashSet;
 use rand::distributions::WeightedIndex;
 use rand::prelude::*;
+use std::collections::HashMap;
 
 pub trait Transitivity: GraphBase
 where
@@ -36,7 +37,114 @@ where
     // Triples : pairs of neighbors of a given node.
     fn triples_count(&self, node_id: NodeId) -> usize {
         let num_neighbors = &self.get_node(node_id).degree();
-        num_neighbors * (num_neighbors - 1) / 2
+        num_neighbors * num_neighbors.saturating_sub(1) / 2
+    }
+
+    // Connected triples (paths of length 2, open or closed) in the whole graph,
+    // i.e. the sum of triples over all nodes.
+    fn count_connected_triples(&self) -> usize {
+        self.get_ids_iter().map(|x| self.triples_count(*x)).sum()
+    }
+
+    // Triangles of each node over its triples, i.e. the fraction of pairs of its
+    // neighbors that are themselves connected. Same as the local clustering
+    // coefficient, except that nodes with fewer than two neighbors score 0.0
+    // (instead of being left out), so that every node gets a score.
+    fn triangle_participation_ratio(&self) -> HashMap<NodeId, f64> {
+        self.get_ids_iter()
+            .map(|id| {
+                let num_triples = self.triples_count(*id);
+                let ratio = if num_triples == 0 {
+                    0.0
+                } else {
+                    self.triangle_count(*id) as f64 / num_triples as f64
+                };
+                (*id, ratio)
+            })
+            .collect()
+    }
+
+    // Nodes with a triangle participation ratio of at least min_ratio, in
+    // increasing order of id.
+    fn nodes_in_dense_regions(&self, min_ratio: f64) -> Vec<NodeId> {
+        let mut node_ids: Vec<NodeId> = self
+            .triangle_participation_ratio()
+            .into_iter()
+            .filter(|(_, ratio)| *ratio >= min_ratio)
+            .map(|(id, _)| id)
+            .collect();
+        node_ids.sort();
+        node_ids
+    }
+
+    // Number of triangles in the whole graph.
+    fn count_triangles(&self) -> usize {
+        Iterator::sum::<usize>(self.get_ids_iter().map(|x| self.triangle_count(*x))) / 3
+    }
+
+    // Orients edges from lower to higher (degree, id) rank, and returns the
+    // out-neighbors of each node under that orientation.
+    fn _get_forward_neighbors(&self) -> HashMap<NodeId, FxHashSet<NodeId>> {
+        let rank = |id: NodeId| (self.get_node(id).degree(), id);
+        self.get_nodes_iter()
+            .map(|node| {
+                let id = node.get_id();
+                (
+                    id,
+                    node.get_edges()
+                        .map(|e| e.get_neighbor_id())
+                        .filter(|nid| rank(*nid) > rank(id))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    // Lists every triangle once, as a sorted triple of node ids. Uses the "forward"
+    // algorithm: edges are oriented from lower to higher (degree, id) rank, and each
+    // triangle is found from its lowest-ranked edge by intersecting out-neighborhoods.
+    fn enumerate_triangles(&self) -> Vec<(NodeId, NodeId, NodeId)> {
+        let out_neighbors = self._get_forward_neighbors();
+        let mut triangles: Vec<(NodeId, NodeId, NodeId)> = Vec::new();
+        for (u, u_out) in &out_neighbors {
+            for v in u_out {
+                for w in u_out.intersection(&out_neighbors[v]) {
+                    let mut triangle = [*u, *v, *w];
+                    triangle.sort();
+                    triangles.push((triangle[0], triangle[1], triangle[2]));
+                }
+            }
+        }
+        triangles.sort();
+        triangles
+    }
+
+    // Lists every 4-clique once, as a sorted array of node ids. Extends the forward
+    // algorithm: each triangle u -> v -> w (in rank order) is found once, and each of
+    // the common out-neighbors of u, v and w completes it into a 4-clique.
+    fn enumerate_4_cliques(&self) -> Vec<[NodeId; 4]> {
+        let out_neighbors = self._get_forward_neighbors();
+        let mut cliques: Vec<[NodeId; 4]> = Vec::new();
+        for (u, u_out) in &out_neighbors {
+            for v in u_out {
+                let uv_out: FxHashSet<NodeId> =
+                    u_out.intersection(&out_neighbors[v]).cloned().collect();
+                for w in &uv_out {
+                    for x in uv_out.intersection(&out_neighbors[w]) {
+                        let mut clique = [*u, *v, *w, *x];
+                        clique.sort();
+                        cliques.push(clique);
+                    }
+                }
+            }
+        }
+        cliques.sort();
+        cliques
+    }
+
+    // Number of 4-cliques in the whole graph.
+    fn count_4_cliques(&self) -> usize {
+        self.enumerate_4_cliques().len()
     }
 
     // Transitivity: 3 * number of triangles  / number of triples
@@ -44,10 +152,7 @@ where
         let num_triangles =
             Iterator::sum::<usize>(self.get_ids_iter().map(|x| self.triangle_count(*x)));
 
-        let num_triples =
-            Iterator::sum::<usize>(self.get_ids_iter().map(|x| self.triples_count(*x)));
-
-        num_triangles as f64 / num_triples as f64
+        num_triangles as f64 / self.count_connected_triples() as f64
     }
 
     // Approximate Transitivity
@@ -90,3 +195,36 @@ where
         (successes as f64) / (samples as f64)
     }
 }
+
+pub trait WeightedTransitivity: GraphBase<NodeType = WeightedNode> {
+    // Weighted transitivity (Opsahl & Panzarasa, 2009): each connected triple
+    // centered on a node is valued at the geometric mean of the weights of its two
+    // edges, and transitivity is the total value of the closed triples over that of
+    // all triples. Equal to get_transitivity when all weights are 1.0. 0.0 for
+    // triangle-free graphs. Self-loops are ignored.
+    fn weighted_transitivity(&self) -> f64 {
+        let mut closed_value: f64 = 0.0;
+        let mut total_value: f64 = 0.0;
+        for node in self.get_nodes_iter() {
+            let edges: Vec<(NodeId, f64)> = node
+                .edges
+                .iter()
+                .filter(|e| e.target_id != node.node_id)
+                .map(|e| (e.target_id, e.weight))
+                .collect();
+            for (i, (a, weight_a)) in edges.iter().enumerate() {
+                for (b, weight_b) in &edges[i + 1..] {
+                    let value = (weight_a * weight_b).sqrt();
+                    total_value += value;
+                    if self.get_node(*a).neighbors.contains(b) {
+                        closed_value += value;
+                    }
+                }
+            }
+        }
+        if closed_value == 0.0 {
+            return 0.0;
+        }
+        closed_value / total_value
+    }
+}
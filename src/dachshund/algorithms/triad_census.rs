@@ -0,0 +1,134 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{DirectedNodeBase, NodeBase, NodeEdgeBase};
+use crate::dachshund::simple_directed_graph::DirectedGraph;
+use std::collections::{BTreeSet, HashMap};
+
+/// The 16 isomorphism classes of directed triads, in MAN notation: the number of
+/// mutual, asymmetric and null dyads, followed by a letter distinguishing
+/// classes with the same counts (Down, Up, Cyclic, Transitive).
+pub const TRIAD_TYPES: [&str; 16] = [
+    "003", "012", "102", "021D", "021U", "021C", "111D", "111U", "030T", "030C", "201", "120D",
+    "120U", "120C", "210", "300",
+];
+
+// Maps the 6-bit code of the arcs present among (v, u, w), as built by
+// DirectedGraph::_triad_code, to an index into TRIAD_TYPES.
+const TRIAD_CODES: [usize; 64] = [
+    0, 1, 1, 2, 1, 3, 5, 7, 1, 5, 4, 6, 2, 7, 6, 10, 1, 5, 3, 7, 4, 8, 8, 12, 5, 9, 8, 13, 6, 13,
+    11, 14, 1, 4, 5, 6, 5, 8, 9, 13, 3, 8, 8, 11, 7, 12, 13, 14, 2, 6, 7, 10, 6, 11, 13, 14, 7, 13,
+    12, 14, 10, 14, 14, 15,
+];
+
+/// Number of triads (sets of 3 distinct nodes) of each type in a directed graph,
+/// as computed by `TriadCensus::directed_triangle_census`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirectedTriangleCounts {
+    // counts[i] is the number of triads of type TRIAD_TYPES[i].
+    pub counts: [usize; 16],
+}
+impl DirectedTriangleCounts {
+    /// Number of triads of the given MAN type (e.g. "030T"), None if there is no such type.
+    pub fn get(&self, triad_type: &str) -> Option<usize> {
+        TRIAD_TYPES
+            .iter()
+            .position(|x| *x == triad_type)
+            .map(|i| self.counts[i])
+    }
+    /// Triads with edges a->b, a->c and b->c, and no others.
+    pub fn transitive(&self) -> usize {
+        self.counts[8]
+    }
+    /// Triads with edges a->b, b->c and c->a, and no others.
+    pub fn cyclic(&self) -> usize {
+        self.counts[9]
+    }
+}
+
+pub trait TriadCensus: DirectedGraph
+where
+    Self::NodeType: DirectedNodeBase,
+    <Self::NodeType as NodeBase>::NodeEdgeType: NodeEdgeBase<NodeIdType = NodeId>,
+{
+    // Distinct in- and out-neighbors of a node, other than itself.
+    fn _get_undirected_neighbors(&self, id: NodeId) -> BTreeSet<NodeId> {
+        let node = self.get_node(id);
+        node.get_in_neighbors()
+            .chain(node.get_out_neighbors())
+            .map(|e| e.get_neighbor_id())
+            .filter(|x| *x != id)
+            .collect()
+    }
+
+    fn _triad_code(&self, v: NodeId, u: NodeId, w: NodeId) -> usize {
+        [
+            (v, u, 1),
+            (u, v, 2),
+            (v, w, 4),
+            (w, v, 8),
+            (u, w, 16),
+            (w, u, 32),
+        ]
+        .iter()
+        .filter(|(a, b, _)| self.get_node(*a).has_out_neighbor(*b))
+        .map(|(_, _, bit)| bit)
+        .sum()
+    }
+
+    // Counts the triads of each of the 16 types, following Batagelj and Mrvar, "A
+    // subquadratic triad census algorithm for large sparse networks with small
+    // maximum degree": each connected triad is visited once, from the pair of its
+    // nodes which comes first, and triads with a single dyad are counted in bulk.
+    fn directed_triangle_census(&self) -> DirectedTriangleCounts {
+        let node_ids = self.get_ordered_node_ids();
+        let n = node_ids.len();
+        let positions: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let neighbors: HashMap<NodeId, BTreeSet<NodeId>> = node_ids
+            .iter()
+            .map(|id| (*id, self._get_undirected_neighbors(*id)))
+            .collect();
+        let mut counts = [0; 16];
+        for v in &node_ids {
+            for u in &neighbors[v] {
+                if positions[u] <= positions[v] {
+                    continue;
+                }
+                let others: BTreeSet<NodeId> = neighbors[v]
+                    .union(&neighbors[u])
+                    .filter(|x| *x != v && *x != u)
+                    .cloned()
+                    .collect();
+                // triads made of this dyad and a node connected to neither end.
+                let dyad_type = if self.get_node(*u).has_out_neighbor(*v)
+                    && self.get_node(*v).has_out_neighbor(*u)
+                {
+                    2
+                } else {
+                    1
+                };
+                counts[dyad_type] += n - others.len() - 2;
+                for w in &others {
+                    if positions[u] < positions[w]
+                        || (positions[v] < positions[w]
+                            && positions[w] < positions[u]
+                            && !neighbors[v].contains(w))
+                    {
+                        counts[TRIAD_CODES[self._triad_code(*v, *u, *w)]] += 1;
+                    }
+                }
+            }
+        }
+        let num_triads = if n < 3 { 0 } else { n * (n - 1) * (n - 2) / 6 };
+        counts[0] = num_triads - counts[1..].iter().sum::<usize>();
+        DirectedTriangleCounts { counts }
+    }
+}
@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use crate::dachshund::weighted_undirected_graph_builder::{
+    TWeightedUndirectedGraphBuilder, WeightedUndirectedGraphBuilder,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// Distance between feature vectors, as used by `knn_graph`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Euclidean,
+    // 1 - cosine similarity, in [0, 2]. Undefined for zero vectors.
+    Cosine,
+}
+impl Metric {
+    fn distance(&self, x: &[f64], y: &[f64]) -> f64 {
+        match self {
+            Metric::Euclidean => x
+                .iter()
+                .zip(y)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<f64>()
+                .sqrt(),
+            Metric::Cosine => {
+                let dot: f64 = x.iter().zip(y).map(|(a, b)| a * b).sum();
+                let norm = |v: &[f64]| v.iter().map(|a| a * a).sum::<f64>().sqrt();
+                1.0 - dot / (norm(x) * norm(y))
+            }
+        }
+    }
+}
+
+// k-nearest-neighbor graph of a set of feature vectors: each node is connected to
+// the k other nodes closest to it under metric (ties broken by smallest id), and
+// edges are weighted by the similarity 1 / (1 + distance), in (0, 1]. Being
+// undirected, an edge is kept if either end picked the other, so nodes can end up
+// with more than k neighbors. Every node of features is in the graph, even with
+// fewer than k others to connect to. Fails if k is 0, if vectors have different
+// lengths or non-finite values, or on zero vectors with the cosine metric.
+pub fn knn_graph(
+    features: &HashMap<NodeId, Vec<f64>>,
+    k: usize,
+    metric: Metric,
+) -> CLQResult<WeightedUndirectedGraph> {
+    if k == 0 {
+        return Err(CLQError::InvalidParameter(
+            "number of neighbors must be at least 1.".to_owned(),
+        ));
+    }
+    let mut node_ids: Vec<NodeId> = features.keys().cloned().collect();
+    node_ids.sort();
+    let dimension = node_ids.first().map_or(0, |id| features[id].len());
+    for id in &node_ids {
+        let vector = &features[id];
+        if vector.len() != dimension {
+            return Err(CLQError::InvalidParameter(format!(
+                "node {} has {} features, expected {}.",
+                id.value(),
+                vector.len(),
+                dimension
+            )));
+        }
+        if vector.iter().any(|x| !x.is_finite()) {
+            return Err(CLQError::InvalidParameter(format!(
+                "node {} has non-finite features.",
+                id.value()
+            )));
+        }
+        if metric == Metric::Cosine && vector.iter().all(|x| *x == 0.0) {
+            return Err(CLQError::InvalidParameter(format!(
+                "node {} has a zero feature vector, for which cosine distance is undefined.",
+                id.value()
+            )));
+        }
+    }
+
+    let mut ids: BTreeMap<NodeId, BTreeMap<NodeId, f64>> =
+        node_ids.iter().map(|id| (*id, BTreeMap::new())).collect();
+    for id in &node_ids {
+        let mut distances: Vec<(f64, NodeId)> = node_ids
+            .iter()
+            .filter(|other| *other != id)
+            .map(|other| (metric.distance(&features[id], &features[other]), *other))
+            .collect();
+        distances.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap().then(x.1.cmp(&y.1)));
+        for (distance, other) in distances.into_iter().take(k) {
+            let similarity = 1.0 / (1.0 + distance.max(0.0));
+            ids.get_mut(id).unwrap().insert(other, similarity);
+            ids.get_mut(&other).unwrap().insert(*id, similarity);
+        }
+    }
+    let nodes = WeightedUndirectedGraphBuilder::get_nodes(ids);
+    Ok(WeightedUndirectedGraph {
+        ids: nodes.keys().cloned().collect(),
+        nodes,
+    })
+}
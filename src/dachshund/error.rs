@@ -6,6 +6,7 @@
  */
 // https://blog.burntsushi.net/rust-error-handling/
 
+use crate::dachshund::id_types::NodeId;
 use thiserror::Error;
 
 pub type CLQResult<T> = std::result::Result<T, CLQError>;
@@ -15,6 +16,21 @@ pub enum CLQError {
     #[error("{0}")]
     Generic(String),
 
+    #[error("Node {} is not in the graph.", .0.value())]
+    NodeNotFound(NodeId),
+
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+
+    #[error("Graph must be connected.")]
+    NotConnected,
+
+    #[error("Weights must be non-negative, got {0}.")]
+    NegativeWeight(f64),
+
+    #[error("Graph must be bipartite.")]
+    NotBipartite,
+
     #[error("I/O Error: {0}")]
     IO(#[from] std::io::Error),
 
@@ -8,7 +8,10 @@ use crate::dachshund::node::NodeBase;
 
 extern crate fxhash;
 use fxhash::FxHashMap;
+use rayon::prelude::*;
 use std::collections::hash_map::{Keys, Values};
+use std::collections::HashMap;
+use std::hash::Hash;
 
 /// General-purpose trait which indicates the minimum amount of shared context
 /// required between all graph objects. Currently built to accommodate a graph
@@ -39,4 +42,38 @@ where
         node_ids.sort();
         node_ids
     }
+
+    /// Node ids sorted by degree, in ascending or descending order. Ties are always
+    /// broken by smallest id, so the order doesn't depend on the node map.
+    fn nodes_by_degree(&self, ascending: bool) -> Vec<<Self::NodeType as NodeBase>::NodeIdType> {
+        let mut nodes: Vec<(usize, <Self::NodeType as NodeBase>::NodeIdType)> = self
+            .get_nodes_iter()
+            .map(|node| (node.degree(), node.get_id()))
+            .collect();
+        if ascending {
+            nodes.sort();
+        } else {
+            nodes.sort_by(|(degree_a, id_a), (degree_b, id_b)| {
+                degree_b.cmp(degree_a).then_with(|| id_a.cmp(id_b))
+            });
+        }
+        nodes.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Computes f(graph, node_id) for every node in parallel (on rayon's global
+    /// thread pool), for per-node metrics that only need read access to the graph.
+    fn par_map_nodes<F, R>(&self, f: F) -> HashMap<<Self::NodeType as NodeBase>::NodeIdType, R>
+    where
+        Self: Sync,
+        <Self::NodeType as NodeBase>::NodeIdType: Hash + Send + Sync,
+        F: Fn(&Self, <Self::NodeType as NodeBase>::NodeIdType) -> R + Sync,
+        R: Send,
+    {
+        let node_ids: Vec<<Self::NodeType as NodeBase>::NodeIdType> =
+            self.get_ids_iter().cloned().collect();
+        node_ids
+            .into_par_iter()
+            .map(|id| (id.clone(), f(self, id)))
+            .collect()
+    }
 }
@@ -0,0 +1,120 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use std::io::{BufRead, BufReader, Read};
+
+/// Nonzero entries of a square Matrix Market coordinate matrix, read as a graph.
+pub struct MatrixMarketEntries {
+    // number of rows (= columns) of the matrix, i.e. number of nodes.
+    pub num_nodes: i64,
+    // (row, column, value) triples, 1-indexed. Pattern matrices get a value of 1.0.
+    pub entries: Vec<(i64, i64, f64)>,
+}
+
+/// Parses the `%%MatrixMarket matrix coordinate ...` banner, the size line and the
+/// coordinate entries. Diagonal entries (self-loops) are dropped. If `symmetric` is
+/// set, the file is expected to only store the lower triangle (row >= column), which
+/// is then mirrored by the undirected graph builders.
+pub fn read_matrix_market<R: Read>(reader: R, symmetric: bool) -> CLQResult<MatrixMarketEntries> {
+    let mut lines = BufReader::new(reader).lines().enumerate();
+    let banner = match lines.next() {
+        Some((_, line)) => line?,
+        None => return Err(CLQError::from("Empty Matrix Market input.")),
+    };
+    let banner_tokens: Vec<String> = banner
+        .split_whitespace()
+        .map(|x| x.to_lowercase())
+        .collect();
+    if banner_tokens.len() < 4
+        || banner_tokens[0] != "%%matrixmarket"
+        || banner_tokens[1] != "matrix"
+        || banner_tokens[2] != "coordinate"
+    {
+        return Err(CLQError::from(format!(
+            "Line 1: expected a \"%%MatrixMarket matrix coordinate\" banner, got: {}",
+            banner
+        )));
+    }
+    let is_pattern = banner_tokens[3] == "pattern";
+
+    let mut size: Option<(i64, usize)> = None;
+    let mut entries: Vec<(i64, i64, f64)> = Vec::new();
+    let mut num_entries: usize = 0;
+    for (i, line) in lines {
+        let line = line?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.is_empty() || tokens[0].starts_with('%') {
+            continue;
+        }
+        match size {
+            None => {
+                if tokens.len() != 3 {
+                    return Err(CLQError::from(format!(
+                        "Line {}: malformed size line: {}",
+                        i + 1,
+                        line
+                    )));
+                }
+                let num_rows: i64 = tokens[0].parse()?;
+                let num_cols: i64 = tokens[1].parse()?;
+                if num_rows != num_cols {
+                    return Err(CLQError::from(format!(
+                        "Line {}: adjacency matrix must be square, got {}x{}.",
+                        i + 1,
+                        num_rows,
+                        num_cols
+                    )));
+                }
+                size = Some((num_rows, tokens[2].parse()?));
+            }
+            Some((n, _)) => {
+                let expected_len = if is_pattern { 2 } else { 3 };
+                if tokens.len() < expected_len {
+                    return Err(CLQError::from(format!(
+                        "Line {}: malformed entry: {}",
+                        i + 1,
+                        line
+                    )));
+                }
+                let row: i64 = tokens[0].parse()?;
+                let col: i64 = tokens[1].parse()?;
+                if row < 1 || row > n || col < 1 || col > n {
+                    return Err(CLQError::from(format!(
+                        "Line {}: entry out of range 1..={}: {}",
+                        i + 1,
+                        n,
+                        line
+                    )));
+                }
+                if symmetric && row < col {
+                    return Err(CLQError::from(format!(
+                        "Line {}: symmetric matrix entry above the diagonal: {}",
+                        i + 1,
+                        line
+                    )));
+                }
+                let value: f64 = if is_pattern { 1.0 } else { tokens[2].parse()? };
+                num_entries += 1;
+                if row != col {
+                    entries.push((row, col, value));
+                }
+            }
+        }
+    }
+    match size {
+        Some((num_nodes, num_nonzeros)) => {
+            if num_entries != num_nonzeros {
+                return Err(CLQError::from(format!(
+                    "Expected {} Matrix Market entries, found {}.",
+                    num_nonzeros, num_entries
+                )));
+            }
+            Ok(MatrixMarketEntries { num_nodes, entries })
+        }
+        None => Err(CLQError::from("Matrix Market input has no size line.")),
+    }
+}
@@ -8,6 +8,7 @@ pub mod algorithms;
 pub mod beam;
 pub mod candidate;
 pub mod connected_components_transformer;
+pub mod construction;
 pub mod core_transformer;
 pub mod error;
 pub mod graph_base;
@@ -16,6 +17,7 @@ pub mod id_types;
 pub mod input;
 pub mod kpeak_transformer;
 pub mod line_processor;
+pub mod matrix_market;
 pub mod node;
 pub mod non_core_type_ids;
 pub mod output;
@@ -28,6 +30,7 @@ pub mod simple_transformer;
 pub mod simple_undirected_graph;
 pub mod simple_undirected_graph_builder;
 pub mod strongly_connected_components_transformer;
+pub mod temporal_graph;
 pub mod test_utils;
 pub mod transformer;
 pub mod transformer_base;
@@ -35,5 +38,7 @@ pub mod typed_graph;
 pub mod typed_graph_builder;
 pub mod typed_graph_line_processor;
 pub mod weighted_core_transformer;
+pub mod weighted_directed_graph;
+pub mod weighted_directed_graph_builder;
 pub mod weighted_undirected_graph;
 pub mod weighted_undirected_graph_builder;
@@ -354,3 +354,78 @@ impl NodeBase for WeightedNode {
         ids.iter().filter(|x| self.neighbors.contains(x)).count()
     }
 }
+
+/// A node in a weighted directed graph, keeping track of its incoming and outgoing
+/// edges separately. The weight of an incoming edge is that of the edge pointing to
+/// this node from its target.
+pub struct WeightedDirectedNode {
+    pub node_id: NodeId,
+    pub in_edges: Vec<WeightedNodeEdge>,
+    pub out_edges: Vec<WeightedNodeEdge>,
+}
+impl WeightedDirectedNode {
+    // total weight of the outgoing edges, by which a random walk splits its steps.
+    pub fn out_weight(&self) -> f64 {
+        self.out_edges.iter().map(|x| x.get_weight()).sum()
+    }
+}
+impl WeightedNodeBase for WeightedDirectedNode {
+    fn weight(&self) -> f64 {
+        self.get_edges().map(|x| x.get_weight()).sum()
+    }
+}
+impl DirectedNodeBase for WeightedDirectedNode {
+    fn get_in_neighbors(&self) -> Box<dyn Iterator<Item = &Self::NodeEdgeType> + '_> {
+        Box::new(self.in_edges.iter())
+    }
+    fn get_out_neighbors(&self) -> Box<dyn Iterator<Item = &Self::NodeEdgeType> + '_> {
+        Box::new(self.out_edges.iter())
+    }
+    fn has_in_neighbor(&self, nid: NodeId) -> bool {
+        self.in_edges.iter().any(|e| e.target_id == nid)
+    }
+    fn has_out_neighbor(&self, nid: NodeId) -> bool {
+        self.out_edges.iter().any(|e| e.target_id == nid)
+    }
+    fn get_in_degree(&self) -> usize {
+        self.in_edges.len()
+    }
+    fn get_out_degree(&self) -> usize {
+        self.out_edges.len()
+    }
+}
+impl Hash for WeightedDirectedNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node_id.hash(state);
+    }
+}
+impl PartialEq for WeightedDirectedNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_id == other.node_id
+    }
+}
+impl Eq for WeightedDirectedNode {}
+impl NodeBase for WeightedDirectedNode {
+    type NodeIdType = NodeId;
+    type NodeEdgeType = WeightedNodeEdge;
+    type NodeSetType = FxHashSet<NodeId>;
+
+    fn get_id(&self) -> NodeId {
+        self.node_id
+    }
+    fn get_edges(&self) -> Box<dyn Iterator<Item = &WeightedNodeEdge> + '_> {
+        Box::new(self.in_edges.iter().chain(self.out_edges.iter()))
+    }
+    fn get_outgoing_edges(&self) -> Box<dyn Iterator<Item = &WeightedNodeEdge> + '_> {
+        self.get_edges()
+    }
+    /// degree is the edge count (in an unweighted graph)
+    fn degree(&self) -> usize {
+        self.in_edges.len() + self.out_edges.len()
+    }
+    fn count_ties_with_ids(&self, ids: &FxHashSet<NodeId>) -> usize {
+        ids.iter()
+            .filter(|x| self.has_in_neighbor(**x) || self.has_out_neighbor(**x))
+            .count()
+    }
+}
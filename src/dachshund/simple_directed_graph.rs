@@ -10,12 +10,15 @@ use crate::dachshund::algorithms::connected_components::{
     ConnectedComponents, ConnectedComponentsDirected,
 };
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityDirected};
+use crate::dachshund::algorithms::subgraph::InducedSubgraph;
+use crate::dachshund::algorithms::triad_census::TriadCensus;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{DirectedNodeBase, NodeBase, NodeEdgeBase, SimpleDirectedNode};
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use std::collections::hash_map::{Keys, Values};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 pub trait DirectedGraph: GraphBase
 where
@@ -41,6 +44,58 @@ where
         }
         true
     }
+    fn _normalized_degrees(&self, degree: fn(&Self::NodeType) -> usize) -> HashMap<NodeId, f64> {
+        let n = self.count_nodes();
+        self.get_nodes_iter()
+            .map(|node| {
+                let centrality = if n <= 1 {
+                    0.0
+                } else {
+                    degree(node) as f64 / (n - 1) as f64
+                };
+                (node.get_id(), centrality)
+            })
+            .collect()
+    }
+    // Nodes reachable from source through directed paths of at least one edge
+    // (so source itself only if it's on a cycle), found by BFS. Stops as soon as
+    // target is reached, if given.
+    fn _get_reachable_nodes(&self, source: NodeId, target: Option<NodeId>) -> HashSet<NodeId> {
+        let mut reached: HashSet<NodeId> = HashSet::new();
+        let mut queue: VecDeque<NodeId> = VecDeque::from([source]);
+        while let Some(id) = queue.pop_front() {
+            for e in self.get_node(id).get_out_neighbors() {
+                let neighbor_id = e.get_neighbor_id();
+                if reached.insert(neighbor_id) {
+                    if Some(neighbor_id) == target {
+                        return reached;
+                    }
+                    queue.push_back(neighbor_id);
+                }
+            }
+        }
+        reached
+    }
+    // For each node, the set of nodes it can reach through a directed path (of at
+    // least one edge).
+    fn transitive_closure(&self) -> HashMap<NodeId, HashSet<NodeId>> {
+        self.get_ids_iter()
+            .map(|id| (*id, self._get_reachable_nodes(*id, None)))
+            .collect()
+    }
+    // Whether there is a directed path (of at least one edge) from a to b. False if
+    // either node is not in the graph.
+    fn reaches(&self, a: NodeId, b: NodeId) -> bool {
+        self.has_node(a) && self.has_node(b) && self._get_reachable_nodes(a, Some(b)).contains(&b)
+    }
+    // Fraction of the other nodes with an edge pointing to each node.
+    fn in_degree_centrality(&self) -> HashMap<NodeId, f64> {
+        self._normalized_degrees(|node| node.get_in_degree())
+    }
+    // Fraction of the other nodes each node points to.
+    fn out_degree_centrality(&self) -> HashMap<NodeId, f64> {
+        self._normalized_degrees(|node| node.get_out_degree())
+    }
 }
 pub struct SimpleDirectedGraph {
     pub nodes: FxHashMap<NodeId, SimpleDirectedNode>,
@@ -91,8 +146,40 @@ impl GraphBase for SimpleDirectedGraph {
     }
 }
 impl DirectedGraph for SimpleDirectedGraph {}
+impl InducedSubgraph for SimpleDirectedGraph {
+    fn get_induced_subgraph(&self, node_ids: &[NodeId]) -> CLQResult<Self> {
+        let keep: FxHashSet<NodeId> = node_ids.iter().cloned().collect();
+        let mut nodes: FxHashMap<NodeId, SimpleDirectedNode> = FxHashMap::default();
+        for id in &keep {
+            let node = self.nodes.get(id).ok_or(CLQError::NodeNotFound(*id))?;
+            nodes.insert(
+                *id,
+                SimpleDirectedNode {
+                    node_id: *id,
+                    in_neighbors: node
+                        .in_neighbors
+                        .iter()
+                        .filter(|x| keep.contains(x))
+                        .cloned()
+                        .collect(),
+                    out_neighbors: node
+                        .out_neighbors
+                        .iter()
+                        .filter(|x| keep.contains(x))
+                        .cloned()
+                        .collect(),
+                },
+            );
+        }
+        Ok(SimpleDirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        })
+    }
+}
 impl Brokerage for SimpleDirectedGraph {}
 impl ConnectedComponents for SimpleDirectedGraph {}
 impl ConnectedComponentsDirected for SimpleDirectedGraph {}
 impl Connectivity for SimpleDirectedGraph {}
 impl ConnectivityDirected for SimpleDirectedGraph {}
+impl TriadCensus for SimpleDirectedGraph {}
@@ -8,23 +8,42 @@ extern crate fxhash;
 use crate::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
 use crate::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
 use crate::dachshund::algorithms::betweenness::Betweenness;
+use crate::dachshund::algorithms::cliques::Cliques;
+use crate::dachshund::algorithms::closeness::Closeness;
 use crate::dachshund::algorithms::clustering::Clustering;
 use crate::dachshund::algorithms::cnm_communities::CNMCommunities;
 use crate::dachshund::algorithms::connected_components::{
     ConnectedComponents, ConnectedComponentsUndirected,
 };
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
-use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::algorithms::coreness::{Coreness, IncrementalCoreness};
 use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
+use crate::dachshund::algorithms::graph_diff::Diff;
+use crate::dachshund::algorithms::independent_set::IndependentSet;
 use crate::dachshund::algorithms::k_peaks::KPeaks;
 use crate::dachshund::algorithms::laplacian::Laplacian;
+use crate::dachshund::algorithms::min_cut::MinCut;
+use crate::dachshund::algorithms::modularity::Modularity;
+use crate::dachshund::algorithms::motifs::Motifs;
+use crate::dachshund::algorithms::relabel::RelabelContiguous;
+use crate::dachshund::algorithms::rewiring::Rewiring;
+use crate::dachshund::algorithms::roles::StructuralRoles;
 use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::algorithms::similarity::Similarity;
+use crate::dachshund::algorithms::simrank::SimRank;
+use crate::dachshund::algorithms::spanning_tree::SpanningTree;
+use crate::dachshund::algorithms::statistics::Statistics;
+use crate::dachshund::algorithms::subgraph::InducedSubgraph;
 use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase, SimpleNode};
-use fxhash::FxHashMap;
-use std::collections::hash_map::{Keys, Values};
+use fxhash::{FxHashMap, FxHashSet};
+use std::cmp::Reverse;
+use std::collections::hash_map::{Entry, Keys, Values};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::OnceLock;
 
 pub trait UndirectedGraph
 where
@@ -32,10 +51,27 @@ where
 {
 }
 
+/// Size and degree figures of a `SimpleUndirectedGraph`, see `SimpleUndirectedGraph::metadata`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphMetadata {
+    pub num_nodes: usize,
+    pub num_edges: usize,
+    // max and min degree are 0 for the empty graph.
+    pub max_degree: usize,
+    pub min_degree: usize,
+    // sum of all degrees, i.e. twice the number of edges.
+    pub total_degree: usize,
+}
+
 /// Keeps track of a simple undirected graph, composed of nodes without any type information.
 pub struct SimpleUndirectedGraph {
     pub nodes: FxHashMap<NodeId, SimpleNode>,
     pub ids: Vec<NodeId>,
+    // optional key => value attributes of each node, e.g. categories.
+    pub attributes: HashMap<NodeId, HashMap<String, String>>,
+    // computed on the first call to metadata(), and cleared by the mutation methods
+    // (and get_mut_nodes). Code changing nodes directly must call clear_metadata.
+    metadata_cache: OnceLock<GraphMetadata>,
 }
 impl GraphBase for SimpleUndirectedGraph {
     type NodeType = SimpleNode;
@@ -55,6 +91,7 @@ impl GraphBase for SimpleUndirectedGraph {
         self.nodes.values()
     }
     fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, SimpleNode> {
+        self.clear_metadata();
         &mut self.nodes
     }
     fn has_node(&self, node_id: NodeId) -> bool {
@@ -77,10 +114,87 @@ impl GraphBase for SimpleUndirectedGraph {
         SimpleUndirectedGraph {
             nodes: FxHashMap::default(),
             ids: Vec::new(),
+            attributes: HashMap::new(),
+            metadata_cache: OnceLock::new(),
         }
     }
 }
 impl SimpleUndirectedGraph {
+    pub fn new(
+        ids: Vec<NodeId>,
+        nodes: FxHashMap<NodeId, SimpleNode>,
+        attributes: HashMap<NodeId, HashMap<String, String>>,
+    ) -> Self {
+        SimpleUndirectedGraph {
+            nodes,
+            ids,
+            attributes,
+            metadata_cache: OnceLock::new(),
+        }
+    }
+    // Node and edge counts and degree figures in O(1). They're computed on the first
+    // call rather than when the graph is built, so graphs that never ask don't pay
+    // for them, and served from a cache until add_edge, remove_edge, remove_node or
+    // get_mut_nodes clear it. Code changing the public nodes field directly must
+    // call clear_metadata, or this goes stale (count_edges and count_nodes always
+    // look at nodes, so they don't).
+    pub fn metadata(&self) -> &GraphMetadata {
+        self.metadata_cache.get_or_init(|| {
+            let degrees: Vec<usize> = self.nodes.values().map(|node| node.degree()).collect();
+            let total_degree: usize = degrees.iter().sum();
+            GraphMetadata {
+                num_nodes: self.nodes.len(),
+                num_edges: total_degree / 2,
+                max_degree: degrees.iter().copied().max().unwrap_or(0),
+                min_degree: degrees.iter().copied().min().unwrap_or(0),
+                total_degree,
+            }
+        })
+    }
+    pub fn clear_metadata(&mut self) {
+        self.metadata_cache.take();
+    }
+    // Adds the edge (a, b), and either node if it isn't in the graph yet.
+    // Returns false if the edge was already there.
+    pub fn add_edge(&mut self, a: NodeId, b: NodeId) -> bool {
+        self.clear_metadata();
+        for id in [a, b] {
+            if let Entry::Vacant(entry) = self.nodes.entry(id) {
+                entry.insert(SimpleNode {
+                    node_id: id,
+                    neighbors: BTreeSet::new(),
+                });
+                self.ids.push(id);
+            }
+        }
+        self.nodes.get_mut(&b).unwrap().neighbors.insert(a);
+        self.nodes.get_mut(&a).unwrap().neighbors.insert(b)
+    }
+    // Removes the edge (a, b), keeping both nodes. Returns false if there was no
+    // such edge.
+    pub fn remove_edge(&mut self, a: NodeId, b: NodeId) -> CLQResult<bool> {
+        for id in [a, b] {
+            if !self.nodes.contains_key(&id) {
+                return Err(CLQError::NodeNotFound(id));
+            }
+        }
+        self.clear_metadata();
+        self.nodes.get_mut(&b).unwrap().neighbors.remove(&a);
+        Ok(self.nodes.get_mut(&a).unwrap().neighbors.remove(&b))
+    }
+    // Removes a node, along with its edges and attributes.
+    pub fn remove_node(&mut self, id: NodeId) -> CLQResult<()> {
+        let node = self.nodes.remove(&id).ok_or(CLQError::NodeNotFound(id))?;
+        self.clear_metadata();
+        for neighbor_id in node.neighbors {
+            if let Some(neighbor) = self.nodes.get_mut(&neighbor_id) {
+                neighbor.neighbors.remove(&id);
+            }
+        }
+        self.ids.retain(|x| *x != id);
+        self.attributes.remove(&id);
+        Ok(())
+    }
     pub fn as_input_rows(&self, graph_id: usize) -> String {
         let mut rows: Vec<String> = Vec::new();
         for (id, node) in &self.nodes {
@@ -100,22 +214,297 @@ impl SimpleUndirectedGraph {
     pub fn get_node_degree(&self, id: NodeId) -> usize {
         self.nodes[&id].degree()
     }
+    // Sets the value of a node's attribute, replacing any previous value.
+    pub fn set_attribute(&mut self, id: NodeId, key: &str, value: &str) -> CLQResult<()> {
+        if !self.nodes.contains_key(&id) {
+            return Err(CLQError::NodeNotFound(id));
+        }
+        self.attributes
+            .entry(id)
+            .or_default()
+            .insert(key.to_owned(), value.to_owned());
+        Ok(())
+    }
+    pub fn get_attribute(&self, id: NodeId, key: &str) -> Option<&str> {
+        self.attributes
+            .get(&id)
+            .and_then(|values| values.get(key))
+            .map(|x| x.as_str())
+    }
+    // Numbers the distinct values of an attribute 0.. in sorted order, and maps
+    // each node that has the attribute to the number of its value, e.g. as input
+    // to Statistics::attribute_assortativity.
+    pub fn attribute_categories(&self, key: &str) -> HashMap<NodeId, usize> {
+        let values: BTreeSet<&str> = self
+            .attributes
+            .values()
+            .filter_map(|values| values.get(key))
+            .map(|x| x.as_str())
+            .collect();
+        let categories: HashMap<&str, usize> = values
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| (x, i))
+            .collect();
+        self.attributes
+            .iter()
+            .filter_map(|(id, values)| values.get(key).map(|x| (*id, categories[x.as_str()])))
+            .collect()
+    }
+    // Serializes the graph in the DIMACS edge format. Nodes are renumbered 1..=n
+    // in increasing order of their ids.
+    pub fn to_dimacs(&self) -> String {
+        let node_ids = self.get_ordered_node_ids();
+        let positions: FxHashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i + 1))
+            .collect();
+        let mut rows: Vec<String> = vec![format!(
+            "p edge {} {}",
+            self.count_nodes(),
+            self.count_edges()
+        )];
+        for id in &node_ids {
+            for neighbor_id in &self.nodes[id].neighbors {
+                if id < neighbor_id {
+                    rows.push(format!("e {} {}", positions[id], positions[neighbor_id]));
+                }
+            }
+        }
+        rows.join("\n")
+    }
+    // Repeatedly removes nodes with fewer than two neighbors (leaves, and nodes
+    // left isolated), in place, until only the 2-core is left. Returns the removed
+    // nodes in order of removal, starting with the initial leaves by increasing id.
+    pub fn prune_leaves(&mut self) -> Vec<NodeId> {
+        let mut queue: VecDeque<NodeId> = self
+            .get_ordered_node_ids()
+            .into_iter()
+            .filter(|id| self.nodes[id].degree() < 2)
+            .collect();
+        let mut removed: Vec<NodeId> = Vec::new();
+        let nodes = self.get_mut_nodes();
+        while let Some(id) = queue.pop_front() {
+            let node = match nodes.remove(&id) {
+                Some(node) => node,
+                None => continue,
+            };
+            removed.push(id);
+            for neighbor_id in node.neighbors {
+                if let Some(neighbor) = nodes.get_mut(&neighbor_id) {
+                    neighbor.neighbors.remove(&id);
+                    if neighbor.degree() == 1 {
+                        queue.push_back(neighbor_id);
+                    }
+                }
+            }
+        }
+        // ids and attributes are cleaned up once at the end, rather than for each
+        // removed node.
+        let removed_ids: FxHashSet<NodeId> = removed.iter().cloned().collect();
+        self.ids.retain(|id| !removed_ids.contains(id));
+        self.attributes.retain(|id, _| !removed_ids.contains(id));
+        removed
+    }
+    // Subgraph induced by the nodes with coreness at least k (the k-core), given
+    // the coreness values of the whole graph.
+    fn get_k_core_subgraph_from(
+        &self,
+        coreness: &HashMap<NodeId, usize>,
+        k: usize,
+    ) -> SimpleUndirectedGraph {
+        let mut node_ids: Vec<NodeId> = coreness
+            .iter()
+            .filter(|(_, value)| **value >= k)
+            .map(|(id, _)| *id)
+            .collect();
+        node_ids.sort();
+        self.get_induced_subgraph(&node_ids).unwrap()
+    }
+    // The k-core as a graph of its own, i.e. the largest subgraph in which every
+    // node has at least k neighbors. Empty if k is above the largest coreness.
+    pub fn k_core_subgraph(&self, k: usize) -> SimpleUndirectedGraph {
+        self.get_k_core_subgraph_from(&self.get_coreness_values(), k)
+    }
+    // The k-cores for k from the largest coreness down to 1, each one a subgraph
+    // of the next. The last one is the 1-core, which is the whole graph minus any
+    // isolated nodes.
+    pub fn coreness_filtration(&self) -> Vec<(usize, SimpleUndirectedGraph)> {
+        let coreness = self.get_coreness_values();
+        let max_coreness = coreness.values().copied().max().unwrap_or(0);
+        (1..=max_coreness)
+            .rev()
+            .map(|k| (k, self.get_k_core_subgraph_from(&coreness, k)))
+            .collect()
+    }
+    // Nodes in the order of ids (get_core_ids), e.g. the coreness order set by
+    // reorder_by_coreness. get_ids_iter and get_nodes_iter follow the node hash map
+    // instead, whatever the order of ids.
+    pub fn get_nodes_in_order(&self) -> impl Iterator<Item = &SimpleNode> {
+        self.ids.iter().map(move |id| &self.nodes[id])
+    }
+    // Copy of the graph with its ids sorted by decreasing coreness, ties broken by
+    // smallest id, so that get_core_ids and get_nodes_in_order visit the nodes of
+    // the densest cores first. Nodes are still stored in a hash map, so this sets a
+    // visiting order rather than a memory layout, and get_ids_iter and
+    // get_nodes_iter are unaffected. Nodes, edges and attributes are the same.
+    // Also returns the new order.
+    pub fn reorder_by_coreness(&self) -> (SimpleUndirectedGraph, Vec<NodeId>) {
+        let coreness = self.get_coreness_values();
+        let mut order: Vec<NodeId> = self.ids.clone();
+        order.sort_by_key(|id| (Reverse(coreness.get(id).copied().unwrap_or(0)), *id));
+        let nodes: FxHashMap<NodeId, SimpleNode> = order
+            .iter()
+            .map(|id| {
+                let node = SimpleNode {
+                    node_id: *id,
+                    neighbors: self.nodes[id].neighbors.clone(),
+                };
+                (*id, node)
+            })
+            .collect();
+        let graph = SimpleUndirectedGraph {
+            nodes,
+            ids: order.clone(),
+            attributes: self.attributes.clone(),
+            metadata_cache: self.metadata_cache.clone(),
+        };
+        (graph, order)
+    }
+    // k-th power of the graph: two nodes are adjacent iff they are at most k hops
+    // apart in this graph. Computed by a BFS of depth k from every node.
+    pub fn graph_power(&self, k: usize) -> CLQResult<SimpleUndirectedGraph> {
+        if k == 0 {
+            return Err(CLQError::InvalidParameter(
+                "graph power must be at least 1.".to_owned(),
+            ));
+        }
+        let mut nodes: FxHashMap<NodeId, SimpleNode> = FxHashMap::default();
+        for id in self.nodes.keys() {
+            let mut reached: BTreeSet<NodeId> = BTreeSet::new();
+            reached.insert(*id);
+            let mut frontier: Vec<NodeId> = vec![*id];
+            for _ in 0..k {
+                let mut next: Vec<NodeId> = Vec::new();
+                for x in frontier {
+                    for y in &self.nodes[&x].neighbors {
+                        if reached.insert(*y) {
+                            next.push(*y);
+                        }
+                    }
+                }
+                frontier = next;
+            }
+            reached.remove(id);
+            nodes.insert(
+                *id,
+                SimpleNode {
+                    node_id: *id,
+                    neighbors: reached,
+                },
+            );
+        }
+        Ok(SimpleUndirectedGraph {
+            ids: self.ids.clone(),
+            nodes,
+            attributes: self.attributes.clone(),
+            metadata_cache: OnceLock::new(),
+        })
+    }
 }
 impl UndirectedGraph for SimpleUndirectedGraph {}
+impl InducedSubgraph for SimpleUndirectedGraph {
+    fn get_induced_subgraph(&self, node_ids: &[NodeId]) -> CLQResult<Self> {
+        let keep: FxHashSet<NodeId> = node_ids.iter().cloned().collect();
+        let mut nodes: FxHashMap<NodeId, SimpleNode> = FxHashMap::default();
+        for id in &keep {
+            let node = self.nodes.get(id).ok_or(CLQError::NodeNotFound(*id))?;
+            nodes.insert(
+                *id,
+                SimpleNode {
+                    node_id: *id,
+                    neighbors: node
+                        .neighbors
+                        .iter()
+                        .filter(|x| keep.contains(x))
+                        .cloned()
+                        .collect(),
+                },
+            );
+        }
+        let attributes: HashMap<NodeId, HashMap<String, String>> = self
+            .attributes
+            .iter()
+            .filter(|(id, _)| keep.contains(id))
+            .map(|(id, values)| (*id, values.clone()))
+            .collect();
+        Ok(SimpleUndirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+            attributes,
+            metadata_cache: OnceLock::new(),
+        })
+    }
+}
+impl RelabelContiguous for SimpleUndirectedGraph {
+    fn relabel_contiguous(&self) -> (Self, HashMap<NodeId, NodeId>, Vec<NodeId>) {
+        let (old_to_new, new_to_old) = self._get_contiguous_ids();
+        let nodes: FxHashMap<NodeId, SimpleNode> = self
+            .nodes
+            .values()
+            .map(|node| {
+                let id = old_to_new[&node.node_id];
+                let relabeled = SimpleNode {
+                    node_id: id,
+                    neighbors: node.neighbors.iter().map(|x| old_to_new[x]).collect(),
+                };
+                (id, relabeled)
+            })
+            .collect();
+        let graph = SimpleUndirectedGraph {
+            ids: (0..new_to_old.len())
+                .map(|i| NodeId::from(i as i64))
+                .collect(),
+            nodes,
+            attributes: self
+                .attributes
+                .iter()
+                .map(|(id, values)| (old_to_new[id], values.clone()))
+                .collect(),
+            metadata_cache: OnceLock::new(),
+        };
+        (graph, old_to_new, new_to_old)
+    }
+}
 
 impl CNMCommunities for SimpleUndirectedGraph {}
 impl ConnectedComponents for SimpleUndirectedGraph {}
 impl ConnectedComponentsUndirected for SimpleUndirectedGraph {}
 impl Coreness for SimpleUndirectedGraph {}
+impl IncrementalCoreness for SimpleUndirectedGraph {}
 impl KPeaks for SimpleUndirectedGraph {}
+impl Rewiring for SimpleUndirectedGraph {}
 
 impl AdjacencyMatrix for SimpleUndirectedGraph {}
+impl Cliques for SimpleUndirectedGraph {}
+impl Closeness for SimpleUndirectedGraph {}
 impl Clustering for SimpleUndirectedGraph {}
 impl Connectivity for SimpleUndirectedGraph {}
 impl ConnectivityUndirected for SimpleUndirectedGraph {}
 impl Betweenness for SimpleUndirectedGraph {}
 impl Laplacian for SimpleUndirectedGraph {}
 impl Transitivity for SimpleUndirectedGraph {}
+impl MinCut for SimpleUndirectedGraph {}
+impl Modularity for SimpleUndirectedGraph {}
+impl Motifs for SimpleUndirectedGraph {}
 impl ShortestPaths for SimpleUndirectedGraph {}
+impl StructuralRoles for SimpleUndirectedGraph {}
+impl Similarity for SimpleUndirectedGraph {}
+impl SimRank for SimpleUndirectedGraph {}
+impl SpanningTree for SimpleUndirectedGraph {}
+impl Statistics for SimpleUndirectedGraph {}
 impl AlgebraicConnectivity for SimpleUndirectedGraph {}
 impl EigenvectorCentrality for SimpleUndirectedGraph {}
+impl Diff for SimpleUndirectedGraph {}
+impl IndependentSet for SimpleUndirectedGraph {}
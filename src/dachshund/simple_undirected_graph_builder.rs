@@ -4,14 +4,16 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_builder_base::{
     GraphBuilderBase, GraphBuilderBaseWithCliques, GraphBuilderBaseWithPreProcessing,
 };
 use crate::dachshund::id_types::NodeId;
+use crate::dachshund::matrix_market::read_matrix_market;
 use crate::dachshund::node::SimpleNode;
 use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read};
 extern crate fxhash;
 use fxhash::FxHashMap;
 use itertools::Itertools;
@@ -56,6 +58,81 @@ pub trait TSimpleUndirectedGraphBuilder:
         self.from_vector(v.into_iter().map(|(x, y)| (x as i64, y as i64)).collect())
     }
 
+    // Edges of num_cliques disjoint cliques of clique_size nodes each, with clique i
+    // on ids i * clique_size..(i + 1) * clique_size. Fails with fewer than two
+    // cliques, or cliques of fewer than two nodes.
+    fn get_clique_edges(num_cliques: u64, clique_size: u64) -> CLQResult<BTreeSet<(u64, u64)>> {
+        if num_cliques < 2 {
+            return Err(CLQError::InvalidParameter(format!(
+                "number of cliques must be at least 2, got {}.",
+                num_cliques
+            )));
+        }
+        if clique_size < 2 {
+            return Err(CLQError::InvalidParameter(format!(
+                "clique size must be at least 2, got {}.",
+                clique_size
+            )));
+        }
+        let mut edges = BTreeSet::new();
+        for start in (0..num_cliques).map(|i| i * clique_size) {
+            for i in start..start + clique_size {
+                for j in i + 1..start + clique_size {
+                    edges.insert((i, j));
+                }
+            }
+        }
+        Ok(edges)
+    }
+
+    // Connected caveman graph (Watts, 1999): num_cliques cliques of clique_size
+    // nodes, each with one of its internal edges rewired to the next clique around
+    // a ring. Clique i is on ids i * clique_size..(i + 1) * clique_size, and its
+    // first node swaps its edge to the second node for an edge to the last node of
+    // the previous clique.
+    fn get_connected_caveman_graph(
+        &mut self,
+        num_cliques: u64,
+        clique_size: u64,
+    ) -> CLQResult<Self::GraphType> {
+        let mut edges = Self::get_clique_edges(num_cliques, clique_size)?;
+        let num_nodes = num_cliques * clique_size;
+        for start in (0..num_cliques).map(|i| i * clique_size) {
+            edges.remove(&(start, start + 1));
+            let previous = (start + num_nodes - 1) % num_nodes;
+            edges.insert((start.min(previous), start.max(previous)));
+        }
+        self.from_vector(
+            edges
+                .into_iter()
+                .map(|(x, y)| (x as i64, y as i64))
+                .collect(),
+        )
+    }
+
+    // Ring of cliques: num_cliques cliques of clique_size nodes, each joined to the
+    // next one around a ring by a single edge, from the second node of a clique to
+    // the first node of the next. Clique i is on ids i * clique_size..(i + 1) *
+    // clique_size.
+    fn get_ring_of_cliques_graph(
+        &mut self,
+        num_cliques: u64,
+        clique_size: u64,
+    ) -> CLQResult<Self::GraphType> {
+        let mut edges = Self::get_clique_edges(num_cliques, clique_size)?;
+        let num_nodes = num_cliques * clique_size;
+        for start in (0..num_cliques).map(|i| i * clique_size) {
+            let next = (start + clique_size) % num_nodes;
+            edges.insert(((start + 1).min(next), (start + 1).max(next)));
+        }
+        self.from_vector(
+            edges
+                .into_iter()
+                .map(|(x, y)| (x as i64, y as i64))
+                .collect(),
+        )
+    }
+
     // Builds an Erdos-Renyi graph on n edges with p vertices.
     // (Each possible edge is added to the graph independently at random with
     //  probability p.)
@@ -76,6 +153,130 @@ pub trait TSimpleUndirectedGraphBuilder:
         self.from_vector(v.into_iter().map(|(x, y)| (x as i64, y as i64)).collect())
     }
 
+    // Same as from_vector, with key => value attributes for some of the nodes.
+    // Fails if a node with attributes doesn't appear in any edge.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_vector_with_attributes(
+        &mut self,
+        data: Vec<(i64, i64)>,
+        attributes: HashMap<NodeId, HashMap<String, String>>,
+    ) -> CLQResult<SimpleUndirectedGraph> {
+        let mut graph = self.from_vector(data)?;
+        for id in attributes.keys() {
+            if !graph.nodes.contains_key(id) {
+                return Err(CLQError::NodeNotFound(*id));
+            }
+        }
+        graph.attributes = attributes;
+        Ok(graph)
+    }
+
+    // Reads a graph in the DIMACS edge format: "c" comment lines, a single
+    // "p edge <num_nodes> <num_edges>" header, and one "e <u> <v>" line per edge.
+    // Node ids are kept as they appear in the file (1..=num_nodes), and nodes
+    // without any edges are still added to the graph. Fails if the header isn't
+    // an "edge" one, or if the number of "e" lines doesn't match num_edges.
+    fn from_dimacs<R: Read>(reader: R) -> CLQResult<SimpleUndirectedGraph> {
+        let mut ids: BTreeMap<NodeId, BTreeSet<NodeId>> = BTreeMap::new();
+        let mut num_nodes: Option<i64> = None;
+        let mut num_edges: usize = 0;
+        let mut num_edge_lines: usize = 0;
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens.first() {
+                None | Some(&"c") => continue,
+                Some(&"p") => {
+                    if tokens.len() != 4 || tokens[1] != "edge" || num_nodes.is_some() {
+                        return Err(CLQError::from(format!(
+                            "Line {}: malformed DIMACS header: {}",
+                            i + 1,
+                            line
+                        )));
+                    }
+                    let n: i64 = tokens[2].parse()?;
+                    num_edges = tokens[3].parse()?;
+                    for id in 1..=n {
+                        ids.insert(NodeId::from(id), BTreeSet::new());
+                    }
+                    num_nodes = Some(n);
+                }
+                Some(&"e") => {
+                    let n = num_nodes.ok_or_else(|| {
+                        CLQError::from(format!("Line {}: edge before DIMACS header.", i + 1))
+                    })?;
+                    if tokens.len() != 3 {
+                        return Err(CLQError::from(format!(
+                            "Line {}: malformed DIMACS edge: {}",
+                            i + 1,
+                            line
+                        )));
+                    }
+                    num_edge_lines += 1;
+                    let id1: i64 = tokens[1].parse()?;
+                    let id2: i64 = tokens[2].parse()?;
+                    if id1 < 1 || id1 > n || id2 < 1 || id2 > n {
+                        return Err(CLQError::from(format!(
+                            "Line {}: node id out of range 1..={}: {}",
+                            i + 1,
+                            n,
+                            line
+                        )));
+                    }
+                    ids.get_mut(&NodeId::from(id1))
+                        .unwrap()
+                        .insert(NodeId::from(id2));
+                    ids.get_mut(&NodeId::from(id2))
+                        .unwrap()
+                        .insert(NodeId::from(id1));
+                }
+                Some(_) => {
+                    return Err(CLQError::from(format!(
+                        "Line {}: unrecognized DIMACS line: {}",
+                        i + 1,
+                        line
+                    )))
+                }
+            }
+        }
+        if num_edge_lines != num_edges {
+            return Err(CLQError::from(format!(
+                "DIMACS header declares {} edges, but the file has {}.",
+                num_edges, num_edge_lines
+            )));
+        }
+        let nodes = Self::get_nodes(ids);
+        Ok(SimpleUndirectedGraph::new(
+            nodes.keys().cloned().collect(),
+            nodes,
+            HashMap::new(),
+        ))
+    }
+
+    // Reads a square Matrix Market coordinate matrix, treating each off-diagonal
+    // nonzero as an edge. Node ids are the (1-indexed) row numbers. If symmetric is
+    // set, only the lower triangle is expected to be stored in the file.
+    fn from_matrix_market<R: Read>(reader: R, symmetric: bool) -> CLQResult<SimpleUndirectedGraph> {
+        let matrix = read_matrix_market(reader, symmetric)?;
+        let mut ids: BTreeMap<NodeId, BTreeSet<NodeId>> = (1..=matrix.num_nodes)
+            .map(|id| (NodeId::from(id), BTreeSet::new()))
+            .collect();
+        for (row, col, _value) in matrix.entries {
+            ids.get_mut(&NodeId::from(row))
+                .unwrap()
+                .insert(NodeId::from(col));
+            ids.get_mut(&NodeId::from(col))
+                .unwrap()
+                .insert(NodeId::from(row));
+        }
+        let nodes = Self::get_nodes(ids);
+        Ok(SimpleUndirectedGraph::new(
+            nodes.keys().cloned().collect(),
+            nodes,
+            HashMap::new(),
+        ))
+    }
+
     fn get_node_ids(data: &Vec<(i64, i64)>) -> BTreeMap<NodeId, BTreeSet<NodeId>> {
         let mut ids: BTreeMap<NodeId, BTreeSet<NodeId>> = BTreeMap::new();
         for (id1, id2) in data {
@@ -114,10 +315,11 @@ impl<T: GraphBuilderBaseWithPreProcessing + TSimpleUndirectedGraphBuilder> Graph
         let rows = self.pre_process_rows(data)?;
         let ids = Self::get_node_ids(&rows);
         let nodes = Self::get_nodes(ids);
-        Ok(SimpleUndirectedGraph {
-            ids: nodes.keys().cloned().collect(),
+        Ok(SimpleUndirectedGraph::new(
+            nodes.keys().cloned().collect(),
             nodes,
-        })
+            HashMap::new(),
+        ))
     }
 }
 impl TSimpleUndirectedGraphBuilder for SimpleUndirectedGraphBuilder {}
@@ -164,3 +366,49 @@ impl GraphBuilderBaseWithCliques for SimpleUndirectedGraphBuilderWithCliques {
         &self.cliques
     }
 }
+
+/// Builds a `SimpleUndirectedGraph` from edges pushed one at a time (e.g. while
+/// reading a file), instead of from a vector of rows. Edges are kept as a compact
+/// list of id pairs, and neighbor sets are only built by `build`.
+#[derive(Default)]
+pub struct StreamingGraphBuilder {
+    edges: Vec<(NodeId, NodeId)>,
+    // number of edges after the last deduplication.
+    num_compacted: usize,
+}
+
+impl StreamingGraphBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    // Adds the undirected edge (a, b). Repeated edges (in either direction) are
+    // fine: duplicates are dropped whenever the list has doubled in size since it
+    // was last deduplicated, which keeps memory proportional to distinct edges.
+    pub fn push_edge(&mut self, a: NodeId, b: NodeId) {
+        self.edges.push((a.min(b), a.max(b)));
+        if self.edges.len() >= 1024 && self.edges.len() >= 2 * self.num_compacted {
+            self.compact();
+        }
+    }
+    fn compact(&mut self) {
+        self.edges.sort_unstable();
+        self.edges.dedup();
+        self.num_compacted = self.edges.len();
+    }
+    // Builds the same graph as `SimpleUndirectedGraphBuilder::from_vector` would
+    // from the pushed edges.
+    pub fn build(mut self) -> CLQResult<SimpleUndirectedGraph> {
+        self.compact();
+        let mut ids: BTreeMap<NodeId, BTreeSet<NodeId>> = BTreeMap::new();
+        for (a, b) in self.edges {
+            ids.entry(a).or_default().insert(b);
+            ids.entry(b).or_default().insert(a);
+        }
+        let nodes = SimpleUndirectedGraphBuilder::get_nodes(ids);
+        Ok(SimpleUndirectedGraph::new(
+            nodes.keys().cloned().collect(),
+            nodes,
+            HashMap::new(),
+        ))
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+
+/// An undirected edge observed at a given time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TemporalEdge {
+    pub source_id: NodeId,
+    pub target_id: NodeId,
+    pub timestamp: u64,
+}
+
+/// Time-stamped undirected edges, from which static snapshots of the graph over
+/// given time windows can be taken, to be analyzed with the usual algorithms.
+pub struct TemporalGraph {
+    pub edges: Vec<TemporalEdge>,
+}
+impl TemporalGraph {
+    // Builds a temporal graph from (source, target, timestamp) rows. The same edge
+    // may appear several times, with different timestamps.
+    pub fn from_vector(data: Vec<(i64, i64, u64)>) -> Self {
+        let mut edges: Vec<TemporalEdge> = data
+            .into_iter()
+            .map(|(source, target, timestamp)| TemporalEdge {
+                source_id: NodeId::from(source),
+                target_id: NodeId::from(target),
+                timestamp,
+            })
+            .collect();
+        edges.sort_by_key(|e| e.timestamp);
+        TemporalGraph { edges }
+    }
+    // Range of timestamps of the edges, as (earliest, latest). None if there are no edges.
+    pub fn get_time_span(&self) -> Option<(u64, u64)> {
+        match (self.edges.first(), self.edges.last()) {
+            (Some(first), Some(last)) => Some((first.timestamp, last.timestamp)),
+            _ => None,
+        }
+    }
+    // The graph made of the edges with start <= timestamp < end, and the nodes they
+    // touch. Nodes without any edge in the window are not part of the snapshot.
+    pub fn snapshot(&self, start: u64, end: u64) -> CLQResult<SimpleUndirectedGraph> {
+        if start > end {
+            return Err(CLQError::InvalidParameter(format!(
+                "time window start {} is after its end {}.",
+                start, end
+            )));
+        }
+        // edges are sorted by timestamp.
+        let first = self.edges.partition_point(|e| e.timestamp < start);
+        let last = self.edges.partition_point(|e| e.timestamp < end);
+        let rows: Vec<(i64, i64)> = self.edges[first..last]
+            .iter()
+            .map(|e| (e.source_id.value(), e.target_id.value()))
+            .collect();
+        SimpleUndirectedGraphBuilder {}.from_vector(rows)
+    }
+}
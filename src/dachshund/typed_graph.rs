@@ -6,11 +6,15 @@
  */
 extern crate fxhash;
 extern crate nalgebra as na;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
-use crate::dachshund::id_types::NodeLabel;
-use crate::dachshund::node::Node;
-use fxhash::FxHashMap;
+use crate::dachshund::id_types::{EdgeTypeId, NodeId, NodeLabel};
+use crate::dachshund::node::{Node, NodeEdge, SimpleNode};
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use fxhash::{FxHashMap, FxHashSet};
+use roaring::RoaringBitmap;
 use std::collections::hash_map::{Keys, Values};
+use std::collections::{BTreeSet, HashMap};
 
 pub trait LabeledGraph: GraphBase {
     fn get_core_labels(&self) -> Vec<NodeLabel>;
@@ -109,3 +113,125 @@ impl GraphBase for TypedGraph {
         }
     }
 }
+impl TypedGraph {
+    fn get_neighbor_ids(&self, node_id: u32) -> FxHashSet<u32> {
+        self.nodes[&node_id]
+            .edges
+            .iter()
+            .map(|e| e.target_id)
+            .collect()
+    }
+    /// Clustering coefficient for bipartite graphs, as defined by Latapy, Magnien and
+    /// Del Vecchio ("Basic notions for the analysis of large two-mode networks", 2008):
+    /// the average, over all nodes v two hops away from the node u, of the Jaccard
+    /// overlap |N(u) & N(v)| / |N(u) | N(v)| of their neighborhoods. Nodes with no
+    /// nodes two hops away get 0.0. Edges of different types are not distinguished.
+    /// Fails if the node isn't in the graph, or if an edge within two hops of it
+    /// joins two core or two non-core nodes.
+    pub fn bipartite_clustering_coefficient(&self, node_id: u32) -> CLQResult<f64> {
+        let is_core = self
+            .nodes
+            .get(&node_id)
+            .ok_or_else(|| CLQError::NodeNotFound(NodeId::from(node_id)))?
+            .is_core;
+        let neighbors = self.get_neighbor_ids(node_id);
+        let mut second_neighbors: FxHashSet<u32> = FxHashSet::default();
+        for id in &neighbors {
+            if self.nodes[id].is_core == is_core {
+                return Err(CLQError::NotBipartite);
+            }
+            let ids = self.get_neighbor_ids(*id);
+            if ids.iter().any(|other| self.nodes[other].is_core != is_core) {
+                return Err(CLQError::NotBipartite);
+            }
+            second_neighbors.extend(ids);
+        }
+        second_neighbors.remove(&node_id);
+        if second_neighbors.is_empty() {
+            return Ok(0.0);
+        }
+        let total_overlap: f64 = second_neighbors
+            .iter()
+            .map(|id| {
+                let other = self.get_neighbor_ids(*id);
+                neighbors.intersection(&other).count() as f64
+                    / neighbors.union(&other).count() as f64
+            })
+            .sum();
+        Ok(total_overlap / second_neighbors.len() as f64)
+    }
+    /// Untyped copy of the graph, with nodes identified by their labels: core and
+    /// non-core nodes become plain nodes, and nodes connected by edges of one or more
+    /// types become neighbors. Fails if a node has no label.
+    pub fn to_simple_undirected(&self) -> CLQResult<SimpleUndirectedGraph> {
+        let labels = self.get_reverse_labels_map();
+        let get_label = |id: &u32| -> CLQResult<NodeId> {
+            labels
+                .get(id)
+                .cloned()
+                .ok_or_else(|| CLQError::InvalidParameter(format!("node {} has no label.", id)))
+        };
+        let mut nodes: FxHashMap<NodeId, SimpleNode> = FxHashMap::default();
+        for node in self.nodes.values() {
+            let node_id = get_label(&node.node_id)?;
+            let neighbors = node
+                .edges
+                .iter()
+                .map(|e| get_label(&e.target_id))
+                .collect::<CLQResult<BTreeSet<NodeId>>>()?;
+            nodes.insert(node_id, SimpleNode { node_id, neighbors });
+        }
+        Ok(SimpleUndirectedGraph::new(
+            nodes.keys().cloned().collect(),
+            nodes,
+            HashMap::new(),
+        ))
+    }
+    /// Copy of the graph with only the edges of edge_type, for looking at a single
+    /// relation in isolation. Every node is kept, along with its internal id and
+    /// label, even if it loses all of its edges. Fails if no edge has edge_type.
+    pub fn edge_type_subgraph(&self, edge_type: EdgeTypeId) -> CLQResult<TypedGraph> {
+        if !self
+            .nodes
+            .values()
+            .any(|node| node.neighbors_sets.contains_key(&edge_type))
+        {
+            return Err(CLQError::InvalidParameter(format!(
+                "edge type {} does not occur in the graph.",
+                edge_type.value()
+            )));
+        }
+        let nodes: FxHashMap<u32, Node> = self
+            .nodes
+            .iter()
+            .map(|(id, node)| {
+                let edges: Vec<NodeEdge> = node
+                    .edges
+                    .iter()
+                    .filter(|e| e.edge_type == edge_type)
+                    .map(|e| NodeEdge::new(e.edge_type, e.target_id))
+                    .collect();
+                let neighbors_sets: HashMap<EdgeTypeId, RoaringBitmap> = node
+                    .neighbors_sets
+                    .get(&edge_type)
+                    .map(|neighbors| (edge_type, neighbors.clone()))
+                    .into_iter()
+                    .collect();
+                let subgraph_node = Node::new(
+                    node.node_id,
+                    node.is_core,
+                    node.non_core_type,
+                    edges,
+                    neighbors_sets,
+                );
+                (*id, subgraph_node)
+            })
+            .collect();
+        Ok(TypedGraph {
+            nodes,
+            core_ids: self.core_ids.clone(),
+            non_core_ids: self.non_core_ids.clone(),
+            labels_map: self.labels_map.clone(),
+        })
+    }
+}
@@ -0,0 +1,64 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+use crate::dachshund::algorithms::pagerank::PageRank;
+use crate::dachshund::algorithms::triad_census::TriadCensus;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::WeightedDirectedNode;
+use crate::dachshund::simple_directed_graph::DirectedGraph;
+use fxhash::FxHashMap;
+use std::collections::hash_map::{Keys, Values};
+
+/// Keeps track of a weighted directed graph, where each edge carries a
+/// (non-negative) weight.
+pub struct WeightedDirectedGraph {
+    pub nodes: FxHashMap<NodeId, WeightedDirectedNode>,
+    pub ids: Vec<NodeId>,
+}
+impl GraphBase for WeightedDirectedGraph {
+    type NodeType = WeightedDirectedNode;
+
+    /// core and non-core IDs are the same for a `WeightedDirectedGraph`.
+    fn get_core_ids(&self) -> &Vec<NodeId> {
+        &self.ids
+    }
+    /// core and non-core IDs are the same for a `WeightedDirectedGraph`.
+    fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
+        Some(&self.ids)
+    }
+    fn get_ids_iter(&self) -> Keys<'_, NodeId, WeightedDirectedNode> {
+        self.nodes.keys()
+    }
+    fn get_nodes_iter(&self) -> Values<'_, NodeId, WeightedDirectedNode> {
+        self.nodes.values()
+    }
+    fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, WeightedDirectedNode> {
+        &mut self.nodes
+    }
+    fn has_node(&self, node_id: NodeId) -> bool {
+        self.nodes.contains_key(&node_id)
+    }
+    fn get_node(&self, node_id: NodeId) -> &WeightedDirectedNode {
+        &self.nodes[&node_id]
+    }
+    fn count_edges(&self) -> usize {
+        self.nodes.values().map(|node| node.out_edges.len()).sum()
+    }
+    fn count_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+    fn create_empty() -> Self {
+        WeightedDirectedGraph {
+            nodes: FxHashMap::default(),
+            ids: Vec::new(),
+        }
+    }
+}
+impl DirectedGraph for WeightedDirectedGraph {}
+impl PageRank for WeightedDirectedGraph {}
+impl TriadCensus for WeightedDirectedGraph {}
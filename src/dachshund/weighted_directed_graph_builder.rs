@@ -0,0 +1,70 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{WeightedDirectedNode, WeightedNodeEdge};
+use crate::dachshund::weighted_directed_graph::WeightedDirectedGraph;
+use fxhash::FxHashMap;
+use std::collections::BTreeMap;
+
+pub struct WeightedDirectedGraphBuilder {}
+
+impl GraphBuilderBase for WeightedDirectedGraphBuilder {
+    type GraphType = WeightedDirectedGraph;
+    type RowType = (i64, i64, f64);
+
+    // builds a graph from a vector of (source, target, weight) rows. If an edge is
+    // repeated, the last weight is kept. Weights must be non-negative.
+    #[allow(clippy::ptr_arg)]
+    fn from_vector(&mut self, data: Vec<(i64, i64, f64)>) -> CLQResult<WeightedDirectedGraph> {
+        type Edges = BTreeMap<NodeId, f64>;
+        let mut ids: BTreeMap<NodeId, (Edges, Edges)> = BTreeMap::new();
+        for (id1, id2, weight) in data {
+            if weight.is_nan() {
+                return Err(CLQError::InvalidParameter(format!(
+                    "edge ({}, {}) has a NaN weight.",
+                    id1, id2
+                )));
+            }
+            if weight < 0.0 {
+                return Err(CLQError::NegativeWeight(weight));
+            }
+            ids.entry(NodeId::from(id1))
+                .or_default()
+                .1
+                .insert(NodeId::from(id2), weight);
+            ids.entry(NodeId::from(id2))
+                .or_default()
+                .0
+                .insert(NodeId::from(id1), weight);
+        }
+        let to_edges = |edges: Edges| -> Vec<WeightedNodeEdge> {
+            edges
+                .into_iter()
+                .map(|(target_id, weight)| WeightedNodeEdge::new(target_id, weight))
+                .collect()
+        };
+        let mut nodes: FxHashMap<NodeId, WeightedDirectedNode> = FxHashMap::default();
+        for (id, (in_edges, out_edges)) in ids.into_iter() {
+            nodes.insert(
+                id,
+                WeightedDirectedNode {
+                    node_id: id,
+                    in_edges: to_edges(in_edges),
+                    out_edges: to_edges(out_edges),
+                },
+            );
+        }
+        Ok(WeightedDirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        })
+    }
+}
@@ -10,24 +10,55 @@ extern crate priority_queue;
 
 use crate::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
 use crate::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
-use crate::dachshund::algorithms::betweenness::Betweenness;
+use crate::dachshund::algorithms::betweenness::{Betweenness, WeightedBetweenness};
+use crate::dachshund::algorithms::cliques::Cliques;
+use crate::dachshund::algorithms::closeness::{Closeness, WeightedCloseness};
 use crate::dachshund::algorithms::clustering::Clustering;
 use crate::dachshund::algorithms::connected_components::{
-    ConnectedComponents, ConnectedComponentsUndirected,
+    ConnectedComponents, ConnectedComponentsUndirected, WeightedConnectedComponents,
 };
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
 use crate::dachshund::algorithms::coreness::{Coreness, FractionalCoreness};
 use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
-use crate::dachshund::algorithms::laplacian::Laplacian;
-use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
-use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::algorithms::graph_diff::Diff;
+use crate::dachshund::algorithms::independent_set::IndependentSet;
+use crate::dachshund::algorithms::label_propagation::WeightedLabelPropagation;
+use crate::dachshund::algorithms::laplacian::{Laplacian, WeightedLaplacian};
+use crate::dachshund::algorithms::modularity::{Modularity, WeightedModularity};
+use crate::dachshund::algorithms::motifs::Motifs;
+use crate::dachshund::algorithms::relabel::RelabelContiguous;
+use crate::dachshund::algorithms::roles::StructuralRoles;
+use crate::dachshund::algorithms::shortest_paths::{ShortestPaths, WeightedShortestPaths};
+use crate::dachshund::algorithms::similarity::{Similarity, WeightedSimilarity};
+use crate::dachshund::algorithms::spanning_tree::{SpanningTree, WeightedSpanningTree};
+use crate::dachshund::algorithms::statistics::{Statistics, WeightedStatistics};
+use crate::dachshund::algorithms::subgraph::InducedSubgraph;
+use crate::dachshund::algorithms::transitivity::{Transitivity, WeightedTransitivity};
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase};
-use crate::dachshund::simple_undirected_graph::UndirectedGraph;
+use crate::dachshund::node::{
+    NodeBase, NodeEdgeBase, SimpleNode, WeightedNode, WeightedNodeBase, WeightedNodeEdge,
+};
+use crate::dachshund::simple_undirected_graph::{SimpleUndirectedGraph, UndirectedGraph};
 
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use rand::rngs::StdRng;
 use std::collections::hash_map::{Keys, Values};
+use std::collections::{BTreeMap, HashMap};
+
+/// How `WeightedUndirectedGraph::normalize_edge_weights` rescales edge weights.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WeightNorm {
+    /// Maps the lightest edge to 0.0 and the heaviest to 1.0.
+    MinMax,
+    /// Divides by the total edge weight, so that weights sum to 1.0.
+    SumToOne,
+    /// Subtracts the mean edge weight and divides by the standard deviation.
+    Standardize,
+}
 
 /// Keeps track of a weighted undirected graph, composed of nodes that have weighed.
 pub struct WeightedUndirectedGraph {
@@ -95,27 +126,438 @@ impl WeightedUndirectedGraph {
         }
         rows.join("\n")
     }
+    // Rescales all edge weights according to scheme. Statistics are taken over
+    // undirected edges (each counted once), and both directions of an edge are
+    // rescaled the same way. Degenerate cases leave a well-defined result: MinMax
+    // sets equal weights to 1.0, Standardize sets them to 0.0, and SumToOne leaves
+    // weights alone if they sum to 0.0.
+    pub fn normalize_edge_weights(&mut self, scheme: WeightNorm) {
+        let weights: Vec<f64> = self
+            .nodes
+            .iter()
+            .flat_map(|(id, node)| {
+                node.edges
+                    .iter()
+                    .filter(move |e| *id <= e.target_id)
+                    .map(|e| e.weight)
+            })
+            .collect();
+        if weights.is_empty() {
+            return;
+        }
+        let n = weights.len() as f64;
+        let rescale: Box<dyn Fn(f64) -> f64> = match scheme {
+            WeightNorm::MinMax => {
+                let min = weights.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = weights.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                if max > min {
+                    Box::new(move |w| (w - min) / (max - min))
+                } else {
+                    Box::new(|_| 1.0)
+                }
+            }
+            WeightNorm::SumToOne => {
+                let total: f64 = weights.iter().sum();
+                if total == 0.0 {
+                    return;
+                }
+                Box::new(move |w| w / total)
+            }
+            WeightNorm::Standardize => {
+                let mean = weights.iter().sum::<f64>() / n;
+                let variance = weights.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / n;
+                let std_dev = variance.sqrt();
+                if std_dev > 0.0 {
+                    Box::new(move |w| (w - mean) / std_dev)
+                } else {
+                    Box::new(|_| 0.0)
+                }
+            }
+        };
+        for node in self.nodes.values_mut() {
+            for e in node.edges.iter_mut() {
+                e.weight = rescale(e.weight);
+            }
+        }
+    }
+    // Unweighted copy of the graph, keeping only the edges weighing at least
+    // min_weight. Every node is kept, even if it loses all of its edges, so that
+    // sweeping the threshold gives a filtration of graphs on the same nodes.
+    pub fn threshold(&self, min_weight: f64) -> CLQResult<SimpleUndirectedGraph> {
+        if min_weight.is_nan() {
+            return Err(CLQError::InvalidParameter(
+                "threshold weight cannot be NaN.".to_owned(),
+            ));
+        }
+        let nodes: FxHashMap<NodeId, SimpleNode> = self
+            .nodes
+            .iter()
+            .map(|(id, node)| {
+                let simple_node = SimpleNode {
+                    node_id: *id,
+                    neighbors: node
+                        .edges
+                        .iter()
+                        .filter(|e| e.weight >= min_weight)
+                        .map(|e| e.target_id)
+                        .collect(),
+                };
+                (*id, simple_node)
+            })
+            .collect();
+        Ok(SimpleUndirectedGraph::new(
+            self.ids.clone(),
+            nodes,
+            HashMap::new(),
+        ))
+    }
+    // Disparity filter backbone (Serrano, Boguna & Vespignani, 2009): an edge of
+    // weight w at a node of strength s and degree k is significant for that node
+    // if (1 - w / s)^(k - 1) < alpha, the probability of a weight share at least as
+    // large under a uniform random split of s among k edges. Keeps the edges that
+    // are significant for either end, so lower alpha keeps fewer edges. Edges at a
+    // node of degree 1 are never significant for that node. Every node is kept,
+    // even if it loses all of its edges. alpha must be in (0.0, 1.0].
+    pub fn disparity_backbone(&self, alpha: f64) -> CLQResult<WeightedUndirectedGraph> {
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return Err(CLQError::InvalidParameter(format!(
+                "significance level must be in (0.0, 1.0], got {}.",
+                alpha
+            )));
+        }
+        let is_significant = |node: &WeightedNode, weight: f64| {
+            let k = node.edges.len();
+            let strength = node.weight();
+            k > 1 && strength > 0.0 && (1.0 - weight / strength).powi(k as i32 - 1) < alpha
+        };
+        let nodes: FxHashMap<NodeId, WeightedNode> = self
+            .nodes
+            .iter()
+            .map(|(id, node)| {
+                let edges: Vec<WeightedNodeEdge> = node
+                    .edges
+                    .iter()
+                    .filter(|e| {
+                        is_significant(node, e.weight)
+                            || is_significant(&self.nodes[&e.target_id], e.weight)
+                    })
+                    .map(|e| WeightedNodeEdge {
+                        target_id: e.target_id,
+                        weight: e.weight,
+                    })
+                    .collect();
+                let backbone_node = WeightedNode {
+                    node_id: *id,
+                    neighbors: edges.iter().map(|e| e.target_id).collect(),
+                    edges,
+                };
+                (*id, backbone_node)
+            })
+            .collect();
+        Ok(WeightedUndirectedGraph {
+            ids: self.ids.clone(),
+            nodes,
+        })
+    }
     pub fn get_node_degree(&self, id: NodeId) -> usize {
         self.nodes[&id].degree()
     }
     pub fn get_node_weight(&self, id: NodeId) -> f64 {
         self.nodes[&id].weight()
     }
+    // Merges b into a along the edge (a, b), which is dropped rather than turned into
+    // a self-loop. Every other edge (b, c) is redirected to a; if a and c were
+    // already connected, the two parallel edges become one edge whose weight is the
+    // sum of theirs, so that weights keep track of edge multiplicities. Returns a.
+    pub fn contract_edge(&mut self, a: NodeId, b: NodeId) -> CLQResult<NodeId> {
+        if a == b {
+            return Err(CLQError::InvalidParameter(
+                "cannot contract a self-loop.".to_owned(),
+            ));
+        }
+        for id in [a, b] {
+            if !self.nodes.contains_key(&id) {
+                return Err(CLQError::NodeNotFound(id));
+            }
+        }
+        if !self.nodes[&a].neighbors.contains(&b) {
+            return Err(CLQError::InvalidParameter(format!(
+                "edge ({}, {}) is not in the graph.",
+                a.value(),
+                b.value()
+            )));
+        }
+        let removed = self.nodes.remove(&b).unwrap();
+        self.ids.retain(|id| *id != b);
+        let node_a = self.nodes.get_mut(&a).unwrap();
+        node_a.edges.retain(|e| e.target_id != b);
+        node_a.neighbors.remove(&b);
+
+        for edge in removed.edges {
+            let c = edge.target_id;
+            if c == a {
+                continue;
+            }
+            let node_c = self.nodes.get_mut(&c).unwrap();
+            node_c.edges.retain(|e| e.target_id != b);
+            node_c.neighbors.remove(&b);
+            for (x, y) in [(a, c), (c, a)] {
+                let node_x = self.nodes.get_mut(&x).unwrap();
+                match node_x.edges.iter_mut().find(|e| e.target_id == y) {
+                    Some(e) => e.weight += edge.weight,
+                    None => {
+                        node_x.edges.push(WeightedNodeEdge::new(y, edge.weight));
+                        node_x.neighbors.insert(y);
+                    }
+                }
+            }
+        }
+        Ok(a)
+    }
+
+    // Random walk of up to length steps from start, stepping to each neighbor with
+    // probability proportional to the weight of the edge to it. Returns the visited
+    // nodes, starting with start; the walk ends early at a node without any edge of
+    // positive weight. Empty if start is not in the graph.
+    pub fn weighted_random_walk(&self, start: NodeId, length: usize, seed: u64) -> Vec<NodeId> {
+        if !self.nodes.contains_key(&start) {
+            return Vec::new();
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut walk: Vec<NodeId> = vec![start];
+        let mut current = start;
+        for _ in 0..length {
+            let edges = &self.nodes[&current].edges;
+            let dist = match WeightedIndex::new(edges.iter().map(|e| e.weight)) {
+                Ok(dist) => dist,
+                Err(_) => break,
+            };
+            current = edges[dist.sample(&mut rng)].target_id;
+            walk.push(current);
+        }
+        walk
+    }
+    // Second-order random walk of node2vec (Grover & Leskovec, 2016) on the weighted
+    // graph. Having stepped from prev to current, the walk steps to a neighbor x of
+    // current with probability proportional to the weight of the edge to x, times
+    // 1 / p if x is prev, 1 if x is a neighbor of prev, and 1 / q otherwise. So a
+    // low p makes the walk backtrack, and a low q makes it move outwards. The first
+    // step has no prev, and is the same as in weighted_random_walk. Otherwise as in
+    // weighted_random_walk, ending early at nodes with no edge of positive weight,
+    // and empty if start is not in the graph. p and q must be positive.
+    pub fn weighted_biased_random_walk(
+        &self,
+        start: NodeId,
+        length: usize,
+        p: f64,
+        q: f64,
+        seed: u64,
+    ) -> CLQResult<Vec<NodeId>> {
+        for (name, value) in [("return parameter p", p), ("in-out parameter q", q)] {
+            if !(value > 0.0 && value.is_finite()) {
+                return Err(CLQError::InvalidParameter(format!(
+                    "{} must be positive, got {}.",
+                    name, value
+                )));
+            }
+        }
+        if !self.nodes.contains_key(&start) {
+            return Ok(Vec::new());
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut walk: Vec<NodeId> = vec![start];
+        let mut prev: Option<NodeId> = None;
+        let mut current = start;
+        for _ in 0..length {
+            let edges = &self.nodes[&current].edges;
+            let bias = |x: NodeId| match prev {
+                None => 1.0,
+                Some(prev) if x == prev => 1.0 / p,
+                Some(prev) if self.nodes[&prev].neighbors.contains(&x) => 1.0,
+                Some(_) => 1.0 / q,
+            };
+            let dist = match WeightedIndex::new(edges.iter().map(|e| e.weight * bias(e.target_id)))
+            {
+                Ok(dist) => dist,
+                Err(_) => break,
+            };
+            prev = Some(current);
+            current = edges[dist.sample(&mut rng)].target_id;
+            walk.push(current);
+        }
+        Ok(walk)
+    }
+    // One coarsening step for multilevel algorithms. Nodes are visited in a random
+    // (seeded) order, and each node that is still unmatched is matched with the
+    // unmatched neighbor it shares the heaviest edge with (ties go to the smaller
+    // id), giving a maximal matching. Each matched pair is then contracted into a
+    // super-node, which takes the smaller of the two ids; unmatched nodes are kept
+    // as they are. Edges within a super-node are dropped and parallel edges are
+    // merged by summing their weights. Returns the coarser graph, along with the
+    // super-node each node of this graph was mapped to.
+    pub fn coarsen(&self, seed: u64) -> CLQResult<(Self, HashMap<NodeId, NodeId>)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        // sorted so that the outcome only depends on the seed.
+        let mut order = self.get_ordered_node_ids();
+        order.shuffle(&mut rng);
+
+        let mut mapping: HashMap<NodeId, NodeId> = HashMap::new();
+        for id in order {
+            if mapping.contains_key(&id) {
+                continue;
+            }
+            let mut best: Option<(f64, NodeId)> = None;
+            for e in &self.nodes[&id].edges {
+                let target_id = e.target_id;
+                if target_id == id || mapping.contains_key(&target_id) {
+                    continue;
+                }
+                best = match best {
+                    Some((weight, best_id))
+                        if weight > e.weight || (weight == e.weight && best_id < target_id) =>
+                    {
+                        Some((weight, best_id))
+                    }
+                    _ => Some((e.weight, target_id)),
+                };
+            }
+            let super_id = match best {
+                Some((_, other)) => {
+                    let super_id = std::cmp::min(id, other);
+                    mapping.insert(other, super_id);
+                    super_id
+                }
+                None => id,
+            };
+            mapping.insert(id, super_id);
+        }
+
+        let mut ids: BTreeMap<NodeId, BTreeMap<NodeId, f64>> = BTreeMap::new();
+        for (id, super_id) in &mapping {
+            let neighbors = ids.entry(*super_id).or_default();
+            for e in &self.nodes[id].edges {
+                let target_super_id = mapping[&e.target_id];
+                if target_super_id != *super_id {
+                    *neighbors.entry(target_super_id).or_insert(0.0) += e.weight;
+                }
+            }
+        }
+        let nodes: FxHashMap<NodeId, WeightedNode> = ids
+            .into_iter()
+            .map(|(id, neighbors)| {
+                let node = WeightedNode {
+                    node_id: id,
+                    edges: neighbors
+                        .iter()
+                        .map(|(target_id, weight)| WeightedNodeEdge::new(*target_id, *weight))
+                        .collect(),
+                    neighbors: neighbors.keys().cloned().collect(),
+                };
+                (id, node)
+            })
+            .collect();
+        let coarse = WeightedUndirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        };
+        Ok((coarse, mapping))
+    }
 }
 impl UndirectedGraph for WeightedUndirectedGraph {}
+impl InducedSubgraph for WeightedUndirectedGraph {
+    fn get_induced_subgraph(&self, node_ids: &[NodeId]) -> CLQResult<Self> {
+        let keep: FxHashSet<NodeId> = node_ids.iter().cloned().collect();
+        let mut nodes: FxHashMap<NodeId, WeightedNode> = FxHashMap::default();
+        for id in &keep {
+            let node = self.nodes.get(id).ok_or(CLQError::NodeNotFound(*id))?;
+            nodes.insert(
+                *id,
+                WeightedNode {
+                    node_id: *id,
+                    edges: node
+                        .edges
+                        .iter()
+                        .filter(|e| keep.contains(&e.target_id))
+                        .map(|e| WeightedNodeEdge::new(e.target_id, e.weight))
+                        .collect(),
+                    neighbors: node
+                        .neighbors
+                        .iter()
+                        .filter(|x| keep.contains(x))
+                        .cloned()
+                        .collect(),
+                },
+            );
+        }
+        Ok(WeightedUndirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        })
+    }
+}
+impl RelabelContiguous for WeightedUndirectedGraph {
+    fn relabel_contiguous(&self) -> (Self, HashMap<NodeId, NodeId>, Vec<NodeId>) {
+        let (old_to_new, new_to_old) = self._get_contiguous_ids();
+        let nodes: FxHashMap<NodeId, WeightedNode> = self
+            .nodes
+            .values()
+            .map(|node| {
+                let id = old_to_new[&node.node_id];
+                let relabeled = WeightedNode {
+                    node_id: id,
+                    edges: node
+                        .edges
+                        .iter()
+                        .map(|e| WeightedNodeEdge::new(old_to_new[&e.target_id], e.weight))
+                        .collect(),
+                    neighbors: node.neighbors.iter().map(|x| old_to_new[x]).collect(),
+                };
+                (id, relabeled)
+            })
+            .collect();
+        let graph = WeightedUndirectedGraph {
+            ids: (0..new_to_old.len())
+                .map(|i| NodeId::from(i as i64))
+                .collect(),
+            nodes,
+        };
+        (graph, old_to_new, new_to_old)
+    }
+}
 
 impl ConnectedComponents for WeightedUndirectedGraph {}
 impl ConnectedComponentsUndirected for WeightedUndirectedGraph {}
+impl WeightedConnectedComponents for WeightedUndirectedGraph {}
 impl Coreness for WeightedUndirectedGraph {}
 impl FractionalCoreness for WeightedUndirectedGraph {}
 
 impl AdjacencyMatrix for WeightedUndirectedGraph {}
+impl Cliques for WeightedUndirectedGraph {}
+impl Closeness for WeightedUndirectedGraph {}
+impl WeightedCloseness for WeightedUndirectedGraph {}
 impl Clustering for WeightedUndirectedGraph {}
 impl Connectivity for WeightedUndirectedGraph {}
 impl ConnectivityUndirected for WeightedUndirectedGraph {}
 impl Betweenness for WeightedUndirectedGraph {}
+impl WeightedBetweenness for WeightedUndirectedGraph {}
 impl Laplacian for WeightedUndirectedGraph {}
+impl WeightedLabelPropagation for WeightedUndirectedGraph {}
+impl WeightedLaplacian for WeightedUndirectedGraph {}
 impl Transitivity for WeightedUndirectedGraph {}
+impl WeightedTransitivity for WeightedUndirectedGraph {}
+impl Modularity for WeightedUndirectedGraph {}
+impl WeightedModularity for WeightedUndirectedGraph {}
+impl Motifs for WeightedUndirectedGraph {}
 impl ShortestPaths for WeightedUndirectedGraph {}
+impl WeightedShortestPaths for WeightedUndirectedGraph {}
+impl StructuralRoles for WeightedUndirectedGraph {}
+impl Similarity for WeightedUndirectedGraph {}
+impl WeightedSimilarity for WeightedUndirectedGraph {}
+impl SpanningTree for WeightedUndirectedGraph {}
+impl WeightedSpanningTree for WeightedUndirectedGraph {}
+impl Statistics for WeightedUndirectedGraph {}
+impl WeightedStatistics for WeightedUndirectedGraph {}
 impl AlgebraicConnectivity for WeightedUndirectedGraph {}
 impl EigenvectorCentrality for WeightedUndirectedGraph {}
+impl Diff for WeightedUndirectedGraph {}
+impl IndependentSet for WeightedUndirectedGraph {}
@@ -4,12 +4,14 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_builder_base::{GraphBuilderBase, GraphBuilderBaseWithPreProcessing};
 use crate::dachshund::id_types::NodeId;
+use crate::dachshund::matrix_market::read_matrix_market;
 use crate::dachshund::node::{WeightedNode, WeightedNodeEdge};
 use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
 use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read};
 extern crate fxhash;
 use fxhash::FxHashMap;
 
@@ -30,6 +32,79 @@ pub trait TWeightedUndirectedGraphBuilder:
         }
         ids
     }
+    // Reads a square Matrix Market coordinate matrix, treating each off-diagonal
+    // nonzero as an edge weighted by its value (1.0 for pattern matrices). If
+    // symmetric is set, only the lower triangle is expected to be stored in the file.
+    fn from_matrix_market<R: Read>(
+        reader: R,
+        symmetric: bool,
+    ) -> CLQResult<WeightedUndirectedGraph> {
+        let matrix = read_matrix_market(reader, symmetric)?;
+        let mut ids: BTreeMap<NodeId, BTreeMap<NodeId, f64>> = (1..=matrix.num_nodes)
+            .map(|id| (NodeId::from(id), BTreeMap::new()))
+            .collect();
+        for (row, col, value) in matrix.entries {
+            ids.get_mut(&NodeId::from(row))
+                .unwrap()
+                .insert(NodeId::from(col), value);
+            ids.get_mut(&NodeId::from(col))
+                .unwrap()
+                .insert(NodeId::from(row), value);
+        }
+        let nodes = Self::get_nodes(ids);
+        Ok(WeightedUndirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        })
+    }
+    // Reads a delimited edge list with source, target and weight columns (any
+    // further columns are ignored). Blank lines and lines starting with '#' are
+    // skipped, as is the first remaining line if has_header is set.
+    fn read_weighted_edge_list<R: Read>(
+        reader: R,
+        has_header: bool,
+        delimiter: u8,
+    ) -> CLQResult<WeightedUndirectedGraph> {
+        let delimiter = delimiter as char;
+        let mut rows: Vec<(i64, i64, f64)> = Vec::new();
+        let mut header_skipped = !has_header;
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if !header_skipped {
+                header_skipped = true;
+                continue;
+            }
+            let fields: Vec<&str> = trimmed.split(delimiter).map(|x| x.trim()).collect();
+            let parsed = match fields[..] {
+                [source, target, weight, ..] => {
+                    match (source.parse(), target.parse(), weight.parse()) {
+                        (Ok(source), Ok(target), Ok(weight)) => Some((source, target, weight)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            match parsed {
+                Some(row) => rows.push(row),
+                None => {
+                    return Err(CLQError::from(format!(
+                        "Line {}: expected source, target and weight, got: {}",
+                        i + 1,
+                        line
+                    )))
+                }
+            }
+        }
+        let nodes = Self::get_nodes(Self::get_node_ids(&rows));
+        Ok(WeightedUndirectedGraph {
+            ids: nodes.keys().cloned().collect(),
+            nodes,
+        })
+    }
     fn get_nodes(ids: BTreeMap<NodeId, BTreeMap<NodeId, f64>>) -> FxHashMap<NodeId, WeightedNode> {
         let mut nodes: FxHashMap<NodeId, WeightedNode> = FxHashMap::default();
         for (id, neighbors) in ids.into_iter() {
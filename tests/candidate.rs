@@ -9,9 +9,10 @@ extern crate lib_dachshund;
 use std::collections::{HashMap, HashSet};
 
 use lib_dachshund::dachshund::candidate::{Candidate, Recipe};
-use lib_dachshund::dachshund::error::CLQResult;
-use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
-use lib_dachshund::dachshund::node::Node;
+use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::id_types::{EdgeTypeId, GraphId, NodeId};
+use lib_dachshund::dachshund::node::{Node, NodeBase, NodeEdge};
 use lib_dachshund::dachshund::row::CliqueRow;
 use lib_dachshund::dachshund::row::EdgeRow;
 use lib_dachshund::dachshund::scorer::Scorer;
@@ -414,3 +415,123 @@ fn test_one_step_search() -> CLQResult<()> {
 
     Ok(())
 }
+
+/// Test the bipartite clustering coefficient on the sample graph.
+///
+///  1 - 2
+///    \\
+///  3 - 4
+///    \
+///  5 - 6
+#[test]
+fn test_bipartite_clustering_coefficient() -> CLQResult<()> {
+    let (graph, _transformer) = build_sample_graph();
+    let coefficient = |label: i64| {
+        graph
+            .bipartite_clustering_coefficient(graph.labels_map[&NodeId::from(label)])
+            .unwrap()
+    };
+    // 3 is two hops away from 1 (overlap 1/3: {4} out of {2, 4, 6}) and 5 (overlap
+    // 1/2: {6} out of {4, 6}).
+    assert!((coefficient(3) - (1.0 / 3.0 + 1.0 / 2.0) / 2.0).abs() <= 1e-12);
+    assert!((coefficient(1) - 1.0 / 3.0).abs() <= 1e-12);
+    assert!((coefficient(5) - 1.0 / 2.0).abs() <= 1e-12);
+    // article 6 is only two hops away from 4, with which it shares author 3 out of {1, 3, 5}.
+    assert!((coefficient(6) - 1.0 / 3.0).abs() <= 1e-12);
+
+    assert!(matches!(
+        graph.bipartite_clustering_coefficient(1000),
+        Err(CLQError::NodeNotFound(_))
+    ));
+
+    // an edge between authors 1 and 3 makes the graph non-bipartite around them.
+    let (mut graph, _transformer) = build_sample_graph();
+    let edge_type = graph.nodes[&graph.labels_map[&NodeId::from(1)]].edges[0].edge_type;
+    for (source, target) in [(1, 3), (3, 1)] {
+        let target_id = graph.labels_map[&NodeId::from(target)];
+        graph
+            .nodes
+            .get_mut(&graph.labels_map[&NodeId::from(source)])
+            .unwrap()
+            .edges
+            .push(NodeEdge::new(edge_type, target_id));
+    }
+    for label in [1, 4] {
+        assert!(matches!(
+            graph.bipartite_clustering_coefficient(graph.labels_map[&NodeId::from(label)]),
+            Err(CLQError::NotBipartite)
+        ));
+    }
+    Ok(())
+}
+
+/// Test the untyped copy of the sample graph, in which the "published" and "cited"
+/// edges between 1 and 4 become a single edge.
+#[test]
+fn test_to_simple_undirected() -> CLQResult<()> {
+    let (graph, _transformer) = build_sample_graph();
+    let simple = graph.to_simple_undirected()?;
+    assert_eq!(simple.count_nodes(), graph.count_nodes());
+    assert_eq!(simple.count_edges(), 5);
+    for (label, id) in &graph.labels_map {
+        let typed_neighbors: HashSet<u32> =
+            graph.nodes[id].edges.iter().map(|e| e.target_id).collect();
+        assert_eq!(simple.get_node(*label).degree(), typed_neighbors.len());
+    }
+    assert!(simple
+        .get_node(NodeId::from(3))
+        .neighbors
+        .contains(&NodeId::from(6)));
+
+    let (mut graph, _transformer) = build_sample_graph();
+    graph.labels_map.remove(&NodeId::from(6));
+    assert!(matches!(
+        graph.to_simple_undirected(),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    Ok(())
+}
+
+/// Test splitting the sample graph by edge type: only 1 -> 4 is a "cited" edge.
+#[test]
+fn test_edge_type_subgraph() -> CLQResult<()> {
+    let (graph, transformer) = build_sample_graph();
+    let edge_type = |name: &str| -> EdgeTypeId {
+        transformer
+            .edge_types
+            .iter()
+            .position(|x| x == name)
+            .unwrap()
+            .into()
+    };
+    let degree = |subgraph: &TypedGraph, label: i64| {
+        subgraph.nodes[&graph.labels_map[&NodeId::from(label)]]
+            .edges
+            .len()
+    };
+    // edges are stored at both ends.
+    assert_eq!(graph.count_edges(), 12);
+
+    let cited = graph.edge_type_subgraph(edge_type("cited"))?;
+    assert_eq!(cited.count_edges(), 2);
+    assert_eq!(cited.count_nodes(), graph.count_nodes());
+    assert_eq!(cited.core_ids, graph.core_ids);
+    assert_eq!(degree(&cited, 1), 1);
+    assert_eq!(degree(&cited, 4), 1);
+    assert_eq!(degree(&cited, 3), 0);
+    for node in cited.get_nodes_iter() {
+        assert!(node.neighbors_sets.keys().all(|x| *x == edge_type("cited")));
+        assert!(node.edges.iter().all(|e| e.edge_type == edge_type("cited")));
+    }
+
+    let published = graph.edge_type_subgraph(edge_type("published"))?;
+    assert_eq!(published.count_edges(), 10);
+    assert_eq!(degree(&published, 1), 2);
+    assert_eq!(degree(&published, 4), 2);
+    assert!(published
+        .get_nodes_iter()
+        .all(|node| node.neighbors_sets.len() == 1));
+
+    assert!(graph.edge_type_subgraph(EdgeTypeId::from(7_usize)).is_err());
+    Ok(())
+}
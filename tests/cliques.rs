@@ -0,0 +1,113 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::cliques::Cliques;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use std::collections::HashMap;
+use std::time::Instant;
+
+fn is_clique(graph: &SimpleUndirectedGraph, clique: &[NodeId]) -> bool {
+    clique.iter().all(|a| {
+        clique
+            .iter()
+            .all(|b| a == b || graph.get_node(*a).neighbors.contains(b))
+    })
+}
+
+#[test]
+fn test_enumerate_maximal_cliques() -> CLQResult<()> {
+    // two triangles sharing the edge (1, 2), plus a pendant node.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (0, 2),
+        (1, 2),
+        (1, 3),
+        (2, 3),
+        (3, 4),
+    ])?;
+    let mut cliques = graph.enumerate_maximal_cliques();
+    cliques.sort();
+    let expected: Vec<Vec<NodeId>> = vec![vec![0, 1, 2], vec![1, 2, 3], vec![3, 4]]
+        .into_iter()
+        .map(|c| c.into_iter().map(|x| NodeId::from(x as i64)).collect())
+        .collect();
+    assert_eq!(cliques, expected);
+    Ok(())
+}
+
+#[test]
+fn test_maximal_clique_size_distribution() -> CLQResult<()> {
+    // K4 on 0..4, a triangle (3, 4, 5) sharing node 3, and a tail 5-6-7.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (1, 2),
+        (1, 3),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+        (5, 6),
+        (6, 7),
+    ])?;
+    let distribution = graph.maximal_clique_size_distribution();
+    assert_eq!(distribution, HashMap::from([(4, 1), (3, 1), (2, 2)]));
+
+    for p in [0.1, 0.3, 0.6] {
+        let graph = SimpleUndirectedGraphBuilder {}.get_er_graph(30, p)?;
+        let mut expected: HashMap<usize, usize> = HashMap::new();
+        for clique in graph.enumerate_maximal_cliques() {
+            *expected.entry(clique.len()).or_insert(0) += 1;
+        }
+        assert_eq!(graph.maximal_clique_size_distribution(), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_max_clique_bnb() -> CLQResult<()> {
+    let mut graphs = vec![
+        SimpleUndirectedGraphBuilder {}.get_complete_graph(6)?,
+        SimpleUndirectedGraphBuilder {}.get_cycle_graph(7)?,
+        SimpleUndirectedGraphBuilder {}.get_path_graph(5)?,
+    ];
+    for _ in 0..5 {
+        graphs.push(SimpleUndirectedGraphBuilder {}.get_er_graph(40, 0.3)?);
+    }
+    for graph in graphs {
+        let max_clique = graph.max_clique_bnb();
+        assert!(is_clique(&graph, &max_clique));
+        let max_size = graph
+            .enumerate_maximal_cliques()
+            .iter()
+            .map(|c| c.len())
+            .max()
+            .unwrap();
+        assert_eq!(max_clique.len(), max_size);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_max_clique_bnb_timing() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.get_er_graph(300, 0.1)?;
+    let start = Instant::now();
+    let max_clique = graph.max_clique_bnb();
+    assert!(is_clique(&graph, &max_clique));
+    // a generous bound: this takes well under a second.
+    assert!(start.elapsed().as_secs() < 10);
+    Ok(())
+}
@@ -5,10 +5,12 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::triad_census::{TriadCensus, TRIAD_TYPES};
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
 use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
-use lib_dachshund::dachshund::simple_directed_graph::SimpleDirectedGraph;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_directed_graph::{DirectedGraph, SimpleDirectedGraph};
 use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
 use std::collections::HashSet;
 fn get_rows(idx: usize) -> CLQResult<Vec<(usize, usize)>> {
@@ -153,3 +155,85 @@ fn test_build_graph() -> CLQResult<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+#[test]
+fn test_degree_centrality() -> CLQResult<()> {
+    // a star with all edges pointing out from the center.
+    let star = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (0, 2), (0, 3), (0, 4)])?;
+    let in_centrality = star.in_degree_centrality();
+    let out_centrality = star.out_degree_centrality();
+    let center = NodeId::from(0_i64);
+    assert_eq!(out_centrality[&center], 1.0);
+    assert_eq!(in_centrality[&center], 0.0);
+    for i in 1..5 {
+        let leaf = NodeId::from(i as i64);
+        assert_eq!(out_centrality[&leaf], 0.0);
+        assert_eq!(in_centrality[&leaf], 0.25);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_directed_triangle_census() -> CLQResult<()> {
+    let cycle = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 0)])?;
+    let census = cycle.directed_triangle_census();
+    assert_eq!(census.cyclic(), 1);
+    assert_eq!(census.transitive(), 0);
+    assert_eq!(census.counts.iter().sum::<usize>(), 1);
+
+    let transitive = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (0, 2), (1, 2)])?;
+    let census = transitive.directed_triangle_census();
+    assert_eq!(census.transitive(), 1);
+    assert_eq!(census.cyclic(), 0);
+    assert_eq!(census.get("030T"), Some(1));
+    assert_eq!(census.get("030X"), None);
+
+    // one mutual dyad and one asymmetric one, plus a node connected to neither.
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 0), (1, 2), (3, 3)])?;
+    let census = graph.directed_triangle_census();
+    // of the 4 triads, {0, 1, 2} is 111U, {0, 1, 3} is 102, {1, 2, 3} is 012,
+    // and {0, 2, 3} is 003.
+    for (triad_type, count) in [("111U", 1), ("102", 1), ("012", 1), ("003", 1)] {
+        assert_eq!(census.get(triad_type), Some(count));
+    }
+    assert_eq!(census.counts.iter().sum::<usize>(), 4);
+
+    // every triad is counted exactly once.
+    let graph = get_graph(0)?;
+    let n = graph.count_nodes();
+    let census = graph.directed_triangle_census();
+    assert_eq!(
+        census.counts.iter().sum::<usize>(),
+        n * (n - 1) * (n - 2) / 6
+    );
+    assert_eq!(TRIAD_TYPES.len(), census.counts.len());
+    Ok(())
+}
+
+#[test]
+fn test_transitive_closure() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // chain 0 -> 1 -> 2 -> 3 -> 4
+    let chain = SimpleDirectedGraphBuilder {}.from_vector((0..4).map(|i| (i, i + 1)).collect())?;
+    let closure = chain.transitive_closure();
+    assert_eq!(closure.len(), 5);
+    for i in 0..5 {
+        let expected: HashSet<NodeId> = (i + 1..5).map(id).collect();
+        assert_eq!(closure[&id(i)], expected);
+        for j in 0..5 {
+            assert_eq!(chain.reaches(id(i), id(j)), j > i);
+        }
+    }
+    assert!(!chain.reaches(id(0), id(10)));
+
+    // nodes on a cycle reach themselves.
+    let cycle = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 0), (2, 3)])?;
+    let closure = cycle.transitive_closure();
+    assert_eq!(closure[&id(1)], [0, 1, 2, 3].into_iter().map(id).collect());
+    assert!(closure[&id(3)].is_empty());
+    assert!(cycle.reaches(id(2), id(2)));
+    assert!(!cycle.reaches(id(3), id(3)));
+    Ok(())
+}
@@ -4,28 +4,53 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+extern crate fxhash;
 extern crate lib_dachshund;
 
 use crate::lib_dachshund::TransformerBase;
+use fxhash::FxHashSet;
+use lib_dachshund::dachshund::algorithms::betweenness::Betweenness;
+use lib_dachshund::dachshund::algorithms::closeness::Closeness;
 use lib_dachshund::dachshund::algorithms::cnm_communities::CNMCommunities;
 use lib_dachshund::dachshund::algorithms::connected_components::{
     ConnectedComponents, ConnectedComponentsUndirected,
 };
+use lib_dachshund::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
 use lib_dachshund::dachshund::algorithms::coreness::averaged_ties_ranking;
-use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::algorithms::coreness::{Coreness, IncrementalCoreness};
+use lib_dachshund::dachshund::algorithms::graph_diff::{Diff, GraphDiff};
+use lib_dachshund::dachshund::algorithms::independent_set::{ColoringOrder, IndependentSet};
 use lib_dachshund::dachshund::algorithms::k_peaks::KPeaks;
+use lib_dachshund::dachshund::algorithms::laplacian::Laplacian;
+use lib_dachshund::dachshund::algorithms::min_cut::MinCut;
+use lib_dachshund::dachshund::algorithms::modularity::{CommunityReport, Modularity};
+use lib_dachshund::dachshund::algorithms::ranking::top_k_by_value;
+use lib_dachshund::dachshund::algorithms::relabel::RelabelContiguous;
+use lib_dachshund::dachshund::algorithms::rewiring::Rewiring;
+use lib_dachshund::dachshund::algorithms::roles::{StructuralRoles, STRUCTURAL_FEATURE_NAMES};
+use lib_dachshund::dachshund::algorithms::shortest_paths::ShortestPaths;
+use lib_dachshund::dachshund::algorithms::simrank::SimRank;
+use lib_dachshund::dachshund::algorithms::spanning_tree::SpanningTree;
+use lib_dachshund::dachshund::algorithms::statistics::{GraphSummary, Statistics};
+use lib_dachshund::dachshund::algorithms::subgraph::InducedSubgraph;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
 use lib_dachshund::dachshund::id_types::NodeId;
 use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::node::NodeBase;
 use lib_dachshund::dachshund::output::Output;
 use lib_dachshund::dachshund::simple_transformer::{
     GraphStatsTransformerBase, SimpleParallelTransformer, SimpleTransformer,
 };
-use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
-use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph::{GraphMetadata, SimpleUndirectedGraph};
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, StreamingGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::temporal_graph::TemporalGraph;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::iter::FromIterator;
+use std::time::{Duration, Instant};
 
 fn get_graph(idx: usize) -> CLQResult<SimpleUndirectedGraph> {
     let v = match idx {
@@ -280,16 +305,25 @@ fn test_truss_graph() {
         2
     );
 
-    assert_eq!(get_graph(1).unwrap().get_k_trusses(3).0.len(), 1);
-    assert_eq!(get_graph(2).unwrap().get_k_trusses(3).0.len(), 1);
-    assert_eq!(get_graph(3).unwrap().get_k_trusses(3).0.len(), 2);
-    assert_eq!(get_graph(4).unwrap().get_k_trusses(3).0.len(), 2);
+    assert_eq!(get_graph(1).unwrap().get_k_trusses(3).unwrap().0.len(), 1);
+    assert_eq!(get_graph(2).unwrap().get_k_trusses(3).unwrap().0.len(), 1);
+    assert_eq!(get_graph(3).unwrap().get_k_trusses(3).unwrap().0.len(), 2);
+    assert_eq!(get_graph(4).unwrap().get_k_trusses(3).unwrap().0.len(), 2);
 
-    assert_eq!(get_graph(1).unwrap().get_k_trusses(3).0[0].len(), 3);
-    assert_eq!(get_graph(2).unwrap().get_k_trusses(3).0[0].len(), 5);
-    assert_eq!(get_graph(5).unwrap().get_k_trusses(3).0[0].len(), 3);
+    assert_eq!(
+        get_graph(1).unwrap().get_k_trusses(3).unwrap().0[0].len(),
+        3
+    );
+    assert_eq!(
+        get_graph(2).unwrap().get_k_trusses(3).unwrap().0[0].len(),
+        5
+    );
+    assert_eq!(
+        get_graph(5).unwrap().get_k_trusses(3).unwrap().0[0].len(),
+        3
+    );
 
-    let (truss, truss_nodes) = get_graph(0).unwrap().get_k_trusses(3);
+    let (truss, truss_nodes) = get_graph(0).unwrap().get_k_trusses(3).unwrap();
     assert_eq!(truss.len(), 2);
     assert!(truss_nodes.contains(&BTreeSet::from_iter(
         vec![0, 1, 9].into_iter().map(|x| NodeId::from(x as i64))
@@ -298,7 +332,7 @@ fn test_truss_graph() {
         vec![8, 10, 16].into_iter().map(|x| NodeId::from(x as i64))
     )));
 
-    let (truss2, truss_nodes2) = get_graph(6).unwrap().get_k_trusses(4);
+    let (truss2, truss_nodes2) = get_graph(6).unwrap().get_k_trusses(4).unwrap();
     assert_eq!(truss2.len(), 2);
     assert!(truss_nodes2.contains(&BTreeSet::from_iter(
         vec![3, 8, 9, 18]
@@ -343,6 +377,91 @@ fn test_coreness() {
     }
 }
 
+#[cfg(test)]
+#[test]
+fn test_incremental_coreness() -> CLQResult<()> {
+    for target in [
+        get_graph(7)?,
+        SimpleUndirectedGraphBuilder {}.get_complete_graph(6)?,
+    ] {
+        let mut edges: Vec<(NodeId, NodeId)> = Vec::new();
+        for node in target.get_nodes_iter() {
+            for neighbor_id in &node.neighbors {
+                if node.node_id < *neighbor_id {
+                    edges.push((node.node_id, *neighbor_id));
+                }
+            }
+        }
+        edges.sort();
+
+        // start from the same nodes with no edges, and add edges one at a time.
+        let mut graph = SimpleUndirectedGraph::new(
+            target.ids.clone(),
+            SimpleUndirectedGraphBuilder::get_nodes(
+                target
+                    .get_ids_iter()
+                    .map(|id| (*id, BTreeSet::new()))
+                    .collect(),
+            ),
+            HashMap::new(),
+        );
+        let mut coreness = graph.get_coreness_values();
+        for (a, b) in edges {
+            graph.insert_edge_update_coreness(a, b, &mut coreness)?;
+            assert_eq!(coreness, graph.get_coreness_values());
+        }
+        assert_eq!(coreness, target.get_coreness_values());
+
+        // edges can't be inserted twice.
+        let (a, b) = (
+            target.ids[0],
+            *target
+                .get_node(target.ids[0])
+                .neighbors
+                .iter()
+                .next()
+                .unwrap(),
+        );
+        assert!(graph
+            .insert_edge_update_coreness(a, b, &mut coreness)
+            .is_err());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_decremental_coreness() -> CLQResult<()> {
+    for mut graph in [
+        get_graph(7)?,
+        SimpleUndirectedGraphBuilder {}.get_complete_graph(6)?,
+    ] {
+        let mut edges: Vec<(NodeId, NodeId)> = Vec::new();
+        for node in graph.get_nodes_iter() {
+            for neighbor_id in &node.neighbors {
+                if node.node_id < *neighbor_id {
+                    edges.push((node.node_id, *neighbor_id));
+                }
+            }
+        }
+        edges.sort();
+
+        let mut coreness = graph.get_coreness_values();
+        for (a, b) in &edges {
+            graph.remove_edge_update_coreness(*a, *b, &mut coreness)?;
+            assert_eq!(coreness, graph.get_coreness_values());
+        }
+        assert!(coreness.values().all(|c| *c == 0));
+
+        // edges can't be removed twice.
+        let (a, b) = edges[0];
+        assert!(graph
+            .remove_edge_update_coreness(a, b, &mut coreness)
+            .is_err());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[test]
 fn test_averaged_ties_ranking() {
@@ -360,6 +479,25 @@ fn test_averaged_ties_ranking() {
     }
 }
 
+#[cfg(test)]
+#[test]
+fn test_top_k_by_value() {
+    let values = vec![(5, 0.5), (1, 0.25), (4, 0.5), (3, 1.0), (2, 0.5), (6, 0.0)];
+    let value_map: HashMap<NodeId, f64> = values
+        .into_iter()
+        .map(|(node, val)| (NodeId::from(node as i64), val))
+        .collect();
+    let expected: Vec<(NodeId, f64)> = vec![(3, 1.0), (2, 0.5), (4, 0.5), (5, 0.5)]
+        .into_iter()
+        .map(|(node, val)| (NodeId::from(node as i64), val))
+        .collect();
+    // tied nodes come out in increasing order of id, and a tie can be cut off.
+    assert_eq!(top_k_by_value(&value_map, 4), expected);
+    assert_eq!(top_k_by_value(&value_map, 3), expected[..3]);
+    assert_eq!(top_k_by_value(&value_map, 100).len(), 6);
+    assert!(top_k_by_value(&value_map, 0).is_empty());
+}
+
 #[test]
 fn test_simple_transformer() {
     let mut transformer = SimpleTransformer::new();
@@ -528,3 +666,1701 @@ fn test_k_peaks() {
         true
     );
 }
+
+#[test]
+fn test_double_edge_swap() {
+    let mut g = get_graph(0).unwrap();
+    let degrees: HashMap<NodeId, usize> =
+        g.nodes.values().map(|x| (x.node_id, x.degree())).collect();
+    let num_edges = g.count_edges();
+
+    let num_swaps = g.double_edge_swap(100, 42).unwrap();
+    assert!(num_swaps > 0);
+    assert_eq!(g.count_edges(), num_edges);
+    for node in g.nodes.values() {
+        assert_eq!(node.degree(), degrees[&node.node_id]);
+        // no self-loops were introduced.
+        assert!(!node.neighbors.contains(&node.node_id));
+    }
+
+    // the same seed reproduces the same rewiring.
+    let mut g2 = get_graph(0).unwrap();
+    assert_eq!(g2.double_edge_swap(100, 42).unwrap(), num_swaps);
+    for node in g.nodes.values() {
+        assert_eq!(node.neighbors, g2.nodes[&node.node_id].neighbors);
+    }
+
+    // a single edge can't be swapped.
+    let mut single_edge = SimpleUndirectedGraphBuilder {}
+        .from_vector(vec![(0, 1)])
+        .unwrap();
+    assert!(matches!(
+        single_edge.double_edge_swap(10, 0),
+        Err(CLQError::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn test_for_each_component() {
+    // A triangle plus a path on 4 nodes.
+    let g = SimpleUndirectedGraphBuilder {}
+        .from_vector(vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 6)])
+        .unwrap();
+    let diameters = g.for_each_component(|component| {
+        component
+            .get_ids_iter()
+            .map(|id| {
+                let (dist, _parents) = component.get_shortest_paths(*id, &None);
+                dist.values().map(|d| d.unwrap()).max().unwrap()
+            })
+            .max()
+            .unwrap()
+    });
+    assert_eq!(diameters, vec![1, 3]);
+
+    let sizes = g.for_each_component(|component| component.count_nodes());
+    assert_eq!(sizes, vec![3, 4]);
+}
+
+#[test]
+fn test_dimacs() {
+    let dimacs = "c a small test graph\n\
+                  c with an isolated node\n\
+                  p edge 5 4\n\
+                  e 1 2\n\
+                  e 2 3\n\
+                  e 3 1\n\
+                  e 3 4\n";
+    let g = SimpleUndirectedGraphBuilder::from_dimacs(dimacs.as_bytes()).unwrap();
+    assert_eq!(g.count_nodes(), 5);
+    assert_eq!(g.count_edges(), 4);
+    assert_eq!(g.get_node_degree(NodeId::from(3_i64)), 3);
+    assert_eq!(g.get_node_degree(NodeId::from(5_i64)), 0);
+    assert_eq!(
+        g.to_dimacs(),
+        "p edge 5 4\ne 1 2\ne 1 3\ne 2 3\ne 3 4".to_string()
+    );
+
+    // round trip
+    let g = get_graph(0).unwrap();
+    let round_tripped =
+        SimpleUndirectedGraphBuilder::from_dimacs(g.to_dimacs().as_bytes()).unwrap();
+    assert_eq!(round_tripped.count_nodes(), g.count_nodes());
+    assert_eq!(round_tripped.count_edges(), g.count_edges());
+    assert_eq!(round_tripped.to_dimacs(), g.to_dimacs());
+
+    assert!(SimpleUndirectedGraphBuilder::from_dimacs("e 1 2\n".as_bytes()).is_err());
+    assert!(SimpleUndirectedGraphBuilder::from_dimacs("p edge 2 1\ne 1 3\n".as_bytes()).is_err());
+    // the header must be an "edge" one, and its edge count must match the file.
+    assert!(SimpleUndirectedGraphBuilder::from_dimacs("p col 2 1\ne 1 2\n".as_bytes()).is_err());
+    assert!(SimpleUndirectedGraphBuilder::from_dimacs("p edge 3 2\ne 1 2\n".as_bytes()).is_err());
+    assert!(
+        SimpleUndirectedGraphBuilder::from_dimacs("p edge 3 1\ne 1 2\ne 2 3\n".as_bytes()).is_err()
+    );
+    assert!(SimpleUndirectedGraphBuilder::from_dimacs("p edge 3 x\ne 1 2\n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_matrix_market() {
+    let mtx = "%%MatrixMarket matrix coordinate pattern symmetric\n\
+               % lower triangle of a 5-node graph: a triangle, a pendant and an isolated node\n\
+               5 5 5\n\
+               2 1\n\
+               3 1\n\
+               3 2\n\
+               4 3\n\
+               4 4\n";
+    let g = SimpleUndirectedGraphBuilder::from_matrix_market(mtx.as_bytes(), true).unwrap();
+    assert_eq!(g.count_nodes(), 5);
+    // the diagonal entry is not an edge.
+    assert_eq!(g.count_edges(), 4);
+    assert_eq!(g.get_node_degree(NodeId::from(3_i64)), 3);
+    assert_eq!(g.get_node_degree(NodeId::from(5_i64)), 0);
+
+    // upper-triangle entries aren't allowed in a symmetric file...
+    let general = "%%MatrixMarket matrix coordinate pattern general\n3 3 2\n1 2\n2 1\n";
+    assert!(SimpleUndirectedGraphBuilder::from_matrix_market(general.as_bytes(), true).is_err());
+    // ... but both directions of an edge collapse into a single undirected edge otherwise.
+    let g = SimpleUndirectedGraphBuilder::from_matrix_market(general.as_bytes(), false).unwrap();
+    assert_eq!(g.count_nodes(), 3);
+    assert_eq!(g.count_edges(), 1);
+
+    let wrong_count = "%%MatrixMarket matrix coordinate pattern general\n3 3 3\n1 2\n";
+    assert!(
+        SimpleUndirectedGraphBuilder::from_matrix_market(wrong_count.as_bytes(), false).is_err()
+    );
+}
+
+#[test]
+fn test_summary() -> CLQResult<()> {
+    // a triangle with a pendant node, plus a separate edge.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (4, 5),
+    ])?;
+    let summary = graph.summary();
+    assert_eq!(
+        summary,
+        GraphSummary {
+            num_nodes: 6,
+            num_edges: 5,
+            density: 5.0 / 15.0,
+            min_degree: 1,
+            mean_degree: 10.0 / 6.0,
+            max_degree: 3,
+            num_connected_components: 2,
+            largest_component_size: 4,
+            // 3 triangle corners out of 5 connected triples.
+            global_clustering: 0.6,
+            degeneracy: 2,
+        }
+    );
+    let printed = format!("{}", summary);
+    for expected in [
+        "nodes: 6",
+        "edges: 5",
+        "density: 0.3333",
+        "1 / 1.6667 / 3",
+        "connected components: 2",
+        "largest component size: 4",
+        "clustering coefficient: 0.6000",
+        "degeneracy: 2",
+    ] {
+        assert!(
+            printed.contains(expected),
+            "{} not in {}",
+            expected,
+            printed
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_karger_min_cut() -> CLQResult<()> {
+    // two copies of K5 (nodes 0..5 and 5..10), joined by the single edge (4, 5).
+    let mut edges: Vec<(i64, i64)> = vec![(4, 5)];
+    for offset in [0, 5] {
+        for i in 0..5 {
+            for j in i + 1..5 {
+                edges.push((offset + i, offset + j));
+            }
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let (cut_size, side) = graph.karger_min_cut(50, 0);
+    assert_eq!(cut_size, 1);
+    let side_values: Vec<i64> = side.iter().map(|x| x.value()).collect();
+    assert!(side_values == vec![0, 1, 2, 3, 4] || side_values == vec![5, 6, 7, 8, 9]);
+
+    // a cycle can't be cut with fewer than 2 edges.
+    let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(8)?;
+    assert_eq!(cycle.karger_min_cut(10, 0).0, 2);
+    Ok(())
+}
+
+#[test]
+fn test_graph_power() -> CLQResult<()> {
+    // path 0 - 1 - 2 - 3 - 4 - 5
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(5)?;
+    let square = path.graph_power(2)?;
+    assert_eq!(square.count_nodes(), 6);
+    for i in 0..6_i64 {
+        for j in 0..6_i64 {
+            assert_eq!(
+                square.nodes[&NodeId::from(i)]
+                    .neighbors
+                    .contains(&NodeId::from(j)),
+                i != j && (i - j).abs() <= 2
+            );
+        }
+    }
+    // 5 edges between neighbors, plus 4 between nodes 2 hops apart.
+    assert_eq!(square.count_edges(), 9);
+
+    let same = path.graph_power(1)?;
+    for (id, node) in &path.nodes {
+        assert_eq!(node.neighbors, same.nodes[id].neighbors);
+    }
+    // the diameter of the path is 5, so its 5th power is complete.
+    assert_eq!(path.graph_power(5)?.count_edges(), 15);
+    assert!(path.graph_power(0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_neighborhood_function() -> CLQResult<()> {
+    // path 0 - 1 - 2 - 3 - 4, with diameter 4: there are 2 * (5 - h) ordered pairs
+    // exactly h hops apart.
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(4)?;
+    assert_eq!(
+        path.neighborhood_function(6),
+        vec![0, 8, 14, 18, 20, 20, 20]
+    );
+    assert_eq!(path.neighborhood_function(0), vec![0]);
+    Ok(())
+}
+
+#[test]
+fn test_uniform_spanning_tree() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.get_complete_graph(8)?;
+    let tree_edges = graph.uniform_spanning_tree(42)?;
+    assert_eq!(tree_edges.len(), 7);
+    for (a, b) in &tree_edges {
+        assert!(graph.nodes[a].neighbors.contains(b));
+    }
+    // n - 1 edges connecting all n nodes make a tree, so there's no cycle either.
+    let tree = SimpleUndirectedGraphBuilder {}.from_vector(
+        tree_edges
+            .iter()
+            .map(|(a, b)| (a.value(), b.value()))
+            .collect(),
+    )?;
+    assert_eq!(tree.count_nodes(), 8);
+    assert_eq!(tree.count_edges(), 7);
+    assert_eq!(tree.get_connected_components().len(), 1);
+
+    assert_eq!(graph.uniform_spanning_tree(42)?, tree_edges);
+    // K8 has 8^6 spanning trees, so other seeds should find some of the others.
+    assert!((0..10).any(|seed| graph.uniform_spanning_tree(seed).unwrap() != tree_edges));
+
+    assert!(get_graph(3)?.uniform_spanning_tree(0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_flag_coreness_anomalies() -> CLQResult<()> {
+    // K6 on nodes 1..=6, plus a hub (100) attached to node 1 and to 20 leaves. The
+    // hub has the highest degree, but only coreness 1.
+    let mut edges: Vec<(i64, i64)> = vec![(1, 100)];
+    for i in 1..=6 {
+        for j in i + 1..=6 {
+            edges.push((i, j));
+        }
+    }
+    for leaf in 200..220 {
+        edges.push((100, leaf));
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let coreness = graph.get_coreness_values();
+    let hub = NodeId::from(100_i64);
+
+    assert_eq!(graph.flag_coreness_anomalies(&coreness, 1.0), vec![hub]);
+    let top = graph.top_anomalies(&coreness, 3);
+    assert_eq!(top.len(), 3);
+    assert_eq!(top[0].0, hub);
+    // the hub ranks first by degree, and 17th (tied for 7th-27th) by coreness.
+    assert!((top[0].1 - 17.0_f64.ln()).abs() <= 1e-12);
+    assert!(top[1].1 < 1.0);
+    Ok(())
+}
+
+#[test]
+fn test_diff() -> CLQResult<()> {
+    let before = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 3)])?;
+    // (1, 2) was removed, and (3, 4) added along with the new node 4.
+    let after = SimpleUndirectedGraphBuilder {}.from_vector(vec![(1, 0), (3, 2), (4, 3)])?;
+    let id = |x: i64| NodeId::from(x);
+    assert_eq!(
+        before.diff(&after),
+        GraphDiff {
+            added_edges: vec![(id(3), id(4))],
+            removed_edges: vec![(id(1), id(2))],
+            added_nodes: vec![id(4)],
+            removed_nodes: vec![],
+        }
+    );
+    assert_eq!(before.diff(&before), GraphDiff::default());
+    Ok(())
+}
+
+#[test]
+fn test_vertex_and_edge_connectivity() -> CLQResult<()> {
+    let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(6)?;
+    assert_eq!(cycle.vertex_connectivity(), 2);
+    assert_eq!(cycle.edge_connectivity(), 2);
+
+    let complete = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    assert_eq!(complete.vertex_connectivity(), 4);
+    assert_eq!(complete.edge_connectivity(), 4);
+
+    let tree = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (0, 2), (1, 3), (1, 4)])?;
+    assert_eq!(tree.vertex_connectivity(), 1);
+    assert_eq!(tree.edge_connectivity(), 1);
+
+    // two triangles sharing node 2: one node, but two edges, disconnect it.
+    let bowtie = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (3, 4),
+        (4, 2),
+    ])?;
+    assert_eq!(bowtie.vertex_connectivity(), 1);
+    assert_eq!(bowtie.edge_connectivity(), 2);
+
+    // two disjoint triangles.
+    assert_eq!(get_graph(3)?.vertex_connectivity(), 0);
+    assert_eq!(get_graph(3)?.edge_connectivity(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_simrank() -> CLQResult<()> {
+    // star with center 0: leaves only ever meet through the center.
+    let star = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (0, 2), (0, 3)])?;
+    let scores = star.simrank(0.8, 100, 1e-9);
+    assert_eq!(scores.len(), 16);
+    assert_eq!(scores[&(NodeId::from(2), NodeId::from(2))], 1.0);
+    assert!((scores[&(NodeId::from(1), NodeId::from(2))] - 0.8).abs() < 1e-9);
+    assert_eq!(
+        scores[&(NodeId::from(1), NodeId::from(2))],
+        scores[&(NodeId::from(2), NodeId::from(1))]
+    );
+    assert_eq!(scores[&(NodeId::from(0), NodeId::from(1))], 0.0);
+    // symmetric leaves become (nearly) indistinguishable as decay approaches 1.
+    let scores = star.simrank(0.999, 100, 1e-9);
+    assert!(scores[&(NodeId::from(1), NodeId::from(3))] > 0.99);
+
+    // opposite nodes of a square share both neighbors: s = decay / (2 - decay).
+    let square = SimpleUndirectedGraphBuilder {}.get_cycle_graph(4)?;
+    let scores = square.simrank(0.8, 200, 1e-12);
+    let ids = square.get_ordered_node_ids();
+    let expected = 0.8 / 1.2;
+    assert!((scores[&(ids[0], ids[2])] - expected).abs() < 1e-9);
+    assert!((scores[&(ids[1], ids[3])] - expected).abs() < 1e-9);
+    assert!((square.simrank_pair(ids[2], ids[0], 0.8, 200)? - expected).abs() < 1e-9);
+
+    // the single pair computation agrees with the full one after as many iterations.
+    let graph = get_graph(0)?;
+    let scores = graph.simrank(0.6, 5, 0.0);
+    let ids = graph.get_ordered_node_ids();
+    for a in &ids {
+        for b in &ids {
+            let pair = graph.simrank_pair(*a, *b, 0.6, 5)?;
+            assert!((scores[&(*a, *b)] - pair).abs() < 1e-12);
+        }
+    }
+    assert!(graph
+        .simrank_pair(ids[0], NodeId::from(1000), 0.6, 5)
+        .is_err());
+
+    // no iterations: 1.0 for a node with itself, 0.0 for any other pair.
+    let path = SimpleUndirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 3)])?;
+    assert_eq!(
+        path.simrank_pair(NodeId::from(1), NodeId::from(3), 0.8, 0)?,
+        0.0
+    );
+    assert_eq!(
+        path.simrank_pair(NodeId::from(2), NodeId::from(2), 0.8, 0)?,
+        1.0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_resistance_distance() -> CLQResult<()> {
+    // unit resistors: a square 0-1-2-3, with 4 hanging off 2.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (2, 4),
+    ])?;
+    let id = NodeId::from;
+    // across the square, two 2 ohm paths in parallel.
+    assert!((graph.resistance_distance(id(0), id(2))? - 1.0).abs() < 1e-9);
+    // along a side, 1 ohm in parallel with 3 ohms.
+    assert!((graph.resistance_distance(id(0), id(1))? - 0.75).abs() < 1e-9);
+    // the pendant edge is in series.
+    assert!((graph.resistance_distance(id(4), id(0))? - 2.0).abs() < 1e-9);
+    assert!(graph.resistance_distance(id(3), id(3))?.abs() < 1e-9);
+    // a path is all in series.
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(4)?;
+    assert!((path.resistance_distance(id(0), id(4))? - 4.0).abs() < 1e-9);
+
+    assert!(graph.resistance_distance(id(0), id(10)).is_err());
+    // two disjoint triangles.
+    assert!(get_graph(3)?.resistance_distance(id(0), id(1)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_kirchhoff_index() -> CLQResult<()> {
+    for n in 2..8 {
+        // K_n: each of the n (n - 1) / 2 pairs is 2 / n ohms apart.
+        let complete = SimpleUndirectedGraphBuilder {}.get_complete_graph(n)?;
+        let expected = (n - 1) as f64;
+        assert!((complete.kirchhoff_index()? - expected).abs() < 1e-9);
+    }
+    for n in 3..10 {
+        // C_n: (n^3 - n) / 12.
+        let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(n)?;
+        let expected = (n.pow(3) - n) as f64 / 12.0;
+        assert!((cycle.kirchhoff_index()? - expected).abs() < 1e-9);
+    }
+    // agrees with summing the resistance distances.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (2, 4),
+    ])?;
+    let node_ids = graph.get_ordered_node_ids();
+    let mut total = 0.0;
+    for (i, a) in node_ids.iter().enumerate() {
+        for b in &node_ids[i + 1..] {
+            total += graph.resistance_distance(*a, *b)?;
+        }
+    }
+    assert!((graph.kirchhoff_index()? - total).abs() < 1e-9);
+    // two disjoint triangles.
+    assert!(matches!(
+        get_graph(3)?.kirchhoff_index(),
+        Err(CLQError::NotConnected)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_current_flow_betweenness() -> CLQResult<()> {
+    let id = NodeId::from;
+    // on a tree, all the current follows the only path between each pair.
+    let tree = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (1, 3),
+        (3, 4),
+        (3, 5),
+    ])?;
+    let current_flow = tree.current_flow_betweenness();
+    let shortest_path = tree.get_node_betweenness_brandes()?;
+    for node_id in tree.get_ids_iter() {
+        assert!((current_flow[node_id] - shortest_path[node_id]).abs() < 1e-9);
+    }
+
+    // 5 and 6 hang off either end of two parallel routes: 0-1-2 and 0-3-4-2.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (0, 3),
+        (3, 4),
+        (4, 2),
+        (5, 0),
+        (6, 2),
+    ])?;
+    let current_flow = graph.current_flow_betweenness();
+    let shortest_path = graph.get_node_betweenness_brandes()?;
+    assert!(current_flow[&id(5)].abs() < 1e-9);
+    assert!(current_flow[&id(1)] > current_flow[&id(3)]);
+    assert!((current_flow[&id(3)] - current_flow[&id(4)]).abs() < 1e-9);
+    // the longer route carries a bigger share of the current than of shortest paths.
+    assert!(
+        current_flow[&id(3)] / current_flow[&id(1)] > shortest_path[&id(3)] / shortest_path[&id(1)]
+    );
+
+    // two disjoint triangles: in each, a third of the current between two corners
+    // goes through the third one, and pairs across triangles don't exchange any.
+    let current_flow = get_graph(3)?.current_flow_betweenness();
+    for node_id in 0..6 {
+        assert!((current_flow[&id(node_id)] - 1.0 / 3.0).abs() < 1e-9);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_landmark_closeness() -> CLQResult<()> {
+    for graph in [get_graph(0)?, get_graph(3)?, get_graph(6)?] {
+        let exact = graph.closeness_centrality();
+        let estimate = graph.landmark_closeness(graph.count_nodes(), 1);
+        for id in graph.get_ids_iter() {
+            assert!((exact[id] - estimate[id]).abs() < 1e-12);
+        }
+    }
+
+    // hubs 0 and 100 with 10 leaves each, joined by an edge.
+    let mut rows: Vec<(i64, i64)> = vec![(0, 100)];
+    for i in 1..=10 {
+        rows.push((0, i));
+        rows.push((100, 100 + i));
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(rows)?;
+    let exact = graph.closeness_centrality();
+    let hubs: HashSet<NodeId> = [NodeId::from(0), NodeId::from(100)].into_iter().collect();
+    for (id, _) in top_k_by_value(&exact, 2) {
+        assert!(hubs.contains(&id));
+    }
+    for seed in 0..5 {
+        let estimate = graph.landmark_closeness(6, seed);
+        assert_eq!(estimate.len(), graph.count_nodes());
+        for (id, _) in top_k_by_value(&estimate, 2) {
+            assert!(hubs.contains(&id));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_split_by_community() -> CLQResult<()> {
+    // two triangles joined by (0, 3); node 5 is left out of the communities.
+    let graph = get_graph(4)?;
+    let communities: HashMap<NodeId, usize> = vec![(0, 7), (1, 7), (2, 7), (3, 9), (4, 9)]
+        .into_iter()
+        .map(|(id, community)| (NodeId::from(id), community))
+        .collect();
+    let subgraphs = graph.split_by_community(&communities)?;
+    assert_eq!(subgraphs.len(), 2);
+
+    let first = &subgraphs[&7];
+    assert_eq!(
+        first.get_ordered_node_ids(),
+        vec![NodeId::from(0), NodeId::from(1), NodeId::from(2)]
+    );
+    assert_eq!(first.count_edges(), 3);
+    let second = &subgraphs[&9];
+    assert_eq!(
+        second.get_ordered_node_ids(),
+        vec![NodeId::from(3), NodeId::from(4)]
+    );
+    assert_eq!(second.count_edges(), 1);
+    for (community, subgraph) in &subgraphs {
+        for node in subgraph.get_nodes_iter() {
+            assert_eq!(communities[&node.get_id()], *community);
+            for neighbor_id in &node.neighbors {
+                assert_eq!(communities[neighbor_id], *community);
+                assert!(graph
+                    .get_node(node.get_id())
+                    .neighbors
+                    .contains(neighbor_id));
+            }
+        }
+    }
+
+    let mut unknown = communities.clone();
+    unknown.insert(NodeId::from(42), 7);
+    assert!(graph.split_by_community(&unknown).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_maximal_independent_set() -> CLQResult<()> {
+    // nodes 0 to 6.
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(6)?;
+    let id = NodeId::from;
+    let mut natural = path.maximal_independent_set(ColoringOrder::Natural);
+    natural.sort();
+    assert_eq!(natural, vec![id(0), id(2), id(4), id(6)]);
+    // picking the middle nodes first leaves no room for the endpoints.
+    let mut largest_first = path.maximal_independent_set(ColoringOrder::LargestFirst);
+    largest_first.sort();
+    assert_eq!(largest_first, vec![id(1), id(3), id(5)]);
+    assert_eq!(path.independence_number_lower_bound(), 4);
+
+    for graph in [path, get_graph(0)?, get_graph(6)?] {
+        for ordering in [
+            ColoringOrder::Natural,
+            ColoringOrder::LargestFirst,
+            ColoringOrder::SmallestFirst,
+            ColoringOrder::Random(3),
+        ] {
+            let selected: HashSet<NodeId> = graph
+                .maximal_independent_set(ordering)
+                .into_iter()
+                .collect();
+            for node in graph.get_nodes_iter() {
+                let num_selected_neighbors = node
+                    .neighbors
+                    .iter()
+                    .filter(|x| selected.contains(x))
+                    .count();
+                if selected.contains(&node.get_id()) {
+                    // independent: no two selected nodes are adjacent.
+                    assert_eq!(num_selected_neighbors, 0);
+                } else {
+                    // maximal: every other node has a selected neighbor.
+                    assert!(num_selected_neighbors > 0);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_approximate_vertex_cover() -> CLQResult<()> {
+    let covers_every_edge = |graph: &SimpleUndirectedGraph, cover: &HashSet<NodeId>| {
+        graph.get_nodes_iter().all(|node| {
+            cover.contains(&node.get_id()) || node.neighbors.iter().all(|x| cover.contains(x))
+        })
+    };
+    // nodes 0 to 6, where {1, 3, 5} is a minimum cover.
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(6)?;
+    let cover = path.approximate_vertex_cover();
+    assert!(covers_every_edge(&path, &cover));
+    assert!(cover.len() <= 2 * 3);
+    assert_eq!(cover.len() % 2, 0);
+
+    for graph in [get_graph(0)?, get_graph(4)?, get_graph(6)?] {
+        assert!(covers_every_edge(&graph, &graph.approximate_vertex_cover()));
+    }
+    // a triangle needs two of its nodes, and gets them.
+    let triangle = SimpleUndirectedGraphBuilder {}.get_complete_graph(3)?;
+    assert_eq!(triangle.approximate_vertex_cover().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_degree_entropy_and_gini() -> CLQResult<()> {
+    let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(8)?;
+    assert_eq!(cycle.degree_entropy(), 0.0);
+    assert_eq!(cycle.degree_gini(), 0.0);
+    let complete = SimpleUndirectedGraphBuilder {}.get_complete_graph(6)?;
+    assert_eq!(complete.degree_entropy(), 0.0);
+    assert!(complete.degree_gini().abs() < 1e-12);
+
+    // a hub with 9 leaves: degrees are 1 (90% of nodes) and 9 (10%).
+    let star = SimpleUndirectedGraphBuilder {}.from_vector((1..=9).map(|x| (0, x)).collect())?;
+    let expected_entropy = -(0.9_f64 * 0.9_f64.ln() + 0.1 * 0.1_f64.ln());
+    assert!((star.degree_entropy() - expected_entropy).abs() < 1e-12);
+    // mean absolute difference: 2 * 9 pairs (out of 100) differ by 8, over twice
+    // the mean degree of 1.8.
+    assert!((star.degree_gini() - 0.4).abs() < 1e-12);
+    assert!(star.degree_gini() > get_graph(0)?.degree_gini());
+    Ok(())
+}
+
+#[test]
+fn test_are_connected() -> CLQResult<()> {
+    let id = NodeId::from;
+    // two disjoint triangles.
+    let graph = get_graph(3)?;
+    assert!(graph.are_connected(id(0), id(2)));
+    assert!(graph.are_connected(id(5), id(3)));
+    assert!(graph.are_connected(id(4), id(4)));
+    assert!(!graph.are_connected(id(0), id(3)));
+    assert!(!graph.are_connected(id(0), id(42)));
+    let graph = get_graph(0)?;
+    let components = graph.get_connected_components();
+    for a in graph.get_ids_iter() {
+        for b in graph.get_ids_iter() {
+            let same_component = components.iter().any(|c| c.contains(a) && c.contains(b));
+            assert_eq!(graph.are_connected(*a, *b), same_component);
+        }
+    }
+
+    // nearby nodes on a long path are found without walking the whole path.
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(100_000)?;
+    let (connected, num_visited) = path._bidirectional_search(id(50_000), id(50_006));
+    assert!(connected);
+    assert!(num_visited <= 16);
+    // a small component runs out of nodes to visit first.
+    let mut rows: Vec<(i64, i64)> = (0..100_000).map(|x| (x, x + 1)).collect();
+    rows.extend(vec![(200_000, 200_001), (200_001, 200_002)]);
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(rows)?;
+    let (connected, num_visited) = graph._bidirectional_search(id(50_000), id(200_000));
+    assert!(!connected);
+    assert!(num_visited <= 16);
+    Ok(())
+}
+
+#[test]
+fn test_connected_components_excluding() -> CLQResult<()> {
+    let id = NodeId::from;
+    let sorted = |mut components: Vec<Vec<NodeId>>| {
+        for c in components.iter_mut() {
+            c.sort();
+        }
+        components.sort();
+        components
+    };
+    // two triangles sharing the cut vertex 2.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (3, 4),
+        (4, 2),
+    ])?;
+    assert_eq!(graph.connected_components_excluding(None, None).len(), 1);
+
+    // dropping the edges from 2 into the second triangle splits it off, and 2 stays.
+    let edges: HashSet<(NodeId, NodeId)> =
+        vec![(id(2), id(3)), (id(4), id(2))].into_iter().collect();
+    assert_eq!(
+        sorted(graph.connected_components_excluding(None, Some(&edges))),
+        vec![vec![id(0), id(1), id(2)], vec![id(3), id(4)]]
+    );
+    // dropping the cut vertex itself leaves two components without it.
+    let nodes: FxHashSet<NodeId> = vec![id(2)].into_iter().collect();
+    assert_eq!(
+        sorted(graph.connected_components_excluding(Some(&nodes), None)),
+        vec![vec![id(0), id(1)], vec![id(3), id(4)]]
+    );
+    // the graph itself is untouched.
+    assert_eq!(graph.get_connected_components().len(), 1);
+    assert_eq!(graph.count_edges(), 6);
+    Ok(())
+}
+
+#[test]
+fn test_nodes_by_degree() -> CLQResult<()> {
+    // a triangle with a tail: 2 has degree 3, 0 and 1 degree 2, and 3 degree 1.
+    let graph = get_graph(5)?;
+    let ids = |v: Vec<i64>| -> Vec<NodeId> { v.into_iter().map(NodeId::from).collect() };
+    assert_eq!(graph.nodes_by_degree(true), ids(vec![3, 0, 1, 2]));
+    assert_eq!(graph.nodes_by_degree(false), ids(vec![2, 0, 1, 3]));
+
+    // all ties, so the id decides both ways.
+    let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(5)?;
+    assert_eq!(cycle.nodes_by_degree(true), cycle.get_ordered_node_ids());
+    assert_eq!(cycle.nodes_by_degree(false), cycle.get_ordered_node_ids());
+
+    let graph = get_graph(0)?;
+    let ascending = graph.nodes_by_degree(true);
+    assert_eq!(ascending.len(), graph.count_nodes());
+    for pair in ascending.windows(2) {
+        let (a, b) = (graph.get_node(pair[0]), graph.get_node(pair[1]));
+        assert!((a.degree(), pair[0]) < (b.degree(), pair[1]));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_attribute_assortativity() -> CLQResult<()> {
+    let categories = |v: Vec<(i64, usize)>| -> HashMap<NodeId, usize> {
+        v.into_iter().map(|(id, c)| (NodeId::from(id), c)).collect()
+    };
+    // two triangles joined by (0, 3), one per category: 12 of the 14 edge ends
+    // join same-category nodes, and both categories have half of the ends.
+    let graph = get_graph(4)?;
+    let attr = categories(vec![(0, 0), (1, 0), (2, 0), (3, 1), (4, 1), (5, 1)]);
+    assert!((graph.attribute_assortativity(&attr) - 5.0 / 7.0).abs() < 1e-12);
+    // without the bridge, categories are perfectly sorted.
+    assert!((get_graph(3)?.attribute_assortativity(&attr) - 1.0).abs() < 1e-12);
+
+    // a square with alternating categories only has edges across them.
+    let square =
+        SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 3), (3, 0)])?;
+    let attr = categories(vec![(0, 0), (1, 1), (2, 0), (3, 1)]);
+    assert!((square.attribute_assortativity(&attr) + 1.0).abs() < 1e-12);
+
+    // categories unrelated to the (random) structure.
+    let graph = SimpleUndirectedGraphBuilder {}.get_er_graph(300, 0.05)?;
+    let attr: HashMap<NodeId, usize> = graph
+        .get_ids_iter()
+        .map(|id| (*id, id.value() as usize % 3))
+        .collect();
+    assert!(graph.attribute_assortativity(&attr).abs() < 0.1);
+
+    // a single category, or nothing to go on.
+    let attr = categories(vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+    assert_eq!(square.attribute_assortativity(&attr), 0.0);
+    assert_eq!(square.attribute_assortativity(&HashMap::new()), 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_joint_degree_matrix() -> CLQResult<()> {
+    // a star: every edge joins a leaf to the center, of degree 6.
+    let star = SimpleUndirectedGraphBuilder {}.from_vector((1..=6).map(|x| (0, x)).collect())?;
+    let matrix = star.joint_degree_matrix();
+    assert_eq!(matrix.len(), 1);
+    assert_eq!(matrix[&(1, 6)], 6);
+
+    // a path 0-1-2-3: two edges from a leaf to a degree 2 node, one between the
+    // two degree 2 nodes.
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(3)?;
+    let matrix = path.joint_degree_matrix();
+    assert_eq!(matrix.len(), 2);
+    assert_eq!(matrix[&(1, 2)], 2);
+    assert_eq!(matrix[&(2, 2)], 1);
+    let graph = get_graph(0)?;
+    assert_eq!(
+        graph.joint_degree_matrix().values().sum::<usize>(),
+        graph.count_edges()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_streaming_graph_builder() -> CLQResult<()> {
+    // a few thousand edges, most of them repeated, some reversed, and a self-loop.
+    let mut rows: Vec<(i64, i64)> = Vec::new();
+    for i in 0..3000 {
+        let (a, b) = ((i * 7) % 300, (i * 13 + 1) % 300);
+        rows.push(if i % 2 == 0 { (a, b) } else { (b, a) });
+    }
+    rows.push((5, 5));
+    let mut streaming = StreamingGraphBuilder::new();
+    for (a, b) in &rows {
+        streaming.push_edge(NodeId::from(*a), NodeId::from(*b));
+    }
+    let streamed = streaming.build()?;
+    let batch = SimpleUndirectedGraphBuilder {}.from_vector(rows)?;
+
+    assert_eq!(streamed.count_nodes(), batch.count_nodes());
+    assert_eq!(streamed.count_edges(), batch.count_edges());
+    assert_eq!(
+        streamed.get_ordered_node_ids(),
+        batch.get_ordered_node_ids()
+    );
+    for id in batch.get_ids_iter() {
+        assert_eq!(
+            streamed.get_node(*id).neighbors,
+            batch.get_node(*id).neighbors
+        );
+    }
+    assert_eq!(StreamingGraphBuilder::new().build()?.count_nodes(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_temporal_snapshot() -> CLQResult<()> {
+    let rows: Vec<(i64, i64, u64)> = vec![
+        (0, 1, 10),
+        (1, 2, 20),
+        (2, 0, 30),
+        (2, 3, 30),
+        (3, 4, 40),
+        (0, 1, 50),
+    ];
+    let graph = TemporalGraph::from_vector(rows.clone());
+    assert_eq!(graph.get_time_span(), Some((10, 50)));
+    let edge_set = |g: &SimpleUndirectedGraph| -> BTreeSet<(NodeId, NodeId)> {
+        g.get_nodes_iter()
+            .flat_map(|node| {
+                node.neighbors
+                    .iter()
+                    .filter(move |x| **x > node.node_id)
+                    .map(move |x| (node.node_id, *x))
+            })
+            .collect()
+    };
+    let id = NodeId::from;
+
+    // windows include their start, but not their end.
+    let early = graph.snapshot(10, 30)?;
+    assert_eq!(
+        edge_set(&early),
+        vec![(id(0), id(1)), (id(1), id(2))].into_iter().collect()
+    );
+    assert!(!early.has_node(id(3)));
+    let late = graph.snapshot(30, 51)?;
+    assert_eq!(
+        edge_set(&late),
+        vec![
+            (id(0), id(1)),
+            (id(0), id(2)),
+            (id(2), id(3)),
+            (id(3), id(4))
+        ]
+        .into_iter()
+        .collect()
+    );
+    assert_eq!(graph.snapshot(41, 50)?.count_nodes(), 0);
+    assert!(graph.snapshot(30, 20).is_err());
+
+    let full = SimpleUndirectedGraphBuilder {}
+        .from_vector(rows.iter().map(|(a, b, _)| (*a, *b)).collect())?;
+    let all_time = graph.snapshot(0, u64::MAX)?;
+    assert_eq!(edge_set(&all_time), edge_set(&full));
+    assert_eq!(all_time.get_ordered_node_ids(), full.get_ordered_node_ids());
+    Ok(())
+}
+
+#[test]
+fn test_max_weight_independent_set_tree() -> CLQResult<()> {
+    let weights = |v: Vec<(i64, f64)>| -> HashMap<NodeId, f64> {
+        v.into_iter().map(|(id, w)| (NodeId::from(id), w)).collect()
+    };
+    let ids = |v: Vec<i64>| -> Vec<NodeId> { v.into_iter().map(NodeId::from).collect() };
+
+    // nodes 0 to 4: taking 1 and 3 beats every other node.
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(4)?;
+    let node_weights = weights(vec![(0, 1.0), (1, 4.0), (2, 2.0), (3, 5.0), (4, 1.0)]);
+    assert_eq!(
+        path.max_weight_independent_set_tree(&node_weights)?,
+        (ids(vec![1, 3]), 9.0)
+    );
+    // a heavy middle node pushes its neighbors out.
+    let node_weights = weights(vec![(0, 1.0), (1, 4.0), (2, 9.0), (3, 5.0), (4, 1.0)]);
+    assert_eq!(
+        path.max_weight_independent_set_tree(&node_weights)?,
+        (ids(vec![0, 2, 4]), 11.0)
+    );
+
+    // a star: either the hub alone, or all of the leaves.
+    let star = SimpleUndirectedGraphBuilder {}.from_vector((1..=4).map(|x| (0, x)).collect())?;
+    let node_weights = weights(vec![(0, 5.0), (1, 1.0), (2, 1.0), (3, 1.0), (4, 1.0)]);
+    assert_eq!(
+        star.max_weight_independent_set_tree(&node_weights)?,
+        (ids(vec![0]), 5.0)
+    );
+    let node_weights = weights(vec![(0, 3.0), (1, 1.0), (2, 1.0), (3, 1.0), (4, 1.0)]);
+    assert_eq!(
+        star.max_weight_independent_set_tree(&node_weights)?,
+        (ids(vec![1, 2, 3, 4]), 4.0)
+    );
+
+    // a forest, where unweighted nodes are left out.
+    let forest = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (2, 3), (3, 4)])?;
+    let node_weights = weights(vec![(1, 2.0), (2, 1.0), (4, 1.0)]);
+    assert_eq!(
+        forest.max_weight_independent_set_tree(&node_weights)?,
+        (ids(vec![1, 2, 4]), 4.0)
+    );
+
+    let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(5)?;
+    assert!(matches!(
+        cycle.max_weight_independent_set_tree(&HashMap::new()),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    // weights for nodes that aren't in the graph are rejected.
+    let node_weights = weights(vec![(0, 1.0), (9, 1.0)]);
+    assert!(matches!(
+        path.max_weight_independent_set_tree(&node_weights),
+        Err(CLQError::NodeNotFound(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_structural_features() -> CLQResult<()> {
+    // a triangle (0, 1, 2) with a tail 2-3.
+    let graph = get_graph(5)?;
+    let features = graph.structural_features();
+    assert_eq!(features.len(), 4);
+    for vector in features.values() {
+        assert_eq!(vector.len(), STRUCTURAL_FEATURE_NAMES.len());
+    }
+    let id = NodeId::from;
+    assert_eq!(features[&id(0)], vec![2.0, 1.0, 1.0, 2.5, 3.0]);
+    assert_eq!(features[&id(2)], vec![3.0, 1.0 / 3.0, 1.0, 5.0 / 3.0, 3.0]);
+    assert_eq!(features[&id(3)], vec![1.0, 0.0, 0.0, 3.0, 3.0]);
+
+    // in K5, every node has the same role.
+    let k5 = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    for vector in k5.structural_features().values() {
+        assert_eq!(*vector, vec![4.0, 1.0, 6.0, 4.0, 4.0]);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_node_disjoint_paths() -> CLQResult<()> {
+    let id = NodeId::from;
+    // nodes 0 to 5: any two nodes are joined both ways around.
+    let cycle = SimpleUndirectedGraphBuilder {}.get_cycle_graph(6)?;
+    for (a, b) in [(0, 3), (0, 1), (2, 5), (4, 5)] {
+        assert_eq!(cycle.node_disjoint_paths(id(a), id(b)), 2);
+    }
+
+    // two squares sharing node 0, which is an articulation point.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (0, 4),
+        (4, 5),
+        (5, 6),
+        (6, 0),
+    ])?;
+    assert_eq!(graph.node_disjoint_paths(id(2), id(5)), 1);
+    assert_eq!(graph.node_disjoint_paths(id(1), id(3)), 2);
+    assert_eq!(graph.node_disjoint_paths(id(0), id(5)), 2);
+
+    // in K5, the direct edge and one path through each of the 3 other nodes.
+    let k5 = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    assert_eq!(k5.node_disjoint_paths(id(1), id(2)), 4);
+
+    // disconnected pairs, and degenerate queries.
+    let triangles = get_graph(3)?;
+    assert_eq!(triangles.node_disjoint_paths(id(0), id(3)), 0);
+    assert_eq!(triangles.node_disjoint_paths(id(0), id(0)), 0);
+    assert_eq!(triangles.node_disjoint_paths(id(0), id(42)), 0);
+    Ok(())
+}
+
+#[test]
+fn test_relabel_contiguous() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (10, 42),
+        (42, 7),
+        (7, 10),
+        (7, 100),
+        (100, 55),
+    ])?;
+    let (relabeled, old_to_new, new_to_old) = graph.relabel_contiguous();
+    assert_eq!(relabeled.count_nodes(), graph.count_nodes());
+    assert_eq!(relabeled.count_edges(), graph.count_edges());
+    let expected_ids: Vec<NodeId> = (0..5).map(NodeId::from).collect();
+    assert_eq!(relabeled.get_ordered_node_ids(), expected_ids);
+    // ids are assigned in increasing order of the original ids.
+    assert_eq!(
+        new_to_old,
+        vec![7, 10, 42, 55, 100]
+            .into_iter()
+            .map(NodeId::from)
+            .collect::<Vec<NodeId>>()
+    );
+    // the two maps are inverses of each other.
+    assert_eq!(old_to_new.len(), new_to_old.len());
+    for (new, old) in new_to_old.iter().enumerate() {
+        assert_eq!(old_to_new[old], NodeId::from(new as i64));
+    }
+    // edges map onto edges in both directions.
+    for node in graph.get_nodes_iter() {
+        let new_id = old_to_new[&node.node_id];
+        let mapped: BTreeSet<NodeId> = node.neighbors.iter().map(|x| old_to_new[x]).collect();
+        assert_eq!(relabeled.get_node(new_id).neighbors, mapped);
+    }
+    for node in relabeled.get_nodes_iter() {
+        let old_id = new_to_old[node.node_id.value() as usize];
+        let mapped: BTreeSet<NodeId> = node
+            .neighbors
+            .iter()
+            .map(|x| new_to_old[x.value() as usize])
+            .collect();
+        assert_eq!(graph.get_node(old_id).neighbors, mapped);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_shortest_path_tree() -> CLQResult<()> {
+    let graph = get_graph(0)?;
+    let source = NodeId::from(0);
+    let parents = graph.shortest_path_tree(source);
+    let dists = graph.get_hop_distances(source);
+    assert_eq!(parents[&source], source);
+    // every reachable node is in the tree, and nothing else.
+    assert_eq!(
+        parents.keys().collect::<HashSet<_>>(),
+        dists.keys().collect::<HashSet<_>>()
+    );
+    for (node_id, parent_id) in &parents {
+        if *node_id == source {
+            continue;
+        }
+        // each parent pointer is an edge one hop closer to the source.
+        assert!(graph.get_node(*node_id).neighbors.contains(parent_id));
+        assert_eq!(dists[parent_id] + 1, dists[node_id]);
+        // following parents reaches the source in exactly dist hops.
+        let mut current = *node_id;
+        let mut hops = 0;
+        while current != source {
+            current = parents[&current];
+            hops += 1;
+        }
+        assert_eq!(hops, dists[node_id]);
+    }
+
+    // nodes outside the source's component are not in the tree.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 3), (4, 5)])?;
+    let parents = graph.shortest_path_tree(NodeId::from(1));
+    assert_eq!(parents.len(), 3);
+    assert_eq!(parents[&NodeId::from(3)], NodeId::from(2));
+    assert!(!parents.contains_key(&NodeId::from(4)));
+    assert!(graph.shortest_path_tree(NodeId::from(9)).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_community_report() -> CLQResult<()> {
+    // two 4-cliques, 0-3 and 4-7, joined by the edge (3, 4).
+    let mut edges: Vec<(i64, i64)> = vec![(3, 4)];
+    for offset in [0, 4] {
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                edges.push((offset + i, offset + j));
+            }
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let communities: HashMap<NodeId, usize> = (0..8)
+        .map(|i| (NodeId::from(i), (i / 4) as usize))
+        .collect();
+    let report = graph.community_report(&communities);
+    // 13 edges; each community has 6 internal edges and total degree 13.
+    let expected = CommunityReport {
+        modularity: 2.0 * (6.0 / 13.0 - 0.25),
+        num_communities: 2,
+        community_sizes: vec![4, 4],
+        average_internal_density: 1.0,
+        average_conductance: 1.0 / 13.0,
+    };
+    assert!((report.modularity - expected.modularity).abs() <= 1e-12);
+    assert!((report.modularity - graph.modularity(&communities)).abs() <= 1e-12);
+    assert_eq!(report.num_communities, expected.num_communities);
+    assert_eq!(report.community_sizes, expected.community_sizes);
+    assert!((report.average_internal_density - expected.average_internal_density).abs() <= 1e-12);
+    assert!((report.average_conductance - expected.average_conductance).abs() <= 1e-12);
+
+    // splitting off node 7 as a singleton.
+    let mut communities = communities;
+    communities.insert(NodeId::from(7), 2);
+    let report = graph.community_report(&communities);
+    assert_eq!(report.num_communities, 3);
+    assert_eq!(report.community_sizes, vec![4, 3, 1]);
+    assert!((report.average_internal_density - 2.0 / 3.0).abs() <= 1e-12);
+    Ok(())
+}
+
+#[test]
+fn test_community_edge_counts() -> CLQResult<()> {
+    // two 4-cliques, 0-3 and 4-7, joined by the edge (3, 4).
+    let mut edges: Vec<(i64, i64)> = vec![(3, 4)];
+    for offset in [0, 4] {
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                edges.push((offset + i, offset + j));
+            }
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let mut communities: HashMap<NodeId, usize> = (0..8)
+        .map(|i| (NodeId::from(i), (i / 4) as usize))
+        .collect();
+    let counts = graph.community_edge_counts(&communities);
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[&0], (6, 1));
+    assert_eq!(counts[&1], (6, 1));
+
+    // splitting off node 7 as a singleton moves its 3 edges out of community 1.
+    communities.insert(NodeId::from(7), 2);
+    let counts = graph.community_edge_counts(&communities);
+    assert_eq!(counts[&0], (6, 1));
+    assert_eq!(counts[&1], (3, 4));
+    assert_eq!(counts[&2], (0, 3));
+    let internal: usize = counts.values().map(|(internal, _)| internal).sum();
+    let external: usize = counts.values().map(|(_, external)| external).sum();
+    assert_eq!(internal + external / 2, graph.count_edges());
+    Ok(())
+}
+
+#[test]
+fn test_degree_constrained_subgraph() -> CLQResult<()> {
+    let graph = get_graph(0)?;
+    let bounds =
+        |b: usize| -> HashMap<NodeId, usize> { graph.get_ids_iter().map(|id| (*id, b)).collect() };
+    let degrees_of = |edges: &Vec<(NodeId, NodeId)>| -> HashMap<NodeId, usize> {
+        let mut degrees: HashMap<NodeId, usize> = HashMap::new();
+        for (a, b) in edges {
+            assert!(graph.get_node(*a).neighbors.contains(b));
+            *degrees.entry(*a).or_insert(0) += 1;
+            *degrees.entry(*b).or_insert(0) += 1;
+        }
+        degrees
+    };
+
+    // with bounds of 1, a maximal matching: every edge has a matched end.
+    let matching = graph.degree_constrained_subgraph(&bounds(1))?;
+    let matched = degrees_of(&matching);
+    assert!(matched.values().all(|d| *d == 1));
+    for node in graph.get_nodes_iter() {
+        for neighbor_id in &node.neighbors {
+            assert!(matched.contains_key(&node.node_id) || matched.contains_key(neighbor_id));
+        }
+    }
+
+    // higher bounds allow more edges, each node staying within its bound.
+    let denser = graph.degree_constrained_subgraph(&bounds(2))?;
+    assert!(denser.len() > matching.len());
+    assert!(degrees_of(&denser).values().all(|d| *d <= 2));
+
+    // bounds at least the max degree keep every edge.
+    let all = graph.degree_constrained_subgraph(&bounds(graph.count_nodes()))?;
+    assert_eq!(all.len(), graph.count_edges());
+
+    let mut missing = bounds(1);
+    missing.remove(&NodeId::from(0));
+    assert!(graph.degree_constrained_subgraph(&missing).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_coreness_by_component() -> CLQResult<()> {
+    // a 4-clique, a triangle with a pendant node, a path and a 5-cycle.
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..4 {
+        for j in (i + 1)..4 {
+            edges.push((i, j));
+        }
+    }
+    edges.extend(vec![(10, 11), (11, 12), (12, 10), (12, 13)]);
+    edges.extend(vec![(20, 21), (21, 22), (22, 23)]);
+    edges.extend((0..5).map(|i| (30 + i, 30 + (i + 1) % 5)));
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let coreness = graph.get_coreness_by_component();
+    assert_eq!(coreness, graph.get_coreness_values());
+    let id = NodeId::from;
+    assert_eq!(coreness[&id(0)], 3);
+    assert_eq!(coreness[&id(10)], 2);
+    assert_eq!(coreness[&id(13)], 1);
+    assert_eq!(coreness[&id(22)], 1);
+    assert_eq!(coreness[&id(34)], 2);
+
+    // sparse random graphs have many small components, and isolated nodes.
+    for _ in 0..5 {
+        let graph = SimpleUndirectedGraphBuilder {}.get_er_graph(300, 0.005)?;
+        assert_eq!(
+            graph.get_coreness_by_component(),
+            graph.get_coreness_values()
+        );
+    }
+    Ok(())
+}
+
+// Benchmark on many small components: run with `cargo test --release --
+// --ignored --nocapture test_coreness_by_component_timing` to see the timings.
+#[test]
+#[ignore]
+fn test_coreness_by_component_timing() -> CLQResult<()> {
+    // 20,000 components of 5 nodes: a 4-clique with a pendant node.
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for c in 0..20_000 {
+        let offset = 5 * c;
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                edges.push((offset + i, offset + j));
+            }
+        }
+        edges.push((offset + 3, offset + 4));
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+
+    // best of 3 runs each, to smooth out noise.
+    let mut whole_graph_time = Duration::MAX;
+    let mut by_component_time = Duration::MAX;
+    let mut coreness = HashMap::new();
+    let mut by_component = HashMap::new();
+    for _ in 0..3 {
+        let start = Instant::now();
+        coreness = graph.get_coreness_values();
+        whole_graph_time = whole_graph_time.min(start.elapsed());
+        let start = Instant::now();
+        by_component = graph.get_coreness_by_component();
+        by_component_time = by_component_time.min(start.elapsed());
+    }
+    println!(
+        "get_coreness_values: {:?}, get_coreness_by_component: {:?}",
+        whole_graph_time, by_component_time
+    );
+    assert_eq!(by_component, coreness);
+    assert!(by_component_time < whole_graph_time);
+    Ok(())
+}
+
+#[test]
+fn test_structured_errors() -> CLQResult<()> {
+    let graph = get_graph(0)?;
+    match graph.get_k_trusses(1) {
+        Err(CLQError::InvalidParameter(_)) => (),
+        other => panic!("expected InvalidParameter, got {:?}", other.map(|x| x.0)),
+    }
+    let missing = NodeId::from(1000);
+    match graph.get_induced_subgraph(&[NodeId::from(0), missing]) {
+        Err(CLQError::NodeNotFound(id)) => assert_eq!(id, missing),
+        _ => panic!("expected NodeNotFound"),
+    }
+    assert_eq!(
+        CLQError::NodeNotFound(missing).to_string(),
+        "Node 1000 is not in the graph."
+    );
+    let disconnected = SimpleUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (2, 3)])?;
+    assert!(matches!(
+        disconnected.uniform_spanning_tree(0),
+        Err(CLQError::NotConnected)
+    ));
+    // string errors are still supported.
+    assert!(matches!(
+        CLQError::from("message".to_string()),
+        CLQError::Generic(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_iter_shells() -> CLQResult<()> {
+    for i in 0..7 {
+        let graph = get_graph(i)?;
+        let coreness = graph.get_coreness_values();
+        let mut seen: HashSet<NodeId> = HashSet::new();
+        let mut previous_k: Option<usize> = None;
+        for (k, node_ids) in graph.iter_shells() {
+            assert!(previous_k.is_none_or(|x| x < k));
+            previous_k = Some(k);
+            assert!(!node_ids.is_empty());
+            for id in node_ids {
+                assert_eq!(coreness[&id], k);
+                // every node is in exactly one shell.
+                assert!(seen.insert(id));
+            }
+        }
+        assert_eq!(seen.len(), graph.count_nodes());
+    }
+
+    // a 5-clique with a path hanging off it: shells are produced from the outside in.
+    let mut edges: Vec<(i64, i64)> = vec![(4, 5), (5, 6), (6, 7)];
+    for a in 0..5 {
+        for b in a + 1..5 {
+            edges.push((a, b));
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let mut shells = graph.iter_shells();
+    assert_eq!(
+        shells.next(),
+        Some((1, vec![5, 6, 7].into_iter().map(NodeId::from).collect()))
+    );
+    assert_eq!(shells.next(), Some((4, (0..5).map(NodeId::from).collect())));
+    assert_eq!(shells.next(), None);
+    Ok(())
+}
+
+#[test]
+fn test_bfs_distances_parallel() -> CLQResult<()> {
+    let mut graphs: Vec<SimpleUndirectedGraph> = (0..7).map(get_graph).collect::<CLQResult<_>>()?;
+    graphs.push(SimpleUndirectedGraphBuilder {}.get_complete_graph(30)?);
+    graphs.push(SimpleUndirectedGraphBuilder {}.get_er_graph(500, 0.01)?);
+    for graph in graphs {
+        for id in graph.get_ordered_node_ids().into_iter().take(10) {
+            assert_eq!(
+                graph.bfs_distances_parallel(id),
+                graph.get_hop_distances(id)
+            );
+        }
+    }
+    assert!(get_graph(0)?
+        .bfs_distances_parallel(NodeId::from(1000))
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_node_attributes() -> CLQResult<()> {
+    let id = NodeId::from;
+    let attribute = |value: &str| -> HashMap<String, String> {
+        HashMap::from([("team".to_string(), value.to_string())])
+    };
+    // two teams, each a triangle, with one edge between them.
+    let edges = vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)];
+    let attributes: HashMap<NodeId, HashMap<String, String>> = (0..6)
+        .map(|i| (id(i), attribute(if i < 3 { "red" } else { "blue" })))
+        .collect();
+    let mut graph =
+        SimpleUndirectedGraphBuilder {}.from_vector_with_attributes(edges.clone(), attributes)?;
+    assert_eq!(graph.get_attribute(id(0), "team"), Some("red"));
+    assert_eq!(graph.get_attribute(id(4), "team"), Some("blue"));
+    assert_eq!(graph.get_attribute(id(4), "size"), None);
+    let categories = graph.attribute_categories("team");
+    // values are numbered in sorted order.
+    assert_eq!(categories[&id(0)], 1);
+    assert_eq!(categories[&id(5)], 0);
+    assert!(graph.attribute_assortativity(&categories) > 0.5);
+
+    // moving nodes across teams mixes them up.
+    graph.set_attribute(id(1), "team", "blue")?;
+    graph.set_attribute(id(4), "team", "red")?;
+    assert_eq!(graph.get_attribute(id(1), "team"), Some("blue"));
+    let mixed = graph.attribute_assortativity(&graph.attribute_categories("team"));
+    assert!(mixed < 0.0);
+    assert!(graph.set_attribute(id(10), "team", "red").is_err());
+
+    // attributes follow nodes into subgraphs.
+    let subgraph = graph.get_induced_subgraph(&[id(0), id(1)])?;
+    assert_eq!(subgraph.get_attribute(id(1), "team"), Some("blue"));
+    assert_eq!(subgraph.get_attribute(id(4), "team"), None);
+
+    let unknown = HashMap::from([(id(10), attribute("red"))]);
+    assert!(SimpleUndirectedGraphBuilder {}
+        .from_vector_with_attributes(edges, unknown)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_graph_metadata() -> CLQResult<()> {
+    let id = NodeId::from;
+    let expected = |graph: &SimpleUndirectedGraph| {
+        let degrees: Vec<usize> = graph.get_nodes_iter().map(|node| node.degree()).collect();
+        GraphMetadata {
+            num_nodes: graph.nodes.len(),
+            num_edges: graph
+                .get_nodes_iter()
+                .map(|node| node.degree())
+                .sum::<usize>()
+                / 2,
+            max_degree: degrees.iter().copied().max().unwrap_or(0),
+            min_degree: degrees.iter().copied().min().unwrap_or(0),
+            total_degree: degrees.iter().sum(),
+        }
+    };
+    let mut graph = SimpleUndirectedGraphBuilder {}.get_path_graph(3)?;
+    assert_eq!(
+        graph.metadata(),
+        &GraphMetadata {
+            num_nodes: 4,
+            num_edges: 3,
+            max_degree: 2,
+            min_degree: 1,
+            total_degree: 6,
+        }
+    );
+
+    assert!(graph.add_edge(id(0), id(3)));
+    assert!(!graph.add_edge(id(3), id(0)));
+    assert_eq!(graph.metadata(), &expected(&graph));
+    assert_eq!(graph.metadata().min_degree, 2);
+    assert_eq!(graph.density(), 4.0 / 6.0);
+
+    assert!(graph.add_edge(id(4), id(0)));
+    assert_eq!(graph.metadata(), &expected(&graph));
+    assert_eq!(graph.count_nodes(), 5);
+    assert_eq!(graph.ids.len(), 5);
+
+    assert!(graph.remove_edge(id(1), id(2))?);
+    assert!(!graph.remove_edge(id(1), id(2))?);
+    assert_eq!(graph.metadata(), &expected(&graph));
+    assert_eq!(graph.count_edges(), 4);
+
+    graph.remove_node(id(0))?;
+    assert_eq!(graph.metadata(), &expected(&graph));
+    assert_eq!(graph.metadata().max_degree, 1);
+    assert!(graph.remove_node(id(0)).is_err());
+    assert!(graph.remove_edge(id(0), id(1)).is_err());
+
+    // algorithms mutating the graph through get_mut_nodes also reset the cache.
+    let mut graph = SimpleUndirectedGraphBuilder {}.get_cycle_graph(8)?;
+    graph.metadata();
+    let mut coreness = graph.get_coreness_values();
+    graph.remove_edge_update_coreness(id(0), id(1), &mut coreness)?;
+    assert!(matches!(
+        graph.remove_edge_update_coreness(id(0), id(1), &mut coreness),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    assert_eq!(graph.metadata(), &expected(&graph));
+    assert_eq!(graph.count_edges(), 7);
+
+    // editing nodes directly leaves the cache as it was until it's cleared, but
+    // count_edges always recounts.
+    graph
+        .nodes
+        .get_mut(&id(2))
+        .unwrap()
+        .neighbors
+        .remove(&id(3));
+    graph
+        .nodes
+        .get_mut(&id(3))
+        .unwrap()
+        .neighbors
+        .remove(&id(2));
+    assert_eq!(graph.count_edges(), 6);
+    assert_eq!(graph.metadata().num_edges, 7);
+    graph.clear_metadata();
+    assert_eq!(graph.metadata(), &expected(&graph));
+    Ok(())
+}
+
+#[test]
+fn test_coreness_filtration() -> CLQResult<()> {
+    // K4 on 1..=4, a triangle 4-5-6 sharing node 4, a tail 6-7-8 and a 9-10 edge.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (1, 2),
+        (1, 3),
+        (1, 4),
+        (2, 3),
+        (2, 4),
+        (3, 4),
+        (4, 5),
+        (5, 6),
+        (6, 4),
+        (6, 7),
+        (7, 8),
+        (9, 10),
+    ])?;
+    let node_set =
+        |g: &SimpleUndirectedGraph| g.get_ids_iter().cloned().collect::<BTreeSet<NodeId>>();
+    let filtration = graph.coreness_filtration();
+    assert_eq!(
+        filtration.iter().map(|(k, _)| *k).collect::<Vec<usize>>(),
+        vec![3, 2, 1]
+    );
+    let sizes: Vec<(usize, usize)> = filtration
+        .iter()
+        .map(|(_, g)| (g.count_nodes(), g.count_edges()))
+        .collect();
+    assert_eq!(sizes, vec![(4, 6), (6, 9), (10, 12)]);
+    for (i, (_, core)) in filtration.iter().enumerate() {
+        for (_, lower_core) in &filtration[i..] {
+            assert!(node_set(core).is_subset(&node_set(lower_core)));
+        }
+        for node in core.get_nodes_iter() {
+            assert!(node
+                .neighbors
+                .is_subset(&graph.nodes[&node.node_id].neighbors));
+        }
+    }
+    let one_core = graph.k_core_subgraph(1);
+    assert_eq!(node_set(&filtration[2].1), node_set(&one_core));
+    assert_eq!(node_set(&one_core), node_set(&graph));
+    assert_eq!(graph.k_core_subgraph(4).count_nodes(), 0);
+
+    // isolated nodes are left out of every core.
+    let dimacs = "p edge 4 2\ne 1 2\ne 2 3\n";
+    let with_isolated = SimpleUndirectedGraphBuilder::from_dimacs(dimacs.as_bytes())?;
+    let filtration = with_isolated.coreness_filtration();
+    assert_eq!(filtration.len(), 1);
+    assert_eq!(filtration[0].1.count_nodes(), 3);
+    assert!(SimpleUndirectedGraph::create_empty()
+        .coreness_filtration()
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_estimate_degree_distribution() -> CLQResult<()> {
+    // 50 stars with 3 leaves each: a quarter of the nodes have degree 3, the rest 1.
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for star in 0..50 {
+        for leaf in 1..=3 {
+            edges.push((4 * star, 4 * star + leaf));
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let exact = graph.degree_distribution();
+    assert_eq!(exact.len(), 2);
+    assert!((exact[&1] - 0.75).abs() <= 1e-9);
+    assert!((exact[&3] - 0.25).abs() <= 1e-9);
+
+    for seed in 0..5 {
+        let estimate = graph.estimate_degree_distribution(0.25, seed)?;
+        assert!((estimate.values().sum::<f64>() - 1.0).abs() <= 1e-9);
+        for (degree, fraction) in &estimate {
+            assert!((fraction - exact[degree]).abs() <= 0.15);
+        }
+    }
+    let full = graph.estimate_degree_distribution(1.0, 0)?;
+    for (degree, fraction) in &exact {
+        assert!((full[degree] - fraction).abs() <= 1e-9);
+    }
+    assert!(graph.estimate_degree_distribution(0.0, 0).is_err());
+    assert!(graph.estimate_degree_distribution(1.5, 0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_reorder_by_coreness() -> CLQResult<()> {
+    // a K4 on 10..14 (coreness 3), a square 0-1-2-3 (coreness 2) attached to 10,
+    // and a leaf 20 (coreness 1) on 0.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (10, 11),
+        (10, 12),
+        (10, 13),
+        (11, 12),
+        (11, 13),
+        (12, 13),
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (3, 10),
+        (0, 20),
+    ])?;
+    let (reordered, order) = graph.reorder_by_coreness();
+    let expected: Vec<NodeId> = [10, 11, 12, 13, 0, 1, 2, 3, 20]
+        .into_iter()
+        .map(NodeId::from)
+        .collect();
+    assert_eq!(order, expected);
+    assert_eq!(reordered.get_core_ids(), &expected);
+    let visited: Vec<NodeId> = reordered
+        .get_nodes_in_order()
+        .map(|node| node.node_id)
+        .collect();
+    assert_eq!(visited, expected);
+
+    let coreness = reordered.get_coreness_values();
+    assert!(reordered
+        .get_core_ids()
+        .windows(2)
+        .all(|x| coreness[&x[0]] >= coreness[&x[1]]));
+    assert_eq!(coreness, graph.get_coreness_values());
+    assert_eq!(reordered.count_nodes(), graph.count_nodes());
+    assert_eq!(reordered.count_edges(), graph.count_edges());
+    for node in graph.get_nodes_iter() {
+        assert_eq!(reordered.get_node(node.node_id).neighbors, node.neighbors);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_prune_leaves() -> CLQResult<()> {
+    let ids = |x: &[i64]| {
+        x.iter()
+            .map(|id| NodeId::from(*id))
+            .collect::<Vec<NodeId>>()
+    };
+    // a tree: 0 with children 1 and 2, 1 with children 3 and 4, and 4 with child 5.
+    let mut tree = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (0, 2),
+        (1, 3),
+        (1, 4),
+        (4, 5),
+    ])?;
+    let removed = tree.prune_leaves();
+    assert_eq!(removed, ids(&[2, 3, 5, 0, 4, 1]));
+    assert_eq!(tree.count_nodes(), 0);
+    assert_eq!(tree.count_edges(), 0);
+    assert!(tree.ids.is_empty());
+
+    // a 5-cycle with pendant paths 0-10-11 and 2-20, and a separate path 30-31-32.
+    let mut graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 0),
+        (0, 10),
+        (10, 11),
+        (2, 20),
+        (30, 31),
+        (31, 32),
+    ])?;
+    let mut removed = graph.prune_leaves();
+    assert_eq!(removed.len(), 6);
+    removed.sort();
+    assert_eq!(removed, ids(&[10, 11, 20, 30, 31, 32]));
+    assert_eq!(graph.get_ordered_node_ids(), ids(&[0, 1, 2, 3, 4]));
+    assert_eq!(graph.count_edges(), 5);
+    assert!(graph.get_nodes_iter().all(|node| node.degree() == 2));
+    assert_eq!(graph.ids.len(), 5);
+    assert!(graph.prune_leaves().is_empty());
+
+    // a triangle with a pendant path of 200,000 nodes, pruned from its far end in
+    // linear time.
+    let n: i64 = 200_000;
+    let mut rows: Vec<(i64, i64)> = (0..n).map(|x| (x, x + 1)).collect();
+    rows.extend([(n, n + 1), (n + 1, n + 2), (n + 2, n)]);
+    let mut graph = SimpleUndirectedGraphBuilder {}.from_vector(rows)?;
+    graph.metadata();
+    let start = Instant::now();
+    let removed = graph.prune_leaves();
+    // a generous bound: this takes well under a second.
+    assert!(start.elapsed().as_secs() < 10);
+    assert_eq!(removed, (0..n).map(NodeId::from).collect::<Vec<NodeId>>());
+    assert_eq!(graph.get_ordered_node_ids(), ids(&[n, n + 1, n + 2]));
+    assert_eq!(graph.ids.len(), 3);
+    assert_eq!(graph.metadata().num_edges, 3);
+    Ok(())
+}
@@ -10,8 +10,10 @@ extern crate lib_dachshund;
 extern crate test;
 
 use lib_dachshund::dachshund::algorithms::clustering::Clustering;
+use lib_dachshund::dachshund::algorithms::motifs::{Motif3, Motif4, Motifs};
 use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
 use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
 use lib_dachshund::dachshund::id_types::NodeId;
 use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
@@ -19,6 +21,7 @@ use lib_dachshund::dachshund::simple_undirected_graph_builder::{
     SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
 };
 
+use std::collections::HashMap;
 use test::Bencher;
 
 // The complete graph on 4 nodes with one edge removed.
@@ -69,6 +72,18 @@ fn test_clustering_coefficient() -> CLQResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_par_map_nodes() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.get_er_graph(100, 0.1)?;
+    let parallel = graph.par_map_nodes(|g, id| g.get_clustering_coefficient(id));
+    let serial: HashMap<NodeId, Option<f64>> = graph
+        .get_ids_iter()
+        .map(|id| (*id, graph.get_clustering_coefficient(*id)))
+        .collect();
+    assert_eq!(parallel, serial);
+    Ok(())
+}
+
 #[test]
 fn test_transitivity() -> CLQResult<()> {
     let k4 = &SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
@@ -79,6 +94,76 @@ fn test_transitivity() -> CLQResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_count_connected_triples() -> CLQResult<()> {
+    // a star with 5 leaves: every pair of leaves forms a triple through the center.
+    let star = SimpleUndirectedGraphBuilder {}.from_vector((1..=5).map(|x| (0, x)).collect())?;
+    assert_eq!(star.count_connected_triples(), 10);
+
+    let triangle = SimpleUndirectedGraphBuilder {}.get_complete_graph(3)?;
+    assert_eq!(triangle.count_connected_triples(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_enumerate_triangles() -> CLQResult<()> {
+    let almost_k4 = get_almost_k4_graph()?;
+    let expected: Vec<(NodeId, NodeId, NodeId)> = vec![(0, 1, 2), (0, 1, 3)]
+        .into_iter()
+        .map(|(a, b, c)| (NodeId::from(a), NodeId::from(b), NodeId::from(c)))
+        .collect();
+    assert_eq!(almost_k4.enumerate_triangles(), expected);
+
+    for graph in [
+        SimpleUndirectedGraphBuilder {}.get_complete_graph(7)?,
+        SimpleUndirectedGraphBuilder {}.get_er_graph(50, 0.2)?,
+    ] {
+        let triangles = graph.enumerate_triangles();
+        assert_eq!(triangles.len(), graph.count_triangles());
+        for (a, b, c) in triangles {
+            assert!(a < b && b < c);
+            assert!(graph.nodes[&a].neighbors.contains(&b));
+            assert!(graph.nodes[&a].neighbors.contains(&c));
+            assert!(graph.nodes[&b].neighbors.contains(&c));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_enumerate_4_cliques() -> CLQResult<()> {
+    let k5 = SimpleUndirectedGraphBuilder {}.get_complete_graph(5)?;
+    let cliques = k5.enumerate_4_cliques();
+    assert_eq!(k5.count_4_cliques(), 5);
+    // each one leaves out one of the nodes 1 to 5.
+    for (i, clique) in cliques.iter().rev().enumerate() {
+        let expected: Vec<NodeId> = (1..=5)
+            .filter(|x| *x != i + 1)
+            .map(|x| NodeId::from(x as i64))
+            .collect();
+        assert_eq!(clique.to_vec(), expected);
+    }
+
+    let triangle = SimpleUndirectedGraphBuilder {}.get_complete_graph(3)?;
+    assert_eq!(triangle.count_4_cliques(), 0);
+    assert_eq!(get_almost_k4_graph()?.count_4_cliques(), 0);
+
+    let graph = SimpleUndirectedGraphBuilder {}.get_er_graph(30, 0.4)?;
+    assert_eq!(
+        graph.count_4_cliques(),
+        *graph.motif_census_4().get(&Motif4::Clique).unwrap_or(&0)
+    );
+    for clique in graph.enumerate_4_cliques() {
+        assert!(clique.windows(2).all(|x| x[0] < x[1]));
+        for i in 0..4 {
+            for j in i + 1..4 {
+                assert!(graph.nodes[&clique[i]].neighbors.contains(&clique[j]));
+            }
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn test_approx_avg_clustering() -> CLQResult<()> {
     let k4 = &SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
@@ -103,3 +188,165 @@ fn test_approx_transitivity() -> CLQResult<()> {
     assert!((0.75 - approx_transitivity).abs() <= 0.01);
     Ok(())
 }
+
+#[test]
+fn test_motif_census() -> CLQResult<()> {
+    let triangle = SimpleUndirectedGraphBuilder {}.get_complete_graph(3)?;
+    let census = triangle.motif_census_3();
+    assert_eq!(census[&Motif3::Triangle], 1);
+    assert_eq!(census[&Motif3::Path], 0);
+
+    // 0 - 1 - 2 - 3
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(3)?;
+    let census = path.motif_census_3();
+    assert_eq!(census[&Motif3::Triangle], 0);
+    assert_eq!(census[&Motif3::Path], 2);
+    let census = path.motif_census_4();
+    assert_eq!(census[&Motif4::Path], 1);
+    assert_eq!(census.values().sum::<usize>(), 1);
+
+    let k4 = SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
+    assert_eq!(k4.motif_census_3()[&Motif3::Triangle], 4);
+    assert_eq!(k4.motif_census_4()[&Motif4::Clique], 1);
+
+    let almost_k4 = get_almost_k4_graph()?;
+    let census = almost_k4.motif_census_3();
+    assert_eq!(census[&Motif3::Triangle], 2);
+    assert_eq!(census[&Motif3::Path], 2);
+    let census = almost_k4.motif_census_4();
+    assert_eq!(census[&Motif4::Diamond], 1);
+    assert_eq!(census.values().sum::<usize>(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_canonical_form() -> CLQResult<()> {
+    // a house (square with a roof) with a tail, and a relabeled copy.
+    let edges: Vec<(i64, i64)> = vec![(0, 1), (1, 2), (2, 3), (3, 0), (0, 4), (1, 4), (2, 5)];
+    let relabel = [17, 3, 42, 8, 25, 11];
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges.clone())?;
+    let relabeled = SimpleUndirectedGraphBuilder {}.from_vector(
+        edges
+            .iter()
+            .map(|(a, b)| (relabel[*b as usize], relabel[*a as usize]))
+            .collect(),
+    )?;
+    assert_eq!(graph.canonical_form(), relabeled.canonical_form());
+    assert_eq!(graph.canonical_form().len(), edges.len());
+
+    // same degree sequences, but not isomorphic: color refinement alone can't
+    // tell these apart.
+    let two_triangles = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+    ])?;
+    let hexagon = SimpleUndirectedGraphBuilder {}.get_cycle_graph(6)?;
+    assert_ne!(two_triangles.canonical_form(), hexagon.canonical_form());
+    let prism = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (3, 4),
+        (4, 5),
+        (5, 3),
+        (0, 3),
+        (1, 4),
+        (2, 5),
+    ])?;
+    let bipartite = SimpleUndirectedGraphBuilder {}
+        .from_vector((0..3).flat_map(|a| (3..6).map(move |b| (a, b))).collect())?;
+    assert_ne!(prism.canonical_form(), bipartite.canonical_form());
+    // the prism again, numbered around its sides instead.
+    let prism2 = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 0),
+        (0, 2),
+        (1, 4),
+        (3, 5),
+    ])?;
+    assert_eq!(prism.canonical_form(), prism2.canonical_form());
+    Ok(())
+}
+
+#[test]
+fn test_enumerate_connected_subgraphs() -> CLQResult<()> {
+    let ids = |x: &[i64]| {
+        x.iter()
+            .map(|id| NodeId::from(*id))
+            .collect::<Vec<NodeId>>()
+    };
+    // 0-1-2-3
+    let path = SimpleUndirectedGraphBuilder {}.get_path_graph(3)?;
+    let subgraphs = path.enumerate_connected_subgraphs(3)?;
+    assert_eq!(subgraphs, vec![ids(&[0, 1, 2]), ids(&[1, 2, 3])]);
+    assert_eq!(path.enumerate_connected_subgraphs(1)?.len(), 4);
+    assert_eq!(
+        path.enumerate_connected_subgraphs(4)?,
+        vec![ids(&[0, 1, 2, 3])]
+    );
+    assert!(path.enumerate_connected_subgraphs(5)?.is_empty());
+    assert!(path.enumerate_connected_subgraphs(0).is_err());
+    assert!(path.enumerate_connected_subgraphs(100).is_err());
+
+    // every set is found once, and there is one per 4-node motif occurrence.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (0, 4),
+        (1, 4),
+        (2, 5),
+        (5, 6),
+        (6, 3),
+    ])?;
+    let subgraphs = graph.enumerate_connected_subgraphs(4)?;
+    let mut deduped = subgraphs.clone();
+    deduped.dedup();
+    assert_eq!(deduped, subgraphs);
+    assert_eq!(
+        subgraphs.len(),
+        graph.motif_census_4().values().sum::<usize>()
+    );
+    let k6 = SimpleUndirectedGraphBuilder {}.get_complete_graph(6)?;
+    assert_eq!(k6.enumerate_connected_subgraphs(3)?.len(), 20);
+    Ok(())
+}
+
+#[test]
+fn test_triangle_participation_ratio() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // K5 on 0..5, with a binary tree hanging off 4: 4-5, 5-6, 5-7, 6-8, 6-9.
+    let mut edges: Vec<(i64, i64)> = vec![(4, 5), (5, 6), (5, 7), (6, 8), (6, 9)];
+    for i in 0..5 {
+        for j in i + 1..5 {
+            edges.push((i, j));
+        }
+    }
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let ratios = graph.triangle_participation_ratio();
+    assert_eq!(ratios.len(), 10);
+    for i in 0..4 {
+        assert_eq!(ratios[&id(i)], 1.0);
+    }
+    // 6 of the 10 pairs of 4's neighbors are in the clique.
+    assert_eq!(ratios[&id(4)], 0.6);
+    for i in 5..10 {
+        assert_eq!(ratios[&id(i)], 0.0);
+    }
+    assert_eq!(
+        graph.nodes_in_dense_regions(0.5),
+        (0..5).map(id).collect::<Vec<NodeId>>()
+    );
+    assert_eq!(graph.nodes_in_dense_regions(1.0).len(), 4);
+    assert_eq!(graph.nodes_in_dense_regions(0.0).len(), 10);
+    Ok(())
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::pagerank::PageRank;
+use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::node::DirectedNodeBase;
+use lib_dachshund::dachshund::simple_directed_graph::DirectedGraph;
+use lib_dachshund::dachshund::weighted_directed_graph_builder::WeightedDirectedGraphBuilder;
+
+#[test]
+fn test_build_weighted_directed_graph() -> CLQResult<()> {
+    let graph =
+        WeightedDirectedGraphBuilder {}.from_vector(vec![(0, 1, 2.0), (1, 2, 1.0), (2, 0, 0.5)])?;
+    assert_eq!(graph.count_nodes(), 3);
+    assert_eq!(graph.count_edges(), 3);
+    assert!(!graph.is_acyclic());
+    let node = graph.get_node(NodeId::from(1));
+    assert!(node.has_in_neighbor(NodeId::from(0)));
+    assert!(node.has_out_neighbor(NodeId::from(2)));
+    assert!(!node.has_out_neighbor(NodeId::from(0)));
+    assert_eq!(node.out_weight(), 1.0);
+
+    assert!(WeightedDirectedGraphBuilder {}
+        .from_vector(vec![(0, 1, -1.0)])
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_weighted_pagerank() -> CLQResult<()> {
+    let id = NodeId::from;
+    // 0 points to 1 and 2, which both point back to 0.
+    let rows = |weight_1: f64, weight_2: f64| {
+        vec![(0, 1, weight_1), (0, 2, weight_2), (1, 0, 1.0), (2, 0, 1.0)]
+    };
+    let graph = WeightedDirectedGraphBuilder {}.from_vector(rows(1.0, 1.0))?;
+    let equal_split = graph.pagerank(0.85, 100, 1e-12);
+    assert!((equal_split[&id(1)] - equal_split[&id(2)]).abs() < 1e-9);
+
+    let graph = WeightedDirectedGraphBuilder {}.from_vector(rows(9.0, 1.0))?;
+    let ranks = graph.pagerank(0.85, 100, 1e-12);
+    assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(ranks[&id(1)] > equal_split[&id(1)]);
+    assert!(ranks[&id(2)] < equal_split[&id(2)]);
+    // 0 still gets everything back, so its rank doesn't depend on the split.
+    assert!((ranks[&id(0)] - equal_split[&id(0)]).abs() < 1e-9);
+    // all of 0's rank but the teleportation share is split 9 to 1.
+    let teleport = 0.15 / 3.0;
+    assert!(((ranks[&id(1)] - teleport) / (ranks[&id(2)] - teleport) - 9.0).abs() < 1e-6);
+
+    // 3 is dangling: its rank is spread over every node, and ranks still sum to 1.
+    let graph = WeightedDirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 3.0),
+        (1, 3, 1.0),
+        (2, 0, 1.0),
+    ])?;
+    let ranks = graph.pagerank(0.85, 200, 1e-12);
+    assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(ranks[&id(2)] > ranks[&id(3)]);
+    assert!(ranks.values().all(|rank| *rank > 0.0));
+    Ok(())
+}
+
+#[test]
+fn test_node_weighted_pagerank() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a 3-cycle, plus 3 feeding into it through a heavy edge.
+    let graph = WeightedDirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 1.0),
+        (2, 0, 1.0),
+        (3, 0, 10.0),
+    ])?;
+    let uniform = graph.pagerank(0.85, 200, 1e-12);
+    let ranks = graph.node_weighted_pagerank(0.85, 1e-12, 200)?;
+    assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    // 3 only gets teleported to, in proportion to its weight (10 out of 26).
+    assert!((ranks[&id(3)] - 0.15 * 10.0 / 26.0).abs() < 1e-9);
+    assert!(ranks[&id(3)] > uniform[&id(3)]);
+    assert!(ranks[&id(0)] > uniform[&id(0)]);
+
+    let graph = WeightedDirectedGraphBuilder {}.from_vector(vec![(0, 1, 0.0)])?;
+    assert!(matches!(
+        graph.node_weighted_pagerank(0.85, 1e-12, 200),
+        Err(CLQError::InvalidParameter(_))
+    ));
+
+    // the builder rejects NaN and negative weights, but the nodes can be edited.
+    let mut graph = WeightedDirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0)])?;
+    graph.nodes.get_mut(&id(0)).unwrap().out_edges[0].weight = f64::NAN;
+    assert!(matches!(
+        graph.node_weighted_pagerank(0.85, 1e-12, 200),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    graph.nodes.get_mut(&id(0)).unwrap().out_edges[0].weight = -1.0;
+    assert!(matches!(
+        graph.node_weighted_pagerank(0.85, 1e-12, 200),
+        Err(CLQError::NegativeWeight(_))
+    ));
+    Ok(())
+}
@@ -6,14 +6,37 @@
  */
 extern crate lib_dachshund;
 
+use lib_dachshund::dachshund::algorithms::betweenness::{Betweenness, WeightedBetweenness};
+use lib_dachshund::dachshund::algorithms::closeness::{Closeness, WeightedCloseness};
+use lib_dachshund::dachshund::algorithms::connected_components::{
+    ConnectedComponentsUndirected, WeightedConnectedComponents,
+};
+use lib_dachshund::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
 use lib_dachshund::dachshund::algorithms::coreness::{Coreness, FractionalCoreness};
+use lib_dachshund::dachshund::algorithms::independent_set::IndependentSet;
+use lib_dachshund::dachshund::algorithms::label_propagation::WeightedLabelPropagation;
+use lib_dachshund::dachshund::algorithms::laplacian::WeightedLaplacian;
+use lib_dachshund::dachshund::algorithms::modularity::{Modularity, WeightedModularity};
+use lib_dachshund::dachshund::algorithms::shortest_paths::{ShortestPaths, WeightedShortestPaths};
+use lib_dachshund::dachshund::algorithms::similarity::{Similarity, WeightedSimilarity};
+use lib_dachshund::dachshund::algorithms::spanning_tree::WeightedSpanningTree;
+use lib_dachshund::dachshund::algorithms::statistics::{Statistics, WeightedStatistics};
+use lib_dachshund::dachshund::algorithms::subgraph::InducedSubgraph;
+use lib_dachshund::dachshund::algorithms::transitivity::{Transitivity, WeightedTransitivity};
+use lib_dachshund::dachshund::construction::{knn_graph, Metric};
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
 use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
 use lib_dachshund::dachshund::id_types::NodeId;
 use lib_dachshund::dachshund::node::WeightedNodeBase;
-use lib_dachshund::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
-use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::weighted_undirected_graph::{WeightNorm, WeightedUndirectedGraph};
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::{
+    TWeightedUndirectedGraphBuilder, WeightedUndirectedGraphBuilder,
+};
+use std::collections::{HashMap, HashSet};
 
 fn get_graph(idx: usize) -> CLQResult<WeightedUndirectedGraph> {
     let v = match idx {
@@ -51,6 +74,17 @@ fn get_graph(idx: usize) -> CLQResult<WeightedUndirectedGraph> {
             (5, 7, 3.0),
             (6, 7, 3.0),
         ],
+        // Complete graph on 4 nodes with uniform weight 0.5.
+        7 => vec![
+            (0, 1, 0.5),
+            (0, 2, 0.5),
+            (0, 3, 0.5),
+            (1, 2, 0.5),
+            (1, 3, 0.5),
+            (2, 3, 0.5),
+        ],
+        // A triangle with total weight 6, plus a separate light edge.
+        8 => vec![(0, 1, 1.0), (1, 2, 2.0), (2, 0, 3.0), (10, 11, 0.5)],
         _ => return Err(CLQError::Generic("Invalid index".to_string())),
     };
     WeightedUndirectedGraphBuilder {}.from_vector(
@@ -131,3 +165,1406 @@ fn test_fractional_coreness() {
         );
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_weighted_coreness_anomaly() -> CLQResult<()> {
+    // K4 on nodes 0..4 with weight 10.0 edges, plus a hub (10) whose heavy edges
+    // (weight 6.0) all lead to leaves. The hub is the heaviest node, but it can't be
+    // in any fractional core above 6.0.
+    let mut edges: Vec<(i64, i64, f64)> = Vec::new();
+    for i in 0..4 {
+        for j in i + 1..4 {
+            edges.push((i, j, 10.0));
+        }
+    }
+    for leaf in 20..30 {
+        edges.push((10, leaf, 6.0));
+    }
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+    let weighted_coreness = graph.get_fractional_coreness_values();
+    let anomaly = graph.get_weighted_coreness_anomaly(&weighted_coreness);
+
+    let hub = NodeId::from(10_i64);
+    let (most_anomalous, score) = anomaly
+        .iter()
+        .max_by(|x, y| x.1.partial_cmp(y.1).unwrap())
+        .unwrap();
+    assert_eq!(*most_anomalous, hub);
+    // first by weight, tied for 5th to 15th by fractional coreness.
+    assert!((score - 10.0_f64.ln()).abs() <= 1e-12);
+    Ok(())
+}
+
+#[test]
+fn test_strength_coreness_gap() -> CLQResult<()> {
+    // K10 on nodes 1..=10 with weight 5.0 edges, plus a leaf (11) hanging off node
+    // 1 through a single weight 300.0 edge: the second heaviest node, but the first
+    // one to be peeled.
+    let mut edges: Vec<(i64, i64, f64)> = Vec::new();
+    for i in 1..=10 {
+        for j in i + 1..=10 {
+            edges.push((i, j, 5.0));
+        }
+    }
+    edges.push((1, 11, 300.0));
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+    let leaf = NodeId::from(11_i64);
+
+    let kshell = graph.get_weighted_kshell_values()?;
+    assert!((kshell[&leaf] - 300.0_f64.sqrt()).abs() <= 1e-9);
+    assert!((kshell[&NodeId::from(1_i64)] - 405.0_f64.sqrt()).abs() <= 1e-9);
+    // the fractional core doesn't see it: the heavy edge is a core on its own.
+    assert_eq!(graph.get_fractional_coreness_values()[&leaf], 300.0);
+
+    let gap = graph.strength_coreness_gap()?;
+    let (most_anomalous, score) = gap
+        .iter()
+        .max_by(|x, y| x.1.partial_cmp(y.1).unwrap())
+        .unwrap();
+    assert_eq!(*most_anomalous, leaf);
+    // 2nd by strength, last by weighted k-shell.
+    assert!((score - 0.9).abs() <= 1e-12);
+    assert!(gap.values().all(|x| (-1.0..=1.0).contains(x)));
+
+    for weight in [f64::NAN, f64::INFINITY] {
+        let graph =
+            WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (1, 2, weight)])?;
+        assert!(matches!(
+            graph.get_weighted_kshell_values(),
+            Err(CLQError::InvalidParameter(_))
+        ));
+        assert!(graph.strength_coreness_gap().is_err());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_density() {
+    let complete_graph = get_graph(7).unwrap();
+    assert_eq!(complete_graph.density(), 1.0);
+    assert_eq!(complete_graph.average_degree(), 3.0);
+    assert_eq!(complete_graph.weighted_density(), 0.5);
+    assert_eq!(complete_graph.average_weighted_degree(), 1.5);
+
+    // Star graph: 3 out of 6 possible edges, with total weight 6.0.
+    let star_graph = get_graph(0).unwrap();
+    assert_eq!(star_graph.density(), 0.5);
+    assert_eq!(star_graph.weighted_density(), 1.0);
+    assert_eq!(star_graph.average_weighted_degree(), 3.0);
+}
+
+#[cfg(test)]
+#[test]
+fn test_node_weights() {
+    let graph = get_graph(4).unwrap();
+    let total: f64 = graph.get_ids_iter().map(|id| graph.node_weight(*id)).sum();
+    assert_eq!(graph.total_node_weight(), total);
+    assert_eq!(
+        graph.total_node_weight(),
+        2.0 * graph.get_total_edge_weight()
+    );
+
+    // the triangle corners have weight 5.0 each, the spokes 1.0.
+    let heaviest = graph.heaviest_nodes(4);
+    let expected: Vec<(NodeId, f64)> = vec![(0, 5.0), (1, 5.0), (2, 5.0), (3, 1.0)]
+        .into_iter()
+        .map(|(id, weight)| (NodeId::from(id as i64), weight))
+        .collect();
+    assert_eq!(heaviest, expected);
+}
+
+#[cfg(test)]
+#[test]
+fn test_component_weights() {
+    let graph = get_graph(8).unwrap();
+    let components = graph.get_connected_components();
+    let weights = graph.component_weights();
+    assert_eq!(weights.len(), 2);
+    for (component, weight) in components.iter().zip(weights) {
+        if component.contains(&NodeId::from(10_i64)) {
+            assert_eq!(weight, 0.5);
+        } else {
+            assert_eq!(weight, 6.0);
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_closeness() {
+    // on a path with weight 2 on every edge, all distances double.
+    let path = get_graph(5).unwrap();
+    let closeness = path.closeness_centrality();
+    let weighted_closeness = path.weighted_closeness_centrality().unwrap();
+    for id in path.get_ids_iter() {
+        assert!((weighted_closeness[id] - closeness[id] / 2.0).abs() <= 1e-12);
+    }
+    // the center of the path: 4 nodes at distances 1, 1, 2, 2.
+    assert_eq!(closeness[&NodeId::from(2_i64)], 4.0 / 6.0);
+
+    // node 2 reaches 2 of the other 4 nodes, at distances 2.0 (to 1) and 3.0 (to 0).
+    let graph = get_graph(8).unwrap();
+    let weighted_closeness = graph.weighted_closeness_centrality().unwrap();
+    let expected = (2.0 / 5.0) * (2.0 / 4.0);
+    assert!((weighted_closeness[&NodeId::from(2_i64)] - expected).abs() <= 1e-12);
+    // the separate edge reaches 1 node at distance 0.5.
+    let expected = (1.0 / 0.5) * (1.0 / 4.0);
+    assert!((weighted_closeness[&NodeId::from(10_i64)] - expected).abs() <= 1e-12);
+
+    // a node reaching nobody scores 0.
+    let single = WeightedUndirectedGraph {
+        nodes: WeightedUndirectedGraphBuilder::get_nodes(
+            [(NodeId::from(0_i64), Default::default())].into(),
+        ),
+        ids: vec![NodeId::from(0_i64)],
+    };
+    assert_eq!(
+        single.weighted_closeness_centrality().unwrap()[&NodeId::from(0_i64)],
+        0.0
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_modularity() {
+    // graph 6 is two heavy triangles, each with a light hub, and a bridge between the hubs.
+    let communities: HashMap<NodeId, usize> = (0..8)
+        .map(|i| (NodeId::from(i as i64), if i <= 3 { 0 } else { 1 }))
+        .collect();
+    let graph = get_graph(6).unwrap();
+    let unweighted = graph.modularity(&communities);
+    assert!((unweighted - (12.0 / 13.0 - 0.5)).abs() <= 1e-12);
+    // upweighting the intra-community edges raises modularity for the same partition.
+    assert!(graph.weighted_modularity(&communities) > unweighted);
+
+    // with uniform weights, weighted modularity reduces to the unweighted one.
+    let uniform = WeightedUndirectedGraphBuilder {}
+        .from_vector(
+            graph
+                .get_nodes_iter()
+                .flat_map(|node| node.edges.iter().map(move |e| (node.node_id, e.target_id)))
+                .filter(|(a, b)| a < b)
+                .map(|(a, b)| (a.value(), b.value(), 2.0))
+                .collect(),
+        )
+        .unwrap();
+    assert!((uniform.weighted_modularity(&communities) - unweighted).abs() <= 1e-12);
+}
+
+#[cfg(test)]
+#[test]
+fn test_contract_edge() -> CLQResult<()> {
+    // the triangle (0, 1, 2) with weight 2.0 edges, and a spoke on each corner.
+    let mut graph = get_graph(4)?;
+    let (a, b) = (NodeId::from(0_i64), NodeId::from(1_i64));
+    assert_eq!(graph.contract_edge(a, b)?, a);
+    // (0, 1) is gone, and (1, 2) merged into (0, 2).
+    assert_eq!(graph.count_nodes(), 5);
+    assert_eq!(graph.count_edges(), 4);
+    assert!(!graph.has_node(b));
+    assert!(!graph.ids.contains(&b));
+    for node in graph.get_nodes_iter() {
+        assert!(!node.neighbors.contains(&node.node_id));
+        assert!(!node.neighbors.contains(&b));
+        assert_eq!(node.neighbors.len(), node.edges.len());
+    }
+    assert_eq!(graph.get_node_weight(a), 4.0 + 1.0 + 1.0);
+    assert_eq!(graph.get_node_weight(NodeId::from(2_i64)), 4.0 + 1.0);
+    assert_eq!(graph.get_total_edge_weight(), 9.0 - 2.0);
+
+    // b is gone, and 3 and 5 aren't connected.
+    assert!(graph.contract_edge(a, b).is_err());
+    assert!(matches!(
+        graph.contract_edge(NodeId::from(3_i64), NodeId::from(5_i64)),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        graph.contract_edge(a, a),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_normalize_edge_weights() -> CLQResult<()> {
+    let edge_weights = |graph: &WeightedUndirectedGraph| -> Vec<f64> {
+        let mut weights: Vec<f64> = Vec::new();
+        for node in graph.get_nodes_iter() {
+            for e in &node.edges {
+                // both directions of an edge agree.
+                let reverse = graph
+                    .get_node(e.target_id)
+                    .edges
+                    .iter()
+                    .find(|x| x.target_id == node.node_id);
+                assert_eq!(reverse.unwrap().weight, e.weight);
+                if node.node_id < e.target_id {
+                    weights.push(e.weight);
+                }
+            }
+        }
+        weights
+    };
+
+    // the triangle with weights 1, 2 and 3, and an edge of weight 0.5.
+    let mut graph = get_graph(8)?;
+    graph.normalize_edge_weights(WeightNorm::MinMax);
+    let weights = edge_weights(&graph);
+    assert_eq!(weights.iter().cloned().fold(f64::INFINITY, f64::min), 0.0);
+    assert_eq!(
+        weights.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        1.0
+    );
+    assert!((graph.get_total_edge_weight() - (0.5 + 1.5 + 2.5) / 2.5).abs() < 1e-12);
+
+    let mut graph = get_graph(8)?;
+    graph.normalize_edge_weights(WeightNorm::SumToOne);
+    assert!((graph.get_total_edge_weight() - 1.0).abs() < 1e-12);
+    assert!(edge_weights(&graph).contains(&(0.5 / 6.5)));
+
+    let mut graph = get_graph(8)?;
+    graph.normalize_edge_weights(WeightNorm::Standardize);
+    let weights = edge_weights(&graph);
+    let mean = weights.iter().sum::<f64>() / 4.0;
+    let variance = weights.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / 4.0;
+    assert!(mean.abs() < 1e-12);
+    assert!((variance - 1.0).abs() < 1e-12);
+
+    // all weights equal.
+    let rows = vec![(0, 1, 2.0), (1, 2, 2.0)];
+    let mut graph = WeightedUndirectedGraphBuilder {}.from_vector(rows.clone())?;
+    graph.normalize_edge_weights(WeightNorm::MinMax);
+    assert_eq!(edge_weights(&graph), vec![1.0, 1.0]);
+    let mut graph = WeightedUndirectedGraphBuilder {}.from_vector(rows)?;
+    graph.normalize_edge_weights(WeightNorm::Standardize);
+    assert_eq!(edge_weights(&graph), vec![0.0, 0.0]);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_jaccard_similarity() -> CLQResult<()> {
+    let id = NodeId::from;
+    // 0 and 1 are both tied to 2 and 3, with different strengths; 4 is only tied to 3.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 2, 3.0),
+        (0, 3, 1.0),
+        (1, 2, 1.0),
+        (1, 3, 1.0),
+        (4, 3, 2.0),
+    ])?;
+    assert_eq!(graph.jaccard_similarity(id(0), id(1)), 1.0);
+    assert_eq!(graph.weighted_jaccard_similarity(id(0), id(1)), 2.0 / 4.0);
+    assert_eq!(graph.weighted_jaccard_similarity(id(1), id(0)), 2.0 / 4.0);
+    assert_eq!(graph.jaccard_similarity(id(1), id(4)), 0.5);
+    assert_eq!(graph.weighted_jaccard_similarity(id(1), id(4)), 1.0 / 3.0);
+    assert_eq!(graph.weighted_jaccard_similarity(id(2), id(2)), 1.0);
+    assert_eq!(graph.weighted_jaccard_similarity(id(0), id(2)), 0.0);
+
+    // with unit weights, it's the same as the unweighted version.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (0, 2, 1.0),
+        (1, 2, 1.0),
+        (2, 3, 1.0),
+        (3, 4, 1.0),
+    ])?;
+    for a in graph.get_ids_iter() {
+        for b in graph.get_ids_iter() {
+            assert_eq!(
+                graph.weighted_jaccard_similarity(*a, *b),
+                graph.jaccard_similarity(*a, *b)
+            );
+        }
+    }
+
+    // no edge weight at all.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 0.0), (1, 2, 0.0)])?;
+    assert_eq!(graph.weighted_jaccard_similarity(id(0), id(2)), 0.0);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_threshold() -> CLQResult<()> {
+    // the uneven square: weights 1.0 to 4.0 around the cycle.
+    let graph = get_graph(3)?;
+    let mut num_edges: Vec<usize> = Vec::new();
+    let mut is_connected: Vec<bool> = Vec::new();
+    for min_weight in [0.0, 1.0, 2.0, 3.0, 4.0, 5.0] {
+        let thresholded = graph.threshold(min_weight)?;
+        assert_eq!(thresholded.count_nodes(), 4);
+        num_edges.push(thresholded.count_edges());
+        is_connected.push(thresholded.get_is_connected().unwrap());
+        for node in thresholded.get_nodes_iter() {
+            for neighbor_id in &node.neighbors {
+                assert!(thresholded.nodes[neighbor_id]
+                    .neighbors
+                    .contains(&node.node_id));
+            }
+        }
+    }
+    assert_eq!(num_edges, vec![4, 4, 3, 2, 1, 0]);
+    // a cycle minus one edge is a path, minus two it falls apart.
+    assert_eq!(is_connected, vec![true, true, true, false, false, false]);
+
+    // only (2, 3) and (3, 0) are left, and 1 is isolated but still there.
+    let thresholded = graph.threshold(3.0)?;
+    let id = NodeId::from;
+    assert!(thresholded.are_connected(id(0), id(2)));
+    assert!(!thresholded.are_connected(id(0), id(1)));
+    assert!(thresholded.has_node(id(1)));
+    assert!(thresholded.get_node(id(1)).neighbors.is_empty());
+    assert!(graph.threshold(f64::NAN).is_err());
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_edge_support() -> CLQResult<()> {
+    // with unit weights, support is the number of triangles of each edge.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (0, 2, 1.0),
+        (0, 3, 1.0),
+        (1, 2, 1.0),
+        (1, 3, 1.0),
+        (3, 4, 1.0),
+    ])?;
+    let weighted_support = graph.weighted_edge_support();
+    let support = graph.edge_support();
+    assert_eq!(weighted_support.len(), 6);
+    assert_eq!(support.len(), 6);
+    for (edge, num_triangles) in &support {
+        assert_eq!(weighted_support[edge], *num_triangles as f64);
+    }
+    let id = NodeId::from;
+    assert_eq!(support[&(id(0), id(1))], 2);
+    assert_eq!(support[&(id(1), id(2))], 1);
+    assert_eq!(support[&(id(3), id(4))], 0);
+
+    // the triangle of get_graph(4), weighing 2.0, with 1.0 spokes: only triangle edges get support.
+    let graph = get_graph(4)?;
+    let weighted_support = graph.weighted_edge_support();
+    assert_eq!(weighted_support[&(id(0), id(1))], 2.0);
+    assert_eq!(weighted_support[&(id(0), id(3))], 0.0);
+    // a triangle is only as strong as its weakest other edge.
+    let graph = get_graph(8)?;
+    let weighted_support = graph.weighted_edge_support();
+    let triangle_support: Vec<f64> = vec![(0, 1), (0, 2), (1, 2)]
+        .into_iter()
+        .map(|(a, b)| weighted_support[&(id(a), id(b))])
+        .collect();
+    assert_eq!(triangle_support, vec![2.0, 1.0, 1.0]);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_coarsen() -> CLQResult<()> {
+    let rows: Vec<(i64, i64, f64)> = (0..63).map(|i| (i, i + 1, 1.0)).collect();
+    let mut graph = WeightedUndirectedGraphBuilder {}.from_vector(rows)?;
+    let mut fine_ids = graph.get_ordered_node_ids();
+    while graph.count_nodes() > 1 {
+        let n = graph.count_nodes();
+        let (coarse, mapping) = graph.coarsen(42)?;
+        // a maximal matching on a path contracts at least a third of the nodes.
+        assert!(coarse.count_nodes() >= n / 2);
+        assert!(coarse.count_nodes() <= n - n / 3);
+        let mut mapped_ids: Vec<NodeId> = mapping.keys().cloned().collect();
+        mapped_ids.sort();
+        assert_eq!(mapped_ids, fine_ids);
+        for super_id in mapping.values() {
+            assert!(coarse.has_node(*super_id));
+        }
+        // still a path, with the weight of the contracted edges gone.
+        let num_matched = n - coarse.count_nodes();
+        assert_eq!(coarse.count_edges(), coarse.count_nodes() - 1);
+        assert_eq!(
+            coarse.get_total_edge_weight(),
+            graph.get_total_edge_weight() - num_matched as f64
+        );
+        fine_ids = coarse.get_ordered_node_ids();
+        graph = coarse;
+    }
+
+    // a square with two heavy opposite sides, which get matched whatever the order.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 5.0),
+        (1, 2, 1.0),
+        (2, 3, 5.0),
+        (3, 0, 1.0),
+    ])?;
+    for seed in 0..5 {
+        let (coarse, mapping) = graph.coarsen(seed)?;
+        assert_eq!(coarse.count_nodes(), 2);
+        assert_eq!(mapping[&NodeId::from(1_i64)], NodeId::from(0_i64));
+        assert_eq!(mapping[&NodeId::from(3_i64)], NodeId::from(2_i64));
+        assert_eq!(coarse.get_total_edge_weight(), 2.0);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_edge_betweenness() -> CLQResult<()> {
+    // path 0 - 1 - 2 - 3, with a heavy shortcut (0, 3) and leaves 4 (on 0) and 5 (on 3).
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 1.0),
+        (2, 3, 1.0),
+        (0, 3, 10.0),
+        (0, 4, 1.0),
+        (3, 5, 1.0),
+    ])?;
+    let id = |x: i64| NodeId::from(x);
+    let argmax = |betweenness: &HashMap<(NodeId, NodeId), f64>| {
+        *betweenness
+            .iter()
+            .max_by(|x, y| x.1.partial_cmp(y.1).unwrap())
+            .unwrap()
+            .0
+    };
+
+    // by hops, the shortcut is the only shortest path between {0, 4} and {3, 5}, and
+    // ties with the path for pairs one step further away.
+    let unweighted = graph.get_edge_betweenness();
+    assert_eq!(unweighted.len(), 6);
+    assert_eq!(argmax(&unweighted), (id(0), id(3)));
+    assert_eq!(unweighted[&(id(0), id(3))], 4.0 + 4.0 * 0.5);
+    assert_eq!(unweighted[&(id(0), id(4))], 5.0);
+
+    // by weight, the shortcut is never worth taking.
+    let weighted = graph.weighted_edge_betweenness()?;
+    assert_eq!(weighted.len(), 6);
+    assert_eq!(weighted[&(id(0), id(3))], 0.0);
+    // (1, 2) separates {0, 1, 4} from {2, 3, 5}.
+    assert_eq!(argmax(&weighted), (id(1), id(2)));
+    assert_eq!(weighted[&(id(1), id(2))], 9.0);
+
+    // ties split credit: in a square, each pair of opposite corners has two shortest paths.
+    let square = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 2.0),
+        (1, 2, 2.0),
+        (2, 3, 2.0),
+        (3, 0, 2.0),
+    ])?;
+    for value in square.weighted_edge_betweenness()?.values() {
+        assert_eq!(*value, 1.0 + 2.0 * 0.5);
+    }
+
+    // a zero-weight edge doesn't make its endpoints predecessors of each other.
+    let path = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 0.0), (1, 2, 1.0)])?;
+    let betweenness = path.weighted_edge_betweenness()?;
+    assert_eq!(betweenness[&(id(0), id(1))], 2.0);
+    assert_eq!(betweenness[&(id(1), id(2))], 2.0);
+    let node_betweenness = path.weighted_node_betweenness()?;
+    assert_eq!(node_betweenness[&id(0)], 0.0);
+    assert_eq!(node_betweenness[&id(1)], 1.0);
+
+    let nan = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (1, 2, f64::NAN)])?;
+    assert!(matches!(
+        nan.weighted_edge_betweenness(),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    assert!(nan.weighted_node_betweenness().is_err());
+    assert!(nan.approximate_weighted_betweenness(3, 0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_approximate_weighted_betweenness() -> CLQResult<()> {
+    // same graph as in test_weighted_edge_betweenness: by weight, a path 4-0-1-2-3-5.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 1.0),
+        (2, 3, 1.0),
+        (0, 3, 10.0),
+        (0, 4, 1.0),
+        (3, 5, 1.0),
+    ])?;
+    let id = |x: i64| NodeId::from(x);
+    let exact = graph.weighted_node_betweenness()?;
+    for (node, expected) in [(0, 4.0), (1, 6.0), (2, 6.0), (3, 4.0), (4, 0.0), (5, 0.0)] {
+        assert!((exact[&id(node)] - expected).abs() <= 1e-9);
+    }
+    let sampled = graph.approximate_weighted_betweenness(6, 3)?;
+    for (node, value) in &exact {
+        assert!((sampled[node] - value).abs() <= 1e-9);
+    }
+    // matches the unweighted betweenness when all weights are 1.0.
+    let unit = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 1.0),
+        (2, 3, 1.0),
+        (3, 0, 1.0),
+        (0, 2, 1.0),
+        (3, 4, 1.0),
+    ])?;
+    let brandes = unit.get_node_betweenness_brandes().unwrap();
+    for (node, value) in unit.weighted_node_betweenness()? {
+        assert!((brandes[&node] - value).abs() <= 1e-9);
+    }
+
+    // two stars, with their hubs (0 and 100) joined by an edge.
+    let mut edges: Vec<(i64, i64, f64)> = vec![(0, 100, 1.0)];
+    for leaf in 1..=10 {
+        edges.push((0, leaf, 1.0));
+        edges.push((100, 100 + leaf, 2.0));
+    }
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+    let exact = graph.weighted_node_betweenness()?;
+    for seed in 0..5 {
+        let sampled = graph.approximate_weighted_betweenness(5, seed)?;
+        let mut ranking: Vec<(NodeId, f64)> = sampled.into_iter().collect();
+        ranking.sort_by(|x, y| y.1.partial_cmp(&x.1).unwrap().then(x.0.cmp(&y.0)));
+        let mut top: Vec<NodeId> = ranking.iter().take(2).map(|x| x.0).collect();
+        top.sort();
+        assert_eq!(top, vec![id(0), id(100)]);
+        assert!(ranking[2..].iter().all(|x| x.1 == 0.0));
+    }
+    assert!((exact[&id(0)] - (10.0 * 11.0 + 45.0)).abs() <= 1e-9);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_matrix_market() {
+    let mtx = "%%MatrixMarket matrix coordinate real symmetric\n\
+               4 4 3\n\
+               2 1 1.5\n\
+               3 1 2.0\n\
+               4 3 0.5\n";
+    let g = WeightedUndirectedGraphBuilder::from_matrix_market(mtx.as_bytes(), true).unwrap();
+    assert_eq!(g.count_nodes(), 4);
+    assert_eq!(g.count_edges(), 3);
+    assert_eq!(g.get_node(NodeId::from(1_i64)).weight(), 3.5);
+    assert_eq!(g.get_node(NodeId::from(3_i64)).weight(), 2.5);
+
+    let bad_value = "%%MatrixMarket matrix coordinate real general\n2 2 1\n2 1 heavy\n";
+    assert!(
+        WeightedUndirectedGraphBuilder::from_matrix_market(bad_value.as_bytes(), false).is_err()
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_read_weighted_edge_list() -> CLQResult<()> {
+    let tsv = "0\t1\t1.0\n\
+               # a comment\n\
+               \n\
+               0\t2\t2.0\n\
+               0\t3\t3.0\n";
+    let graph =
+        WeightedUndirectedGraphBuilder::read_weighted_edge_list(tsv.as_bytes(), false, b'\t')?;
+    let star = get_graph(0)?;
+    assert_eq!(graph.count_nodes(), star.count_nodes());
+    assert_eq!(graph.count_edges(), star.count_edges());
+    for id in star.get_ids_iter() {
+        assert_eq!(graph.get_node_weight(*id), star.get_node_weight(*id));
+    }
+
+    let csv = "# edges\nsource,target,weight\n0,1,1.0\n0,2,2.0\n0,3,3.0\n";
+    let graph =
+        WeightedUndirectedGraphBuilder::read_weighted_edge_list(csv.as_bytes(), true, b',')?;
+    assert_eq!(graph.count_edges(), 3);
+    assert_eq!(graph.get_node_weight(NodeId::from(0_i64)), 6.0);
+    // without has_header, the header is a malformed line.
+    assert!(
+        WeightedUndirectedGraphBuilder::read_weighted_edge_list(csv.as_bytes(), false, b',')
+            .is_err()
+    );
+
+    let malformed = "0\t1\t1.0\n0\t2\n";
+    match WeightedUndirectedGraphBuilder::read_weighted_edge_list(
+        malformed.as_bytes(),
+        false,
+        b'\t',
+    ) {
+        Err(CLQError::Generic(message)) => assert!(message.starts_with("Line 2:")),
+        _ => panic!("expected an error on line 2"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_ego_density() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a heavy triangle and a light triangle, joined by a light edge, each
+    // with a pendant node.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 5.0),
+        (1, 2, 5.0),
+        (2, 0, 5.0),
+        (0, 3, 1.0),
+        (10, 11, 1.0),
+        (11, 12, 1.0),
+        (12, 10, 1.0),
+        (10, 13, 1.0),
+        (2, 12, 1.0),
+    ])?;
+    // the ego network of 1 is just the heavy triangle.
+    assert_eq!(graph.weighted_ego_density(id(1)), 15.0 / 3.0);
+    assert_eq!(graph.weighted_ego_density(id(11)), 3.0 / 3.0);
+    assert!(graph.weighted_ego_density(id(0)) > graph.weighted_ego_density(id(10)));
+    // 3's ego network is the single edge (0, 3).
+    assert_eq!(graph.weighted_ego_density(id(3)), 1.0 / 2.0);
+
+    let isolated = graph.get_induced_subgraph(&[id(0)])?;
+    assert_eq!(isolated.weighted_ego_density(id(0)), 0.0);
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_all_pairs_weighted_closeness() -> CLQResult<()> {
+    let mut graphs: Vec<WeightedUndirectedGraph> = vec![get_graph(0)?, get_graph(3)?];
+    for i in 4..8 {
+        graphs.push(get_graph(i)?);
+    }
+    // two components, one of which has a shortcut lighter than the direct edge.
+    graphs.push(WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 5.0),
+        (0, 2, 1.0),
+        (2, 1, 1.5),
+        (3, 4, 0.5),
+    ])?);
+    for graph in graphs {
+        let expected = graph.weighted_closeness_centrality()?;
+        let closeness = graph.all_pairs_weighted_closeness();
+        assert_eq!(closeness.len(), expected.len());
+        for (id, value) in &expected {
+            assert!((closeness[id] - value).abs() <= 1e-12);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_node_weight_core() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a 4-clique of light edges, with a pendant node 4 attached to 0 by a heavy edge,
+    // and a pendant node 5 attached to 1 by a light one.
+    let mut rows: Vec<(i64, i64, f64)> = Vec::new();
+    for i in 0..4 {
+        for j in (i + 1)..4 {
+            rows.push((i, j, 0.1));
+        }
+    }
+    rows.push((0, 4, 5.0));
+    rows.push((1, 5, 0.1));
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(rows)?;
+    // by degree alone, both pendant nodes fall out of the 3-core.
+    let expected: Vec<NodeId> = (0..4).map(id).collect();
+    let graph_without_weight = graph.threshold(0.0)?;
+    let mut three_core: Vec<NodeId> = graph_without_weight
+        .get_k_cores(3)
+        .into_iter()
+        .flatten()
+        .collect();
+    three_core.sort();
+    assert_eq!(three_core, expected);
+    // node 4's weight keeps it in.
+    let core = graph.get_node_weight_core(3.0);
+    assert_eq!(core, vec![id(0), id(1), id(2), id(3), id(4)]);
+    // too high a threshold for the light clique.
+    assert_eq!(graph.get_node_weight_core(4.0), vec![id(0), id(4)]);
+    assert!(graph.get_node_weight_core(100.0).is_empty());
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_widest_path() -> CLQResult<()> {
+    let id = NodeId::from;
+    // from 0 to 3: a direct edge of weight 10, a light path through 1 and a heavy
+    // path through 2 and 4.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 3, 10.0),
+        (0, 1, 1.0),
+        (1, 3, 1.0),
+        (0, 2, 20.0),
+        (2, 4, 20.0),
+        (4, 3, 20.0),
+        (5, 6, 1.0),
+    ])?;
+    // fewest hops is the direct edge, lowest total weight the path through 1.
+    assert_eq!(graph.get_hop_distances(id(0))[&id(3)], 1);
+    assert_eq!(graph.get_weighted_distances(id(0))?[&id(3)], 2.0);
+    assert_eq!(
+        graph.widest_path(id(0), id(3))?,
+        Some((vec![id(0), id(2), id(4), id(3)], 20.0))
+    );
+    // every path out of 1 starts with an edge of weight 1.
+    assert_eq!(graph.widest_path(id(1), id(2))?.unwrap().1, 1.0);
+    assert_eq!(
+        graph.widest_path(id(0), id(0))?,
+        Some((vec![id(0)], f64::INFINITY))
+    );
+    assert_eq!(graph.widest_path(id(0), id(5))?, None);
+    assert_eq!(graph.widest_path(id(0), id(9))?, None);
+
+    let graph =
+        WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (1, 2, f64::NAN)])?;
+    assert!(matches!(
+        graph.widest_path(id(0), id(2)),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_random_walk() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a star whose center has one heavy edge, to 1, and three light ones.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 97.0),
+        (0, 2, 1.0),
+        (0, 3, 1.0),
+        (0, 4, 1.0),
+    ])?;
+    let num_walks = 1000;
+    let mut num_heavy_steps = 0;
+    for seed in 0..num_walks {
+        let walk = graph.weighted_random_walk(id(0), 4, seed);
+        assert_eq!(walk.len(), 5);
+        assert_eq!(walk[0], id(0));
+        // every other step goes back to the center.
+        assert_eq!(walk[2], id(0));
+        assert_eq!(walk[4], id(0));
+        if walk[1] == id(1) {
+            num_heavy_steps += 1;
+        }
+    }
+    // expected to take the heavy edge 97% of the time.
+    assert!(num_heavy_steps > 940);
+    assert_eq!(
+        graph.weighted_random_walk(id(2), 3, 7),
+        graph.weighted_random_walk(id(2), 3, 7)
+    );
+
+    // the walk stops at nodes with no edge of positive weight.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 0.0)])?;
+    assert_eq!(graph.weighted_random_walk(id(0), 5, 0), vec![id(0)]);
+    assert!(graph.weighted_random_walk(id(9), 5, 0).is_empty());
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_biased_random_walk() -> CLQResult<()> {
+    let id = NodeId::from;
+    // from 1, having come from 0: back to 0, on to 2 (also adjacent to 0), or out to 3.
+    let edges = [(0, 1), (0, 2), (1, 2), (1, 3)];
+    let get_graph = |weights: &[f64]| {
+        WeightedUndirectedGraphBuilder {}.from_vector(
+            edges
+                .iter()
+                .zip(weights)
+                .map(|((a, b), w)| (*a, *b, *w))
+                .collect(),
+        )
+    };
+    let unweighted = get_graph(&[1.0, 1.0, 1.0, 1.0])?;
+    let uniform = get_graph(&[5.0, 5.0, 5.0, 5.0])?;
+    let (p, q) = (0.25, 4.0);
+    let num_walks = 4000;
+    let mut counts: HashMap<NodeId, usize> = HashMap::new();
+    for seed in 0..num_walks {
+        let walk = uniform.weighted_biased_random_walk(id(0), 6, p, q, seed)?;
+        // uniform weights give the unweighted node2vec walk.
+        assert_eq!(
+            walk,
+            unweighted.weighted_biased_random_walk(id(0), 6, p, q, seed)?
+        );
+        if walk[1] == id(1) {
+            *counts.entry(walk[2]).or_insert(0) += 1;
+        }
+    }
+    // unnormalized probabilities 1 / p = 4, 1 and 1 / q = 0.25.
+    let num_steps = counts.values().sum::<usize>() as f64;
+    let share = |x: i64| *counts.get(&id(x)).unwrap_or(&0) as f64 / num_steps;
+    assert!((share(0) - 4.0 / 5.25).abs() < 0.05);
+    assert!((share(2) - 1.0 / 5.25).abs() < 0.05);
+    assert!((share(3) - 0.25 / 5.25).abs() < 0.03);
+
+    // with p = q = 1, the walk is the first-order weighted walk.
+    let weighted = get_graph(&[1.0, 2.0, 3.0, 4.0])?;
+    for seed in 0..20 {
+        assert_eq!(
+            weighted.weighted_biased_random_walk(id(0), 8, 1.0, 1.0, seed)?,
+            weighted.weighted_random_walk(id(0), 8, seed)
+        );
+    }
+
+    // otherwise, heavier edges are preferred: from 1, the walk goes out to 3
+    // rather than back to 0 when that edge is heavy enough to outweigh 1 / q.
+    let weighted = get_graph(&[1.0, 1.0, 1.0, 100.0])?;
+    let mut num_outward_steps = 0;
+    let mut num_second_steps = 0;
+    for seed in 0..1000 {
+        let walk = weighted.weighted_biased_random_walk(id(0), 2, 1.0, 2.0, seed)?;
+        if walk[1] == id(1) {
+            num_second_steps += 1;
+            if walk[2] == id(3) {
+                num_outward_steps += 1;
+            }
+        }
+    }
+    // expected to go out to 3 50 / 52 of the time.
+    assert!(num_outward_steps as f64 > 0.9 * num_second_steps as f64);
+
+    assert!(weighted
+        .weighted_biased_random_walk(id(9), 5, 1.0, 1.0, 0)?
+        .is_empty());
+    assert!(weighted
+        .weighted_biased_random_walk(id(0), 5, 0.0, 1.0, 0)
+        .is_err());
+    assert!(weighted
+        .weighted_biased_random_walk(id(0), 5, 1.0, f64::NAN, 0)
+        .is_err());
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_minimum_bottleneck_spanning_tree() -> CLQResult<()> {
+    let id = NodeId::from;
+    // distinct weights, so the minimum spanning tree is unique: 0-1, 1-2, 2-3 and
+    // 3-4, while 1-3, 0-2 and 2-4 each close a cycle.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 2.0),
+        (0, 2, 5.0),
+        (2, 3, 3.0),
+        (1, 3, 4.0),
+        (3, 4, 6.0),
+        (2, 4, 7.0),
+    ])?;
+    let tree = graph.minimum_bottleneck_spanning_tree()?;
+    assert_eq!(
+        tree,
+        vec![
+            (id(0), id(1), 1.0),
+            (id(1), id(2), 2.0),
+            (id(2), id(3), 3.0),
+            (id(3), id(4), 6.0),
+        ]
+    );
+    // the heaviest edge of the minimum spanning tree.
+    assert_eq!(graph.bottleneck()?, 6.0);
+    // edges lighter than the bottleneck leave 4 out.
+    let mut light_edges: Vec<(i64, i64, f64)> = Vec::new();
+    for node in graph.get_nodes_iter() {
+        for e in &node.edges {
+            if node.node_id < e.target_id && e.weight < 6.0 {
+                light_edges.push((node.node_id.value(), e.target_id.value(), e.weight));
+            }
+        }
+    }
+    let light = WeightedUndirectedGraphBuilder {}.from_vector(light_edges)?;
+    assert!(!light.has_node(id(4)));
+
+    let disconnected =
+        WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (2, 3, 1.0)])?;
+    assert!(matches!(
+        disconnected.minimum_bottleneck_spanning_tree(),
+        Err(CLQError::NotConnected)
+    ));
+    assert!(disconnected.bottleneck().is_err());
+    assert!(matches!(
+        WeightedUndirectedGraph::create_empty().minimum_bottleneck_spanning_tree(),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    let graph =
+        WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (1, 2, f64::NAN)])?;
+    assert!(matches!(
+        graph.minimum_bottleneck_spanning_tree(),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_weighted_spectral_bipartition() -> CLQResult<()> {
+    let id = NodeId::from;
+    // a triangle 0-2 and a 4-clique 3-6, joined by a bridge between 2 and 3.
+    let dumbbell = |bridge_weight: f64| -> CLQResult<WeightedUndirectedGraph> {
+        let mut rows: Vec<(i64, i64, f64)> = vec![(2, 3, bridge_weight)];
+        for (start, end) in [(0, 3), (3, 7)] {
+            for i in start..end {
+                for j in (i + 1)..end {
+                    rows.push((i, j, 1.0));
+                }
+            }
+        }
+        WeightedUndirectedGraphBuilder {}.from_vector(rows)
+    };
+
+    let graph = dumbbell(1.0)?;
+    let laplacian = graph.weighted_laplacian_matrix();
+    assert_eq!(laplacian.nrows(), 7);
+    // node 2 has two edges within the triangle, plus the bridge.
+    assert_eq!(laplacian[(2, 2)], 3.0);
+    assert_eq!(laplacian[(2, 3)], -1.0);
+    assert_eq!(laplacian[(0, 4)], 0.0);
+    for i in 0..7 {
+        assert!(laplacian.row(i).sum().abs() <= 1e-12);
+    }
+
+    // a light bridge is the cheapest cut.
+    let (left, right) = dumbbell(0.1)?.weighted_spectral_bipartition();
+    assert_eq!(left, vec![id(0), id(1), id(2)]);
+    assert_eq!(right, vec![id(3), id(4), id(5), id(6)]);
+
+    // a heavy bridge keeps its two ends together.
+    let (left, right) = dumbbell(50.0)?.weighted_spectral_bipartition();
+    assert_eq!(left.len() + right.len(), 7);
+    assert_eq!(left, vec![id(0), id(1)]);
+    assert_eq!(right, vec![id(2), id(3), id(4), id(5), id(6)]);
+    Ok(())
+}
+
+#[test]
+fn test_weight_hierarchy() -> CLQResult<()> {
+    // two weight 3.0 triangles (0, 1, 2) and (3, 4, 5), joined by weight 2.0 edges,
+    // with 6 hanging off 5 through a weight 1.0 edge and a weight 2.0 edge inside
+    // the first triangle, which doesn't change anything.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 3.0),
+        (1, 2, 3.0),
+        (2, 0, 2.0),
+        (3, 4, 3.0),
+        (4, 5, 3.0),
+        (5, 3, 3.0),
+        (2, 3, 2.0),
+        (1, 4, 2.0),
+        (5, 6, 1.0),
+    ])?;
+    assert_eq!(
+        graph.weight_hierarchy()?,
+        vec![(3.0, 3), (2.0, 2), (1.0, 1)]
+    );
+
+    // repeated edges and edges within a component, such as (3, 0), merge nothing.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (2, 3, 1.0),
+        (0, 1, 1.0),
+        (1, 2, 0.5),
+        (3, 0, 0.25),
+        (4, 5, 0.25),
+    ])?;
+    assert_eq!(
+        graph.weight_hierarchy()?,
+        vec![(1.0, 4), (0.5, 3), (0.25, 2)]
+    );
+    assert_eq!(graph.get_connected_components().len(), 2);
+
+    let graph =
+        WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (1, 2, f64::NAN)])?;
+    assert!(matches!(
+        graph.weight_hierarchy(),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_knn_graph() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // two tight clusters of three points, far apart.
+    let points: HashMap<NodeId, Vec<f64>> = [
+        (0, vec![0.0, 0.0]),
+        (1, vec![1.0, 0.0]),
+        (2, vec![0.0, 1.0]),
+        (3, vec![10.0, 10.0]),
+        (4, vec![11.0, 10.0]),
+        (5, vec![10.0, 12.0]),
+    ]
+    .into_iter()
+    .map(|(x, v)| (id(x), v))
+    .collect();
+    let graph = knn_graph(&points, 2, Metric::Euclidean)?;
+    assert_eq!(graph.count_nodes(), 6);
+    assert_eq!(graph.count_edges(), 6);
+    for node in graph.get_nodes_iter() {
+        assert!(node.edges.len() <= 2);
+        let cluster = node.node_id.value() / 3;
+        assert!(node
+            .edges
+            .iter()
+            .all(|e| e.target_id.value() / 3 == cluster));
+    }
+    let weight = |a: i64, b: i64| {
+        graph
+            .get_node(id(a))
+            .edges
+            .iter()
+            .find(|e| e.target_id == id(b))
+            .unwrap()
+            .weight
+    };
+    assert_eq!(weight(0, 1), 0.5);
+    assert!((weight(1, 2) - 1.0 / (1.0 + 2.0_f64.sqrt())).abs() <= 1e-12);
+    // every node keeps an edge to its nearest neighbor.
+    let graph = knn_graph(&points, 1, Metric::Euclidean)?;
+    assert!(graph.get_nodes_iter().all(|node| !node.edges.is_empty()));
+    assert!(graph.get_node(id(5)).neighbors.contains(&id(3)));
+
+    // by angle, (1, 0) and (10, 1) are much closer than (1, 0) and (1, 1).
+    let directions: HashMap<NodeId, Vec<f64>> = [
+        (0, vec![1.0, 0.0]),
+        (1, vec![10.0, 1.0]),
+        (2, vec![1.0, 1.0]),
+        (3, vec![0.0, 3.0]),
+    ]
+    .into_iter()
+    .map(|(x, v)| (id(x), v))
+    .collect();
+    let graph = knn_graph(&directions, 1, Metric::Cosine)?;
+    assert!(graph.get_node(id(0)).neighbors.contains(&id(1)));
+    assert!(graph.get_node(id(3)).neighbors.contains(&id(2)));
+    assert!(!graph.get_node(id(0)).neighbors.contains(&id(2)));
+
+    assert!(knn_graph(&points, 0, Metric::Euclidean).is_err());
+    let mut ragged = points.clone();
+    ragged.insert(id(6), vec![1.0]);
+    assert!(knn_graph(&ragged, 2, Metric::Euclidean).is_err());
+    let mut with_zero = directions;
+    with_zero.insert(id(4), vec![0.0, 0.0]);
+    assert!(knn_graph(&with_zero, 2, Metric::Cosine).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_weighted_diameter() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // path 0-1-2-3-4 with unit weights, and 5 hanging off 2 through a weight 10.0
+    // edge. By hops the diameter is between 0 and 4, by weight it ends at 5.
+    let mut edges: Vec<(i64, i64, f64)> = vec![
+        (0, 1, 1.0),
+        (1, 2, 1.0),
+        (2, 3, 1.0),
+        (3, 4, 1.0),
+        (2, 5, 10.0),
+    ];
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges.clone())?;
+    let hops = graph.get_hop_distances(id(0));
+    assert_eq!(hops[&id(4)], 4);
+    assert_eq!(*hops.values().max().unwrap(), 4);
+    assert_eq!(graph.get_hop_distances(id(5)).values().max(), Some(&3));
+
+    assert_eq!(graph.weighted_eccentricity(id(0))?, Some(12.0));
+    assert_eq!(graph.weighted_eccentricity(id(2))?, Some(10.0));
+    assert_eq!(graph.weighted_eccentricity(id(5))?, Some(12.0));
+    assert_eq!(graph.weighted_eccentricity(id(9))?, None);
+    assert_eq!(graph.weighted_diameter()?, Some(12.0));
+    assert_eq!(graph.weighted_diameter_largest_component()?, Some(12.0));
+
+    // a heavier but smaller component only counts when it's the whole graph.
+    edges.push((6, 7, 100.0));
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+    assert_eq!(graph.weighted_eccentricity(id(0))?, None);
+    assert_eq!(graph.weighted_diameter()?, None);
+    assert_eq!(graph.weighted_diameter_largest_component()?, Some(12.0));
+    // between components of the same size, the one with the smallest node wins.
+    let tied = WeightedUndirectedGraphBuilder {}.from_vector(vec![(2, 3, 5.0), (0, 1, 1.0)])?;
+    assert_eq!(tied.weighted_diameter_largest_component()?, Some(1.0));
+
+    assert!(matches!(
+        graph.get_weighted_distances(id(9)),
+        Err(CLQError::NodeNotFound(_))
+    ));
+    let nan = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (1, 2, f64::NAN)])?;
+    assert!(matches!(
+        nan.get_weighted_distances(id(0)),
+        Err(CLQError::InvalidParameter(_))
+    ));
+    assert!(nan.weighted_eccentricity(id(0)).is_err());
+    assert!(nan.weighted_diameter().is_err());
+    assert!(nan.weighted_diameter_largest_component().is_err());
+    assert!(nan.weighted_closeness_centrality().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_weighted_label_propagation() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // K4 on {1, 2, 4, 5} and a triangle on {3, 6, 7}, with weight 3.0 edges. Node 0
+    // has two light (1.0) edges into the K4, and one heavy (5.0) edge to 3.
+    let mut edges: Vec<(i64, i64, f64)> = vec![(0, 1, 1.0), (0, 2, 1.0), (0, 3, 5.0)];
+    for (a, b) in [
+        (1, 2),
+        (1, 4),
+        (1, 5),
+        (2, 4),
+        (2, 5),
+        (4, 5),
+        (3, 6),
+        (3, 7),
+        (6, 7),
+    ] {
+        edges.push((a, b, 3.0));
+    }
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+    for seed in 0..10 {
+        let communities = graph.weighted_label_propagation(100, seed);
+        assert_eq!(communities.len(), 8);
+        // numbered by smallest id: 0 and the triangle come first.
+        for x in [0, 3, 6, 7] {
+            assert_eq!(communities[&id(x)], 0);
+        }
+        for x in [1, 2, 4, 5] {
+            assert_eq!(communities[&id(x)], 1);
+        }
+    }
+    // no passes: every node on its own.
+    let singletons = graph.weighted_label_propagation(0, 0);
+    assert_eq!(
+        singletons.values().collect::<HashSet<&usize>>().len(),
+        graph.count_nodes()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_weighted_louvain() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // two K5s with weight 5.0 edges, on 0..5 and 5..10, joined by two light
+    // (0.1) bridges.
+    let mut edges: Vec<(i64, i64, f64)> = vec![(4, 5, 0.1), (0, 9, 0.1)];
+    for offset in [0, 5] {
+        for i in 0..5 {
+            for j in i + 1..5 {
+                edges.push((offset + i, offset + j, 5.0));
+            }
+        }
+    }
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+    let singletons: HashMap<NodeId, usize> = (0..10).map(|x| (id(x), x as usize)).collect();
+    for seed in 0..5 {
+        let communities = graph.weighted_louvain(1.0, seed);
+        for x in 0..10 {
+            assert_eq!(communities[&id(x)], (x / 5) as usize);
+        }
+        assert!(graph.weighted_modularity(&communities) > graph.weighted_modularity(&singletons));
+        assert!(graph.weighted_modularity(&communities) > 0.45);
+    }
+    // a very low resolution merges everything.
+    let merged = graph.weighted_louvain(0.001, 0);
+    assert!(merged.values().all(|c| *c == 0));
+    Ok(())
+}
+
+#[test]
+fn test_louvain_on_planted_cliques() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    let (num_cliques, clique_size) = (6, 5);
+    let caveman =
+        SimpleUndirectedGraphBuilder {}.get_connected_caveman_graph(num_cliques, clique_size)?;
+    let ring =
+        SimpleUndirectedGraphBuilder {}.get_ring_of_cliques_graph(num_cliques, clique_size)?;
+    // rewiring keeps the number of edges of the disjoint cliques.
+    assert_eq!(caveman.count_edges(), 60);
+    assert!(!caveman.get_node(id(0)).neighbors.contains(&id(1)));
+    assert!(caveman.get_node(id(0)).neighbors.contains(&id(29)));
+    assert_eq!(ring.count_edges(), 66);
+    assert!(ring.get_node(id(1)).neighbors.contains(&id(5)));
+    assert!(ring.get_node(id(26)).neighbors.contains(&id(0)));
+    for graph in [caveman, ring] {
+        assert_eq!(graph.count_nodes(), 30);
+        assert!(graph.get_is_connected()?);
+        let unit_weights = WeightedUndirectedGraphBuilder {}.from_vector(
+            graph
+                .get_nodes_iter()
+                .flat_map(|node| {
+                    node.neighbors
+                        .iter()
+                        .filter(move |x| node.node_id < **x)
+                        .map(move |x| (node.node_id.value(), x.value(), 1.0))
+                })
+                .collect(),
+        )?;
+        for seed in 0..3 {
+            let communities = unit_weights.weighted_louvain(1.0, seed);
+            for x in 0..30 {
+                assert_eq!(communities[&id(x)], (x / 5) as usize);
+            }
+        }
+    }
+
+    assert!(SimpleUndirectedGraphBuilder {}
+        .get_ring_of_cliques_graph(1, 5)
+        .is_err());
+    assert!(SimpleUndirectedGraphBuilder {}
+        .get_connected_caveman_graph(4, 1)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_disparity_backbone() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // hub 0 with a dominating edge to 1 and light edges to 2..=6, which form a path
+    // 2-3-4-5-6 with edges of weight 1.0 and 3.0 alternating.
+    let mut edges: Vec<(i64, i64, f64)> = vec![(0, 1, 20.0)];
+    for leaf in 2..=6 {
+        edges.push((0, leaf, 1.0));
+    }
+    edges.extend([(2, 3, 1.0), (3, 4, 3.0), (4, 5, 1.0), (5, 6, 3.0)]);
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+
+    let mut last_count = graph.count_edges();
+    let mut counts: Vec<usize> = Vec::new();
+    for alpha in [1.0, 0.5, 0.3, 0.1, 0.01, 0.001] {
+        let backbone = graph.disparity_backbone(alpha)?;
+        assert_eq!(backbone.count_nodes(), graph.count_nodes());
+        assert!(backbone.count_edges() <= last_count);
+        last_count = backbone.count_edges();
+        counts.push(last_count);
+        // (1 - 20 / 25)^5 = 0.00032
+        assert!(backbone.get_node(id(0)).neighbors.contains(&id(1)));
+        for node in backbone.get_nodes_iter() {
+            for e in &node.edges {
+                assert!(backbone
+                    .get_node(e.target_id)
+                    .neighbors
+                    .contains(&node.node_id));
+            }
+        }
+    }
+    assert!(counts[0] > counts[5]);
+    assert_eq!(counts[5], 1);
+    assert!(graph.disparity_backbone(0.0).is_err());
+    assert!(graph.disparity_backbone(2.0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_weighted_coreness_peeling_trace() -> CLQResult<()> {
+    let ids = |x: &[i64]| {
+        x.iter()
+            .map(|id| NodeId::from(*id))
+            .collect::<Vec<NodeId>>()
+    };
+    // path 0-1-2-3-4 with weights 1.0, 5.0, 5.0, 2.0: strengths 1, 6, 10, 7, 2.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 5.0),
+        (2, 3, 5.0),
+        (3, 4, 2.0),
+    ])?;
+    let trace = graph.weighted_coreness_peeling_trace();
+    assert_eq!(
+        trace,
+        vec![
+            (1.0, ids(&[0])),
+            (2.0, ids(&[4])),
+            (5.0, ids(&[1, 3])),
+            (5.0, ids(&[2])),
+        ]
+    );
+
+    // rounds agree with the fractional coreness of their nodes.
+    for idx in [4, 5, 6, 7] {
+        let graph = get_graph(idx)?;
+        let coreness = graph.get_fractional_coreness_values();
+        let trace = graph.weighted_coreness_peeling_trace();
+        assert_eq!(
+            trace.iter().map(|(_, x)| x.len()).sum::<usize>(),
+            graph.count_nodes()
+        );
+        for (threshold, removed) in trace {
+            for id in removed {
+                assert!((coreness[&id] - threshold).abs() <= 1e-9);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_weighted_transitivity() -> CLQResult<()> {
+    // a square 0-1-2-3 with the diagonal 0-2, a pendant 3-4 and a triangle 4-5-6.
+    let edges = [
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 0),
+        (0, 2),
+        (3, 4),
+        (4, 5),
+        (5, 6),
+        (6, 4),
+    ];
+    let unit = WeightedUndirectedGraphBuilder {}
+        .from_vector(edges.iter().map(|(a, b)| (*a, *b, 1.0)).collect())?;
+    assert!(unit.get_transitivity() > 0.0 && unit.get_transitivity() < 1.0);
+    assert!((unit.weighted_transitivity() - unit.get_transitivity()).abs() < 1e-12);
+    // scaling all weights changes nothing.
+    let scaled = WeightedUndirectedGraphBuilder {}
+        .from_vector(edges.iter().map(|(a, b)| (*a, *b, 3.0)).collect())?;
+    assert!((scaled.weighted_transitivity() - unit.get_transitivity()).abs() < 1e-12);
+
+    // a triangle 0-1-2 with a heavy edge 0-1 and a heavier pendant 2-3. Triples
+    // centered on 0 and 1 are worth sqrt(4 * 1) = 2 and closed; those on 2 are
+    // worth 1 (closed, to 0 and 1) and sqrt(1 * 9) = 3 twice (open, to 3).
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 4.0),
+        (1, 2, 1.0),
+        (0, 2, 1.0),
+        (2, 3, 9.0),
+    ])?;
+    assert!((graph.get_transitivity() - 0.6).abs() < 1e-12);
+    assert!((graph.weighted_transitivity() - 5.0 / 11.0).abs() < 1e-12);
+
+    // no triangles.
+    let star = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (0, 2, 2.0)])?;
+    assert_eq!(star.weighted_transitivity(), 0.0);
+    assert_eq!(
+        WeightedUndirectedGraph::create_empty().weighted_transitivity(),
+        0.0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_max_weight_independent_set_tree() -> CLQResult<()> {
+    let id = |x: i64| NodeId::from(x);
+    // a weighted path 0-1-2-3-4: node weights are given separately, and the edge
+    // weights don't matter.
+    let path = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 10.0),
+        (1, 2, 0.5),
+        (2, 3, 3.0),
+        (3, 4, 1.0),
+    ])?;
+    let node_weights: HashMap<NodeId, f64> = [(0, 1.0), (1, 4.0), (2, 2.0), (3, 5.0), (4, 1.0)]
+        .into_iter()
+        .map(|(x, w)| (id(x), w))
+        .collect();
+    assert_eq!(
+        path.max_weight_independent_set_tree(&node_weights)?,
+        (vec![id(1), id(3)], 9.0)
+    );
+    // a weighted star, where the leaves together outweigh the hub.
+    let star = WeightedUndirectedGraphBuilder {}
+        .from_vector((1..=3).map(|x| (0, x, x as f64)).collect())?;
+    let node_weights: HashMap<NodeId, f64> = [(0, 2.5), (1, 1.0), (2, 1.0), (3, 1.0)]
+        .into_iter()
+        .map(|(x, w)| (id(x), w))
+        .collect();
+    assert_eq!(
+        star.max_weight_independent_set_tree(&node_weights)?,
+        (vec![id(1), id(2), id(3)], 3.0)
+    );
+    Ok(())
+}